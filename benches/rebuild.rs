@@ -0,0 +1,57 @@
+#![feature(test)]
+
+extern crate test;
+
+use dary_heap::DaryHeap;
+use rand::seq::SliceRandom;
+use test::Bencher;
+
+/// Returns a `rand::Rng` seeded with a consistent seed.
+///
+/// This is done to avoid introducing nondeterminism in benchmark results.
+fn bench_rng() -> rand_xorshift::XorShiftRng {
+    const SEED: [u8; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+    rand::SeedableRng::from_seed(SEED)
+}
+
+/// Returns the integers `0..n` in random order.
+fn integers(n: u32) -> Vec<u32> {
+    let mut vec: Vec<u32> = (0..n).collect();
+    vec.shuffle(&mut bench_rng());
+    vec
+}
+
+/// Returns `n` distinct strings with a common prefix in random order, which
+/// makes comparisons more expensive than moves.
+fn strings(n: u32) -> Vec<&'static str> {
+    let mut vec: Vec<&'static str> = (0..n)
+        .map(|i| &*Box::leak(format!("item-{:010}", i).into_boxed_str()))
+        .collect();
+    vec.shuffle(&mut bench_rng());
+    vec
+}
+
+macro_rules! bench_from_vec {
+    ($($name:ident: $d:literal, $data:expr;)*) => {$(
+        #[bench]
+        fn $name(b: &mut Bencher) {
+            let data = $data;
+            b.iter(|| DaryHeap::<_, $d>::from(data.clone()))
+        }
+    )*};
+}
+
+bench_from_vec! {
+    from_vec_1k_d2: 2, integers(1_000);
+    from_vec_1k_d4: 4, integers(1_000);
+    from_vec_1k_d8: 8, integers(1_000);
+    from_vec_1k_d16: 16, integers(1_000);
+    from_vec_1m_d2: 2, integers(1_000_000);
+    from_vec_1m_d4: 4, integers(1_000_000);
+    from_vec_1m_d8: 8, integers(1_000_000);
+    from_vec_1m_d16: 16, integers(1_000_000);
+    from_vec_strings_100k_d2: 2, strings(100_000);
+    from_vec_strings_100k_d4: 4, strings(100_000);
+    from_vec_strings_100k_d8: 8, strings(100_000);
+    from_vec_strings_100k_d16: 16, strings(100_000);
+}
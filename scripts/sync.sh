@@ -25,8 +25,25 @@ current="$(git rev-parse "${current_rev}")" \
 new="$(git rev-parse "${new_rev}")" \
     || { echo "New git revision (${new_rev}) not found"; exit 1; }
 
+lib_diff="$(mktemp)" || { echo "Could not create temporary file"; exit 1; }
+trap 'rm -f "${lib_diff}"' EXIT
+
 git diff "${current}" "${new}" "${alloc}/src/collections/binary_heap/mod.rs" \
-    | patch --merge=diff3 "${base}/../src/lib.rs"
+    > "${lib_diff}"
+patch --merge=diff3 "${base}/../src/lib.rs" < "${lib_diff}"
+# The sifting, rebuilding and sorting routines and the Hole type of
+# binary_heap/mod.rs live in src/sift.rs as free functions on slices, so hunks
+# touching them do not apply to src/lib.rs and have to be ported by hand.
+sift_hunks="$(awk '
+    /^@@/ { if (hit) printf "%s", hunk; hunk = ""; hit = 0 }
+    /^@@/ || hunk != "" { hunk = hunk $0 "\n" }
+    /^[-+ ].*(Hole|sift_|rebuild|into_sorted_vec)/ { hit = 1 }
+    END { if (hit) printf "%s", hunk }
+' "${lib_diff}")"
+if [ -n "${sift_hunks}" ]; then
+    echo "The following hunks touch code that lives in src/sift.rs:"
+    echo "${sift_hunks}"
+fi
 git diff "${current}" "${new}" "${alloc}/src/testing/crash_test.rs" \
     | patch --merge=diff3 "${base}/../tests/binary_heap/crash_test.rs"
 git diff "${current}" "${new}" "${alloc}/src/collections/binary_heap/tests.rs" \
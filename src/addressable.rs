@@ -0,0 +1,669 @@
+//! A *d*-ary heap with handles to its items.
+//!
+//! The [`AddressableDaryHeap`] type in this module returns a [`Handle`] for
+//! every pushed item. The handle can be used to look up, change or remove the
+//! item later on, which is needed for example for the decrease-key operation
+//! of Dijkstra's algorithm and A* search.
+
+use core::fmt;
+use core::iter::FusedIterator;
+use core::slice;
+
+use alloc::vec::Vec;
+
+/// A handle to an item in an [`AddressableDaryHeap`].
+///
+/// A handle stays valid until its item is removed from the heap, and does not
+/// refer to any item afterwards, even if other items are pushed later. Using a
+/// handle with a heap other than the one that created it is a logic error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Handle {
+    index: usize,
+    generation: usize,
+}
+
+#[derive(Clone, Debug)]
+struct Entry<T> {
+    item: T,
+    slot: usize,
+}
+
+#[derive(Clone, Debug)]
+struct Slot {
+    /// Position of the item in the heap, or `FREE` if the slot is unused.
+    pos: usize,
+    generation: usize,
+}
+
+const FREE: usize = usize::MAX;
+
+/// A priority queue implemented with a *d*-ary heap, with handles to its items.
+///
+/// This will be a max-heap. Apart from the handles, it behaves like
+/// [`DaryHeap`]; see its documentation for more information. Keeping track of
+/// the position of every item makes operations slower by a constant factor.
+///
+/// [`DaryHeap`]: crate::DaryHeap
+///
+/// # Examples
+///
+/// ```
+/// use dary_heap::addressable::AddressableDaryHeap;
+///
+/// let mut heap = AddressableDaryHeap::<_, 4>::new();
+/// let a = heap.push((1, 'a'));
+/// let b = heap.push((5, 'b'));
+/// heap.push((3, 'c'));
+///
+/// assert_eq!(heap.peek(), Some(&(5, 'b')));
+/// heap.update(b, (0, 'b'));
+/// heap.update(a, (9, 'a'));
+/// assert_eq!(heap.remove(a), Some((9, 'a')));
+/// assert_eq!(heap.get(a), None);
+/// assert_eq!(heap.pop(), Some((3, 'c')));
+/// assert_eq!(heap.pop(), Some((0, 'b')));
+/// assert_eq!(heap.pop(), None);
+/// ```
+pub struct AddressableDaryHeap<T, const D: usize> {
+    data: Vec<Entry<T>>,
+    slots: Vec<Slot>,
+    free: Vec<usize>,
+}
+
+impl<T: Clone, const D: usize> Clone for AddressableDaryHeap<T, D> {
+    fn clone(&self) -> Self {
+        AddressableDaryHeap {
+            data: self.data.clone(),
+            slots: self.slots.clone(),
+            free: self.free.clone(),
+        }
+    }
+}
+
+impl<T: Ord, const D: usize> Default for AddressableDaryHeap<T, D> {
+    /// Creates an empty `AddressableDaryHeap<T, D>`.
+    #[inline]
+    fn default() -> AddressableDaryHeap<T, D> {
+        AddressableDaryHeap::new()
+    }
+}
+
+impl<T: fmt::Debug, const D: usize> fmt::Debug for AddressableDaryHeap<T, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Ord, const D: usize> AddressableDaryHeap<T, D> {
+    /// Creates an empty `AddressableDaryHeap` as a max-heap.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::addressable::AddressableDaryHeap;
+    /// let mut heap = AddressableDaryHeap::<_, 3>::new();
+    /// heap.push(4);
+    /// ```
+    #[must_use]
+    pub fn new() -> AddressableDaryHeap<T, D> {
+        AddressableDaryHeap::with_capacity(0)
+    }
+
+    /// Creates an empty `AddressableDaryHeap` with at least the specified
+    /// capacity.
+    ///
+    /// The *d*-ary heap will be able to hold at least `capacity` elements without
+    /// reallocating. This method is allowed to allocate for more elements than
+    /// `capacity`. If `capacity` is 0, the *d*-ary heap will not allocate.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::addressable::AddressableDaryHeap;
+    /// let mut heap = AddressableDaryHeap::<_, 3>::with_capacity(10);
+    /// heap.push(4);
+    /// ```
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> AddressableDaryHeap<T, D> {
+        AddressableDaryHeap {
+            data: Vec::with_capacity(capacity),
+            slots: Vec::with_capacity(capacity),
+            free: Vec::new(),
+        }
+    }
+
+    /// Pushes an item onto the *d*-ary heap and returns a handle to it.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::addressable::AddressableDaryHeap;
+    /// let mut heap = AddressableDaryHeap::<_, 2>::new();
+    /// let handle = heap.push(3);
+    /// heap.push(5);
+    ///
+    /// assert_eq!(heap.len(), 2);
+    /// assert_eq!(heap.get(handle), Some(&3));
+    /// assert_eq!(heap.peek(), Some(&5));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The worst case cost of `push` on a heap containing *n* elements is
+    /// *O*(log(*n*)).
+    pub fn push(&mut self, item: T) -> Handle {
+        let pos = self.data.len();
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.slots[index].pos = pos;
+                index
+            }
+            None => {
+                self.slots.push(Slot { pos, generation: 0 });
+                self.slots.len() - 1
+            }
+        };
+        self.data.push(Entry { item, slot: index });
+        self.sift_up(pos);
+        Handle {
+            index,
+            generation: self.slots[index].generation,
+        }
+    }
+
+    /// Removes the greatest item from the *d*-ary heap and returns it, or `None` if it
+    /// is empty. Its handle becomes invalid.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::addressable::AddressableDaryHeap;
+    /// let mut heap = AddressableDaryHeap::<_, 2>::new();
+    /// heap.push(1);
+    /// heap.push(3);
+    ///
+    /// assert_eq!(heap.pop(), Some(3));
+    /// assert_eq!(heap.pop(), Some(1));
+    /// assert_eq!(heap.pop(), None);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The worst case cost of `pop` on a heap containing *n* elements is *O*(log(*n*)).
+    pub fn pop(&mut self) -> Option<T> {
+        if self.data.is_empty() {
+            None
+        } else {
+            Some(self.remove_pos(0))
+        }
+    }
+
+    /// Removes the item that `handle` refers to from the *d*-ary heap and
+    /// returns it, or `None` if the handle is no longer valid.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::addressable::AddressableDaryHeap;
+    /// let mut heap = AddressableDaryHeap::<_, 2>::new();
+    /// let handle = heap.push(1);
+    /// heap.push(3);
+    ///
+    /// assert_eq!(heap.remove(handle), Some(1));
+    /// assert_eq!(heap.remove(handle), None);
+    /// assert_eq!(heap.len(), 1);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The worst case cost of `remove` on a heap containing *n* elements is
+    /// *O*(log(*n*)).
+    pub fn remove(&mut self, handle: Handle) -> Option<T> {
+        let pos = self.position(handle)?;
+        Some(self.remove_pos(pos))
+    }
+
+    /// Replaces the item that `handle` refers to by `item`, moves it to its
+    /// new place in the *d*-ary heap and returns the old item.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `handle` is no longer valid.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::addressable::AddressableDaryHeap;
+    /// let mut heap = AddressableDaryHeap::<_, 2>::new();
+    /// let handle = heap.push(1);
+    /// heap.push(3);
+    ///
+    /// assert_eq!(heap.update(handle, 5), 1);
+    /// assert_eq!(heap.peek(), Some(&5));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The worst case cost of `update` on a heap containing *n* elements is
+    /// *O*(log(*n*)).
+    pub fn update(&mut self, handle: Handle, item: T) -> T {
+        let pos = self.expect_position(handle);
+        let old = core::mem::replace(&mut self.data[pos].item, item);
+        let pos = self.sift_up(pos);
+        self.sift_down(pos);
+        old
+    }
+
+    /// Replaces the item that `handle` refers to by a greater or equal `item`
+    /// and returns the old item.
+    ///
+    /// This only has to move the item towards the top of the heap, which is
+    /// cheaper than [`update`]. It is a logic error for `item` to be smaller
+    /// than the item it replaces.
+    ///
+    /// [`update`]: AddressableDaryHeap::update
+    ///
+    /// # Panics
+    ///
+    /// Panics if `handle` is no longer valid.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::addressable::AddressableDaryHeap;
+    /// let mut heap = AddressableDaryHeap::<_, 4>::new();
+    /// let handle = heap.push(1);
+    /// heap.push(3);
+    ///
+    /// heap.increase_key(handle, 4);
+    /// assert_eq!(heap.peek(), Some(&4));
+    /// ```
+    pub fn increase_key(&mut self, handle: Handle, item: T) -> T {
+        let pos = self.expect_position(handle);
+        debug_assert!(item >= self.data[pos].item);
+        let old = core::mem::replace(&mut self.data[pos].item, item);
+        self.sift_up(pos);
+        old
+    }
+
+    /// Replaces the item that `handle` refers to by a smaller or equal `item`
+    /// and returns the old item.
+    ///
+    /// This only has to move the item towards the bottom of the heap, which is
+    /// cheaper than [`update`]. It is a logic error for `item` to be greater
+    /// than the item it replaces.
+    ///
+    /// [`update`]: AddressableDaryHeap::update
+    ///
+    /// # Panics
+    ///
+    /// Panics if `handle` is no longer valid.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::addressable::AddressableDaryHeap;
+    /// let mut heap = AddressableDaryHeap::<_, 4>::new();
+    /// let handle = heap.push(5);
+    /// heap.push(3);
+    ///
+    /// heap.decrease_key(handle, 2);
+    /// assert_eq!(heap.peek(), Some(&3));
+    /// ```
+    pub fn decrease_key(&mut self, handle: Handle, item: T) -> T {
+        let pos = self.expect_position(handle);
+        debug_assert!(item <= self.data[pos].item);
+        let old = core::mem::replace(&mut self.data[pos].item, item);
+        self.sift_down(pos);
+        old
+    }
+
+    /// Changes the item that `handle` refers to in place and moves it to its
+    /// new place in the *d*-ary heap. Returns `false` if the handle is no
+    /// longer valid.
+    pub(crate) fn modify<F: FnOnce(&mut T)>(&mut self, handle: Handle, f: F) -> bool {
+        match self.position(handle) {
+            Some(pos) => {
+                f(&mut self.data[pos].item);
+                let pos = self.sift_up(pos);
+                self.sift_down(pos);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes the item at position `pos`, which must be in bounds.
+    fn remove_pos(&mut self, pos: usize) -> T {
+        let entry = self.data.swap_remove(pos);
+        let slot = &mut self.slots[entry.slot];
+        slot.pos = FREE;
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free.push(entry.slot);
+        if pos < self.data.len() {
+            self.slots[self.data[pos].slot].pos = pos;
+            let pos = self.sift_up(pos);
+            self.sift_down(pos);
+        }
+        entry.item
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        self.data.swap(a, b);
+        self.slots[self.data[a].slot].pos = a;
+        self.slots[self.data[b].slot].pos = b;
+    }
+
+    /// Moves the item at `pos` up while its parent is smaller, and returns its
+    /// new position.
+    fn sift_up(&mut self, mut pos: usize) -> usize {
+        assert_ne!(D, 0, "Arity should be greater than zero");
+        while pos > 0 {
+            let parent = (pos - 1) / D;
+            if self.data[pos].item <= self.data[parent].item {
+                break;
+            }
+            self.swap(pos, parent);
+            pos = parent;
+        }
+        pos
+    }
+
+    /// Moves the item at `pos` down while one of its children is larger.
+    fn sift_down(&mut self, mut pos: usize) {
+        assert_ne!(D, 0, "Arity should be greater than zero");
+        let len = self.data.len();
+        loop {
+            let first_child = D * pos + 1;
+            if first_child >= len {
+                return;
+            }
+            let mut child = first_child;
+            for other in first_child + 1..len.min(first_child + D) {
+                if self.data[child].item <= self.data[other].item {
+                    child = other;
+                }
+            }
+            if self.data[pos].item >= self.data[child].item {
+                return;
+            }
+            self.swap(pos, child);
+            pos = child;
+        }
+    }
+}
+
+impl<T, const D: usize> AddressableDaryHeap<T, D> {
+    fn position(&self, handle: Handle) -> Option<usize> {
+        let slot = self.slots.get(handle.index)?;
+        if slot.generation == handle.generation && slot.pos != FREE {
+            Some(slot.pos)
+        } else {
+            None
+        }
+    }
+
+    fn expect_position(&self, handle: Handle) -> usize {
+        self.position(handle)
+            .expect("handle does not refer to an item in the heap")
+    }
+
+    /// Returns the item that `handle` refers to, or `None` if the handle is no
+    /// longer valid.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::addressable::AddressableDaryHeap;
+    /// let mut heap = AddressableDaryHeap::<_, 2>::new();
+    /// let handle = heap.push(1);
+    ///
+    /// assert_eq!(heap.get(handle), Some(&1));
+    /// heap.pop();
+    /// assert_eq!(heap.get(handle), None);
+    /// ```
+    #[must_use]
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        self.position(handle).map(|pos| &self.data[pos].item)
+    }
+
+    /// Returns `true` if `handle` refers to an item in the *d*-ary heap.
+    #[must_use]
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.position(handle).is_some()
+    }
+
+    /// Returns the greatest item in the *d*-ary heap, or `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::addressable::AddressableDaryHeap;
+    /// let mut heap = AddressableDaryHeap::<_, 2>::new();
+    /// assert_eq!(heap.peek(), None);
+    ///
+    /// heap.push(1);
+    /// heap.push(5);
+    /// heap.push(2);
+    /// assert_eq!(heap.peek(), Some(&5));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Cost is *O*(1) in the worst case.
+    #[must_use]
+    pub fn peek(&self) -> Option<&T> {
+        self.data.first().map(|entry| &entry.item)
+    }
+
+    /// Returns the handle to the greatest item in the *d*-ary heap, or `None`
+    /// if it is empty.
+    #[must_use]
+    pub fn peek_handle(&self) -> Option<Handle> {
+        self.data.first().map(|entry| self.handle(entry))
+    }
+
+    fn handle(&self, entry: &Entry<T>) -> Handle {
+        Handle {
+            index: entry.slot,
+            generation: self.slots[entry.slot].generation,
+        }
+    }
+
+    /// Returns an iterator visiting all handles and values in arbitrary
+    /// order.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::addressable::AddressableDaryHeap;
+    /// let mut heap = AddressableDaryHeap::<_, 3>::new();
+    /// let handle = heap.push(1);
+    /// heap.push(2);
+    ///
+    /// assert!(heap.iter().any(|(h, &x)| h == handle && x == 1));
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            iter: self.data.iter(),
+            slots: &self.slots,
+        }
+    }
+
+    /// Returns the number of elements the *d*-ary heap can hold without reallocating.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.data.capacity()
+    }
+
+    /// Reserves capacity for at least `additional` elements more than the
+    /// current length. See [`DaryHeap::reserve`] for more information.
+    ///
+    /// [`DaryHeap::reserve`]: crate::DaryHeap::reserve
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity overflows [`usize`].
+    pub fn reserve(&mut self, additional: usize) {
+        self.data.reserve(additional);
+        self.slots
+            .reserve(additional.saturating_sub(self.free.len()));
+    }
+
+    /// Consumes the `AddressableDaryHeap` and returns a vector of its items
+    /// in arbitrary order.
+    #[must_use = "`self` will be dropped if the result is not used"]
+    pub fn into_vec(self) -> Vec<T> {
+        self.data.into_iter().map(|entry| entry.item).collect()
+    }
+
+    /// Returns the length of the *d*-ary heap.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Checks if the *d*-ary heap is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drops all items from the *d*-ary heap. All handles become invalid.
+    pub fn clear(&mut self) {
+        for entry in self.data.drain(..) {
+            let slot = &mut self.slots[entry.slot];
+            slot.pos = FREE;
+            slot.generation = slot.generation.wrapping_add(1);
+            self.free.push(entry.slot);
+        }
+    }
+}
+
+/// An iterator over the handles and elements of an `AddressableDaryHeap`.
+///
+/// This `struct` is created by [`AddressableDaryHeap::iter()`]. See its
+/// documentation for more.
+///
+/// [`iter`]: AddressableDaryHeap::iter
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Iter<'a, T: 'a> {
+    iter: slice::Iter<'a, Entry<T>>,
+    slots: &'a [Slot],
+}
+
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Iter {
+            iter: self.iter.clone(),
+            slots: self.slots,
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Iter<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Iter").field(&self.iter.as_slice()).finish()
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = (Handle, &'a T);
+
+    #[inline]
+    fn next(&mut self) -> Option<(Handle, &'a T)> {
+        let slots = self.slots;
+        self.iter.next().map(|entry| {
+            let handle = Handle {
+                index: entry.slot,
+                generation: slots[entry.slot].generation,
+            };
+            (handle, &entry.item)
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+impl<'a, T, const D: usize> IntoIterator for &'a AddressableDaryHeap<T, D> {
+    type Item = (Handle, &'a T);
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{thread_rng, Rng};
+
+    #[test]
+    fn random_operations() {
+        let mut rng = thread_rng();
+        let mut heap = AddressableDaryHeap::<u32, 3>::new();
+        let mut reference: Vec<(Handle, u32)> = Vec::new();
+        for _ in 0..2000 {
+            match rng.gen_range(0..5) {
+                0 | 1 => {
+                    let x = rng.gen_range(0..1000);
+                    reference.push((heap.push(x), x));
+                }
+                2 if !reference.is_empty() => {
+                    let i = rng.gen_range(0..reference.len());
+                    let x = rng.gen_range(0..1000);
+                    assert_eq!(heap.update(reference[i].0, x), reference[i].1);
+                    reference[i].1 = x;
+                }
+                3 if !reference.is_empty() => {
+                    let (handle, x) = reference.swap_remove(rng.gen_range(0..reference.len()));
+                    assert_eq!(heap.remove(handle), Some(x));
+                    assert_eq!(heap.get(handle), None);
+                }
+                _ => {
+                    let max = reference.iter().map(|&(_, x)| x).max();
+                    assert_eq!(heap.pop(), max);
+                    if let Some(max) = max {
+                        // Items with equal values may be popped in any order.
+                        let i = reference.iter().position(|&(h, _)| !heap.contains(h));
+                        assert_eq!(reference.swap_remove(i.unwrap()).1, max);
+                    }
+                }
+            }
+            assert_eq!(heap.len(), reference.len());
+            for &(handle, x) in &reference {
+                assert_eq!(heap.get(handle), Some(&x));
+            }
+        }
+    }
+}
@@ -0,0 +1,562 @@
+//! A *d*-ary heap with a custom allocator, based on [`allocator-api2`].
+//!
+//! The [`DaryHeapIn`] type in this module is a *d*-ary heap that stores its
+//! elements in an [`allocator_api2::vec::Vec`], which makes it possible to back
+//! the heap with arena or bump allocators on stable Rust. On a nightly
+//! compiler, enabling the `nightly` feature of `allocator-api2` makes its
+//! [`Allocator`] trait the same as the one in the standard library.
+//!
+//! [`allocator-api2`]: https://docs.rs/allocator-api2
+//! [`Allocator`]: allocator_api2::alloc::Allocator
+
+use core::fmt;
+use core::iter::FusedIterator;
+use core::mem::swap;
+
+use allocator_api2::alloc::{Allocator, Global};
+use allocator_api2::vec::{self, Vec};
+
+use crate::compare::Natural;
+use crate::{sift, Iter};
+
+/// A priority queue implemented with a *d*-ary heap, using a custom allocator.
+///
+/// This will be a max-heap. Apart from the allocator, it behaves like
+/// [`DaryHeap`]; see its documentation for more information.
+///
+/// [`DaryHeap`]: crate::DaryHeap
+///
+/// # Examples
+///
+/// ```
+/// use allocator_api2::alloc::Global;
+/// use dary_heap::allocator::DaryHeapIn;
+///
+/// let mut heap = DaryHeapIn::<_, 4>::new_in(Global);
+/// heap.push(1);
+/// heap.push(5);
+/// heap.push(2);
+///
+/// assert_eq!(heap.pop(), Some(5));
+/// assert_eq!(heap.pop(), Some(2));
+/// assert_eq!(heap.pop(), Some(1));
+/// assert_eq!(heap.pop(), None);
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "allocator-api2")))]
+pub struct DaryHeapIn<T, const D: usize, A: Allocator = Global> {
+    data: Vec<T, A>,
+}
+
+impl<T: Clone, const D: usize, A: Allocator + Clone> Clone for DaryHeapIn<T, D, A> {
+    fn clone(&self) -> Self {
+        DaryHeapIn {
+            data: self.data.clone(),
+        }
+    }
+}
+
+impl<T: Ord, const D: usize, A: Allocator + Default> Default for DaryHeapIn<T, D, A> {
+    /// Creates an empty `DaryHeapIn<T, D, A>` using the default allocator.
+    #[inline]
+    fn default() -> DaryHeapIn<T, D, A> {
+        DaryHeapIn::new_in(A::default())
+    }
+}
+
+impl<T: fmt::Debug, const D: usize, A: Allocator> fmt::Debug for DaryHeapIn<T, D, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Ord, const D: usize, A: Allocator> DaryHeapIn<T, D, A> {
+    /// Creates an empty `DaryHeapIn` as a max-heap, using `alloc` as allocator.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use allocator_api2::alloc::Global;
+    /// use dary_heap::allocator::DaryHeapIn;
+    /// let mut heap = DaryHeapIn::<_, 3>::new_in(Global);
+    /// heap.push(4);
+    /// ```
+    #[must_use]
+    pub fn new_in(alloc: A) -> DaryHeapIn<T, D, A> {
+        DaryHeapIn {
+            data: Vec::new_in(alloc),
+        }
+    }
+
+    /// Creates an empty `DaryHeapIn` with at least the specific capacity, using
+    /// `alloc` as allocator.
+    ///
+    /// The *d*-ary heap will be able to hold at least `capacity` elements without
+    /// reallocating. This method is allowed to allocate for more elements than
+    /// `capacity`. If `capacity` is 0, the *d*-ary heap will not allocate.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use allocator_api2::alloc::Global;
+    /// use dary_heap::allocator::DaryHeapIn;
+    /// let mut heap = DaryHeapIn::<_, 3>::with_capacity_in(10, Global);
+    /// heap.push(4);
+    /// ```
+    #[must_use]
+    pub fn with_capacity_in(capacity: usize, alloc: A) -> DaryHeapIn<T, D, A> {
+        DaryHeapIn {
+            data: Vec::with_capacity_in(capacity, alloc),
+        }
+    }
+
+    /// Removes the greatest item from the *d*-ary heap and returns it, or `None` if it
+    /// is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use allocator_api2::{alloc::Global, vec};
+    /// use dary_heap::allocator::DaryHeapIn;
+    /// let mut heap = DaryHeapIn::<_, 2>::from(vec![in Global; 1, 3]);
+    ///
+    /// assert_eq!(heap.pop(), Some(3));
+    /// assert_eq!(heap.pop(), Some(1));
+    /// assert_eq!(heap.pop(), None);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The worst case cost of `pop` on a heap containing *n* elements is *O*(log(*n*)).
+    pub fn pop(&mut self) -> Option<T> {
+        self.data.pop().map(|mut item| {
+            if !self.is_empty() {
+                swap(&mut item, &mut self.data[0]);
+                // SAFETY: !self.is_empty() means that self.len() > 0
+                unsafe { sift::sift_down_to_bottom(&mut self.data, sift::Const::<D>, &Natural, 0) };
+            }
+            item
+        })
+    }
+
+    /// Pushes an item onto the *d*-ary heap.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use allocator_api2::alloc::Global;
+    /// use dary_heap::allocator::DaryHeapIn;
+    /// let mut heap = DaryHeapIn::<_, 4>::new_in(Global);
+    /// heap.push(3);
+    /// heap.push(5);
+    /// heap.push(1);
+    ///
+    /// assert_eq!(heap.len(), 3);
+    /// assert_eq!(heap.peek(), Some(&5));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The expected cost of `push`, averaged over every possible ordering of
+    /// the elements being pushed, and over a sufficiently large number of
+    /// pushes, is *O*(1). See [`DaryHeap::push`] for a more detailed analysis.
+    ///
+    /// [`DaryHeap::push`]: crate::DaryHeap::push
+    pub fn push(&mut self, item: T) {
+        let old_len = self.len();
+        self.data.push(item);
+        // SAFETY: Since we pushed a new item it means that
+        //  old_len = self.len() - 1 < self.len()
+        unsafe { sift::sift_up(&mut self.data, sift::Const::<D>, &Natural, 0, old_len) };
+    }
+
+    /// Consumes the `DaryHeapIn` and returns a vector in sorted
+    /// (ascending) order, using the same allocator.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use allocator_api2::{alloc::Global, vec};
+    /// use dary_heap::allocator::DaryHeapIn;
+    ///
+    /// let mut heap = DaryHeapIn::<_, 8>::from(vec![in Global; 1, 2, 4, 5, 7]);
+    /// heap.push(6);
+    /// heap.push(3);
+    ///
+    /// let vec = heap.into_sorted_vec();
+    /// assert_eq!(vec, [1, 2, 3, 4, 5, 6, 7]);
+    /// ```
+    #[must_use = "`self` will be dropped if the result is not used"]
+    pub fn into_sorted_vec(mut self) -> Vec<T, A> {
+        sift::sort_heap(&mut self.data, sift::Const::<D>, &Natural);
+        self.into_vec()
+    }
+
+    /// Moves all the elements of `other` into `self`, leaving `other` empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use allocator_api2::{alloc::Global, vec};
+    /// use dary_heap::allocator::DaryHeapIn;
+    ///
+    /// let mut a = DaryHeapIn::<_, 3>::from(vec![in Global; -10, 1, 2, 3, 3]);
+    /// let mut b = DaryHeapIn::<_, 3>::from(vec![in Global; -20, 5, 43]);
+    ///
+    /// a.append(&mut b);
+    ///
+    /// assert_eq!(a.into_sorted_vec(), [-20, -10, 1, 2, 3, 3, 5, 43]);
+    /// assert!(b.is_empty());
+    /// ```
+    pub fn append(&mut self, other: &mut Self) {
+        let start = self.data.len();
+        self.data.append(&mut other.data);
+        sift::rebuild_tail(&mut self.data, sift::Const::<D>, &Natural, start);
+    }
+}
+
+impl<T, const D: usize, A: Allocator> DaryHeapIn<T, D, A> {
+    /// Returns an iterator visiting all values in the underlying vector, in
+    /// arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use allocator_api2::{alloc::Global, vec};
+    /// use dary_heap::allocator::DaryHeapIn;
+    /// let heap = DaryHeapIn::<_, 3>::from(vec![in Global; 1, 2, 3, 4]);
+    ///
+    /// // Print 1, 2, 3, 4 in arbitrary order
+    /// for x in heap.iter() {
+    ///     println!("{x}");
+    /// }
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            iter: self.data.iter(),
+        }
+    }
+
+    /// Returns the greatest item in the *d*-ary heap, or `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use allocator_api2::alloc::Global;
+    /// use dary_heap::allocator::DaryHeapIn;
+    /// let mut heap = DaryHeapIn::<_, 2>::new_in(Global);
+    /// assert_eq!(heap.peek(), None);
+    ///
+    /// heap.push(1);
+    /// heap.push(5);
+    /// heap.push(2);
+    /// assert_eq!(heap.peek(), Some(&5));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Cost is *O*(1) in the worst case.
+    #[must_use]
+    pub fn peek(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    /// Returns a reference to the underlying allocator.
+    #[must_use]
+    pub fn allocator(&self) -> &A {
+        self.data.allocator()
+    }
+
+    /// Returns the number of elements the *d*-ary heap can hold without reallocating.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.data.capacity()
+    }
+
+    /// Reserves the minimum capacity for at least `additional` elements more than
+    /// the current length. See [`DaryHeap::reserve_exact`] for more information.
+    ///
+    /// [`DaryHeap::reserve_exact`]: crate::DaryHeap::reserve_exact
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity overflows [`usize`].
+    pub fn reserve_exact(&mut self, additional: usize) {
+        self.data.reserve_exact(additional);
+    }
+
+    /// Reserves capacity for at least `additional` elements more than the
+    /// current length. See [`DaryHeap::reserve`] for more information.
+    ///
+    /// [`DaryHeap::reserve`]: crate::DaryHeap::reserve
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity overflows [`usize`].
+    pub fn reserve(&mut self, additional: usize) {
+        self.data.reserve(additional);
+    }
+
+    /// Discards as much additional capacity as possible.
+    pub fn shrink_to_fit(&mut self) {
+        self.data.shrink_to_fit();
+    }
+
+    /// Returns a slice of all values in the underlying vector, in arbitrary
+    /// order.
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        self.data.as_slice()
+    }
+
+    /// Consumes the `DaryHeapIn` and returns the underlying vector
+    /// in arbitrary order.
+    #[must_use = "`self` will be dropped if the result is not used"]
+    pub fn into_vec(self) -> Vec<T, A> {
+        self.data
+    }
+
+    /// Returns the length of the *d*-ary heap.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Checks if the *d*-ary heap is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Clears the *d*-ary heap, returning an iterator over the removed elements
+    /// in arbitrary order. If the iterator is dropped before being fully
+    /// consumed, it drops the remaining elements in arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use allocator_api2::{alloc::Global, vec};
+    /// use dary_heap::allocator::DaryHeapIn;
+    /// let mut heap = DaryHeapIn::<_, 4>::from(vec![in Global; 1, 3]);
+    ///
+    /// assert!(!heap.is_empty());
+    ///
+    /// for x in heap.drain() {
+    ///     println!("{x}");
+    /// }
+    ///
+    /// assert!(heap.is_empty());
+    /// ```
+    #[inline]
+    pub fn drain(&mut self) -> Drain<'_, T, A> {
+        Drain {
+            iter: self.data.drain(..),
+        }
+    }
+
+    /// Drops all items from the *d*-ary heap.
+    pub fn clear(&mut self) {
+        self.drain();
+    }
+}
+
+/// An owning iterator over the elements of a `DaryHeapIn`.
+///
+/// This `struct` is created by [`DaryHeapIn::into_iter()`]
+/// (provided by the [`IntoIterator`] trait). See its documentation for more.
+///
+/// [`into_iter`]: DaryHeapIn::into_iter
+#[cfg_attr(docsrs, doc(cfg(feature = "allocator-api2")))]
+pub struct IntoIter<T, A: Allocator = Global> {
+    iter: vec::IntoIter<T, A>,
+}
+
+impl<T: fmt::Debug, A: Allocator> fmt::Debug for IntoIter<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IntoIter")
+            .field(&self.iter.as_slice())
+            .finish()
+    }
+}
+
+impl<T, A: Allocator> Iterator for IntoIter<T, A> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.iter.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T, A: Allocator> DoubleEndedIterator for IntoIter<T, A> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        self.iter.next_back()
+    }
+}
+
+impl<T, A: Allocator> ExactSizeIterator for IntoIter<T, A> {}
+
+impl<T, A: Allocator> FusedIterator for IntoIter<T, A> {}
+
+/// A draining iterator over the elements of a `DaryHeapIn`.
+///
+/// This `struct` is created by [`DaryHeapIn::drain()`]. See its
+/// documentation for more.
+///
+/// [`drain`]: DaryHeapIn::drain
+#[cfg_attr(docsrs, doc(cfg(feature = "allocator-api2")))]
+pub struct Drain<'a, T: 'a, A: Allocator = Global> {
+    iter: vec::Drain<'a, T, A>,
+}
+
+impl<T: fmt::Debug, A: Allocator> fmt::Debug for Drain<'_, T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Drain").field(&self.iter.as_slice()).finish()
+    }
+}
+
+impl<T, A: Allocator> Iterator for Drain<'_, T, A> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.iter.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T, A: Allocator> DoubleEndedIterator for Drain<'_, T, A> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        self.iter.next_back()
+    }
+}
+
+impl<T, A: Allocator> ExactSizeIterator for Drain<'_, T, A> {}
+
+impl<T, A: Allocator> FusedIterator for Drain<'_, T, A> {}
+
+impl<T: Ord, const D: usize, A: Allocator> From<Vec<T, A>> for DaryHeapIn<T, D, A> {
+    /// Converts a `Vec<T, A>` into a `DaryHeapIn<T, D, A>`.
+    ///
+    /// This conversion happens in-place, and has *O*(*n*) time complexity.
+    fn from(vec: Vec<T, A>) -> DaryHeapIn<T, D, A> {
+        let mut heap = DaryHeapIn { data: vec };
+        sift::rebuild(&mut heap.data, sift::Const::<D>, &Natural);
+        heap
+    }
+}
+
+impl<T, const D: usize, A: Allocator> From<DaryHeapIn<T, D, A>> for Vec<T, A> {
+    /// Converts a `DaryHeapIn<T, D, A>` into a `Vec<T, A>`.
+    ///
+    /// This conversion requires no data movement or allocation, and has
+    /// constant time complexity.
+    fn from(heap: DaryHeapIn<T, D, A>) -> Vec<T, A> {
+        heap.data
+    }
+}
+
+impl<T, const D: usize, A: Allocator> IntoIterator for DaryHeapIn<T, D, A> {
+    type Item = T;
+    type IntoIter = IntoIter<T, A>;
+
+    /// Creates a consuming iterator, that is, one that moves each value out of
+    /// the *d*-ary heap in arbitrary order. The allocation is freed using the
+    /// allocator of the heap.
+    fn into_iter(self) -> IntoIter<T, A> {
+        IntoIter {
+            iter: self.data.into_iter(),
+        }
+    }
+}
+
+impl<'a, T, const D: usize, A: Allocator> IntoIterator for &'a DaryHeapIn<T, D, A> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<T: Ord, const D: usize, A: Allocator> Extend<T> for DaryHeapIn<T, D, A> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let start = self.data.len();
+        self.data.extend(iter);
+        sift::rebuild_tail(&mut self.data, sift::Const::<D>, &Natural, start);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use allocator_api2::alloc::{AllocError, Layout};
+    use core::cell::Cell;
+    use core::ptr::NonNull;
+    use rand::{seq::SliceRandom, thread_rng};
+
+    /// Allocator that counts the number of live allocations.
+    struct Counting<'a>(&'a Cell<usize>);
+
+    unsafe impl Allocator for Counting<'_> {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            self.0.set(self.0.get() + 1);
+            Global.allocate(layout)
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            self.0.set(self.0.get() - 1);
+            Global.deallocate(ptr, layout)
+        }
+    }
+
+    #[test]
+    fn pop_with_allocator() {
+        let live = Cell::new(0);
+        let nelem = if cfg!(miri) { 100 } else { 1000 };
+        let mut data: alloc::vec::Vec<_> = (0..nelem).collect();
+        data.shuffle(&mut thread_rng());
+
+        let mut heap = DaryHeapIn::<_, 4, _>::new_in(Counting(&live));
+        heap.extend(data);
+        assert!(live.get() > 0);
+        for i in (0..nelem).rev() {
+            assert_eq!(heap.pop(), Some(i));
+        }
+        assert_eq!(heap.pop(), None);
+
+        heap.push(1);
+        let mut drained = DaryHeapIn::<_, 4, _>::from(heap.into_sorted_vec());
+        assert_eq!(drained.drain().collect::<alloc::vec::Vec<_>>(), [1]);
+        drop(drained);
+        assert_eq!(live.get(), 0);
+    }
+}
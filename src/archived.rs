@@ -0,0 +1,153 @@
+//! Zero-copy (de)serialization using [`rkyv`].
+//!
+//! A [`DaryHeap`] is archived as an [`ArchivedDaryHeap`], which stores the
+//! items in the same order as the heap. Since the archived items still satisfy
+//! the heap property, the greatest item can be read directly from the archived
+//! bytes, without deserializing the heap first.
+//!
+//! [`rkyv`]: https://docs.rs/rkyv
+//!
+//! # Examples
+//!
+//! ```
+//! use dary_heap::QuaternaryHeap;
+//! use rkyv::rancor::Error;
+//!
+//! let heap = QuaternaryHeap::from(vec![1, 5, 2]);
+//! let bytes = rkyv::to_bytes::<Error>(&heap).unwrap();
+//!
+//! let archived = rkyv::access::<rkyv::Archived<QuaternaryHeap<i32>>, Error>(&bytes).unwrap();
+//! assert_eq!(archived.peek().map(|x| x.to_native()), Some(5));
+//! assert_eq!(archived.len(), 3);
+//!
+//! let heap = rkyv::deserialize::<QuaternaryHeap<i32>, Error>(archived).unwrap();
+//! assert_eq!(heap.into_sorted_vec(), [1, 2, 5]);
+//! ```
+
+use core::fmt;
+
+use alloc::vec::Vec;
+
+use rkyv::munge::munge;
+use rkyv::rancor::Fallible;
+use rkyv::ser::{Allocator, Writer};
+use rkyv::vec::{ArchivedVec, VecResolver};
+use rkyv::{Archive, Deserialize, Place, Portable, Serialize};
+
+use crate::{DaryHeap, Iter};
+
+/// An archived [`DaryHeap`].
+///
+/// The items are stored in the order of the original heap, so they form a
+/// valid *d*-ary heap as long as the ordering of the archived items agrees
+/// with the ordering of the original items.
+#[derive(Portable, rkyv::bytecheck::CheckBytes)]
+#[bytecheck(crate = rkyv::bytecheck)]
+#[rkyv(crate = rkyv)]
+#[repr(transparent)]
+pub struct ArchivedDaryHeap<T, const D: usize> {
+    data: ArchivedVec<T>,
+}
+
+impl<T, const D: usize> ArchivedDaryHeap<T, D> {
+    /// Returns the greatest item in the archived *d*-ary heap, or `None` if it
+    /// is empty.
+    ///
+    /// # Time complexity
+    ///
+    /// Cost is *O*(1) in the worst case.
+    #[must_use]
+    pub fn peek(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    /// Returns an iterator visiting all values in the archived *d*-ary heap,
+    /// in arbitrary order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            iter: self.data.iter(),
+        }
+    }
+
+    /// Returns a slice of all values in the archived *d*-ary heap, in
+    /// arbitrary order.
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        self.data.as_slice()
+    }
+
+    /// Returns the length of the archived *d*-ary heap.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Checks if the archived *d*-ary heap is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+}
+
+impl<T: fmt::Debug, const D: usize> fmt::Debug for ArchivedDaryHeap<T, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Archive, const D: usize> Archive for DaryHeap<T, D> {
+    type Archived = ArchivedDaryHeap<T::Archived, D>;
+    type Resolver = VecResolver;
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        munge!(let ArchivedDaryHeap { data } = out);
+        ArchivedVec::resolve_from_slice(self.data.as_slice(), resolver, data);
+    }
+}
+
+impl<T, S, const D: usize> Serialize<S> for DaryHeap<T, D>
+where
+    T: Serialize<S>,
+    S: Fallible + Allocator + Writer + ?Sized,
+{
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedVec::<T::Archived>::serialize_from_slice(self.data.as_slice(), serializer)
+    }
+}
+
+impl<T, De, const D: usize> Deserialize<DaryHeap<T, D>, De> for ArchivedDaryHeap<T::Archived, D>
+where
+    T: Ord + Archive,
+    ArchivedVec<T::Archived>: Deserialize<Vec<T>, De>,
+    De: Fallible + ?Sized,
+{
+    fn deserialize(&self, deserializer: &mut De) -> Result<DaryHeap<T, D>, De::Error> {
+        self.data.deserialize(deserializer).map(DaryHeap::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DaryHeap, TernaryHeap};
+    use rand::{thread_rng, Rng};
+    use rkyv::rancor::Error;
+
+    #[test]
+    fn round_trip() {
+        let mut rng = thread_rng();
+        let heap: TernaryHeap<u32> = (0..100).map(|_| rng.gen()).collect();
+        let bytes = rkyv::to_bytes::<Error>(&heap).unwrap();
+
+        let archived = rkyv::access::<rkyv::Archived<TernaryHeap<u32>>, Error>(&bytes).unwrap();
+        assert_eq!(archived.len(), heap.len());
+        assert_eq!(archived.peek().map(|x| x.to_native()), heap.peek().copied());
+        assert!(archived
+            .iter()
+            .map(|x| x.to_native())
+            .eq(heap.iter().copied()));
+
+        let deserialized: DaryHeap<u32, 3> = rkyv::deserialize::<_, Error>(archived).unwrap();
+        assert_eq!(deserialized.as_slice(), heap.as_slice());
+        deserialized.assert_valid_state();
+    }
+}
@@ -0,0 +1,446 @@
+//! A fixed-capacity *d*-ary heap that never allocates.
+//!
+//! The [`ArrayDaryHeap`] type in this module stores its elements inline in an
+//! array, so its capacity is fixed at compile time and it never touches the
+//! heap. This makes it suitable as a bounded priority queue in environments
+//! without a global allocator: the module remains available when the default
+//! `alloc` feature is disabled.
+
+use core::fmt;
+use core::iter::FusedIterator;
+use core::mem::{swap, ManuallyDrop, MaybeUninit};
+use core::ptr;
+use core::slice;
+
+use crate::compare::Natural;
+use crate::{sift, Iter};
+
+/// A fixed-capacity priority queue implemented with a *d*-ary heap.
+///
+/// This will be a max-heap that can hold at most `N` elements, stored inline
+/// without allocating. Apart from [`push`] being fallible, it behaves like
+/// [`DaryHeap`]; see its documentation for more information.
+///
+/// [`push`]: ArrayDaryHeap::push
+/// [`DaryHeap`]: crate::DaryHeap
+///
+/// # Examples
+///
+/// ```
+/// use dary_heap::array::ArrayDaryHeap;
+///
+/// let mut heap = ArrayDaryHeap::<_, 3, 4>::new();
+/// assert_eq!(heap.push(1), Ok(()));
+/// assert_eq!(heap.push(5), Ok(()));
+/// assert_eq!(heap.push(2), Ok(()));
+///
+/// // The heap is full now
+/// assert_eq!(heap.push(3), Err(3));
+///
+/// assert_eq!(heap.pop(), Some(5));
+/// assert_eq!(heap.pop(), Some(2));
+/// assert_eq!(heap.pop(), Some(1));
+/// assert_eq!(heap.pop(), None);
+/// ```
+pub struct ArrayDaryHeap<T, const N: usize, const D: usize> {
+    data: [MaybeUninit<T>; N],
+    len: usize,
+}
+
+impl<T: Clone, const N: usize, const D: usize> Clone for ArrayDaryHeap<T, N, D> {
+    fn clone(&self) -> Self {
+        let mut heap = ArrayDaryHeap::empty();
+        for item in self.as_slice() {
+            heap.data[heap.len] = MaybeUninit::new(item.clone());
+            heap.len += 1;
+        }
+        heap
+    }
+}
+
+impl<T: Ord, const N: usize, const D: usize> Default for ArrayDaryHeap<T, N, D> {
+    /// Creates an empty `ArrayDaryHeap<T, N, D>`.
+    #[inline]
+    fn default() -> ArrayDaryHeap<T, N, D> {
+        ArrayDaryHeap::new()
+    }
+}
+
+impl<T: fmt::Debug, const N: usize, const D: usize> fmt::Debug for ArrayDaryHeap<T, N, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T, const N: usize, const D: usize> Drop for ArrayDaryHeap<T, N, D> {
+    fn drop(&mut self) {
+        // SAFETY: the first `len` elements are initialized and not used again
+        unsafe { ptr::drop_in_place(self.as_mut_slice()) };
+    }
+}
+
+impl<T: Ord, const N: usize, const D: usize> ArrayDaryHeap<T, N, D> {
+    /// Creates an empty `ArrayDaryHeap` as a max-heap.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::array::ArrayDaryHeap;
+    /// let mut heap = ArrayDaryHeap::<_, 16, 3>::new();
+    /// heap.push(4).unwrap();
+    /// ```
+    #[must_use]
+    pub fn new() -> ArrayDaryHeap<T, N, D> {
+        ArrayDaryHeap::empty()
+    }
+
+    /// Removes the greatest item from the *d*-ary heap and returns it, or `None` if it
+    /// is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::array::ArrayDaryHeap;
+    /// let mut heap = ArrayDaryHeap::<_, 2, 2>::from([1, 3]);
+    ///
+    /// assert_eq!(heap.pop(), Some(3));
+    /// assert_eq!(heap.pop(), Some(1));
+    /// assert_eq!(heap.pop(), None);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The worst case cost of `pop` on a heap containing *n* elements is *O*(log(*n*)).
+    pub fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        // SAFETY: the element at the old last position was initialized and is
+        //  no longer considered part of the heap
+        let mut item = unsafe { ptr::read(self.data[self.len].as_ptr()) };
+        if !self.is_empty() {
+            let data = self.as_mut_slice();
+            swap(&mut item, &mut data[0]);
+            // SAFETY: !self.is_empty() means that self.len() > 0
+            unsafe { sift::sift_down_to_bottom(data, sift::Const::<D>, &Natural, 0) };
+        }
+        Some(item)
+    }
+
+    /// Pushes an item onto the *d*-ary heap, or returns it as an error if the
+    /// heap is full.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::array::ArrayDaryHeap;
+    /// let mut heap = ArrayDaryHeap::<_, 3, 4>::new();
+    /// heap.push(3).unwrap();
+    /// heap.push(5).unwrap();
+    /// heap.push(1).unwrap();
+    ///
+    /// assert_eq!(heap.len(), 3);
+    /// assert_eq!(heap.peek(), Some(&5));
+    /// assert_eq!(heap.push(7), Err(7));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The expected cost of `push`, averaged over every possible ordering of
+    /// the elements being pushed, and over a sufficiently large number of
+    /// pushes, is *O*(1). The worst case cost is *O*(log(*n*)).
+    pub fn push(&mut self, item: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(item);
+        }
+        let old_len = self.len;
+        self.data[old_len] = MaybeUninit::new(item);
+        self.len += 1;
+        // SAFETY: Since we pushed a new item it means that
+        //  old_len = self.len() - 1 < self.len()
+        unsafe { sift::sift_up(self.as_mut_slice(), sift::Const::<D>, &Natural, 0, old_len) };
+        Ok(())
+    }
+}
+
+impl<T, const N: usize, const D: usize> ArrayDaryHeap<T, N, D> {
+    fn empty() -> ArrayDaryHeap<T, N, D> {
+        ArrayDaryHeap {
+            // SAFETY: an array of `MaybeUninit` does not require initialization
+            data: unsafe { MaybeUninit::<[MaybeUninit<T>; N]>::uninit().assume_init() },
+            len: 0,
+        }
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        // SAFETY: the first `len` elements are initialized
+        unsafe { slice::from_raw_parts_mut(self.data.as_mut_ptr() as *mut T, self.len) }
+    }
+
+    /// Returns an iterator visiting all values in the underlying array, in
+    /// arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::array::ArrayDaryHeap;
+    /// let heap = ArrayDaryHeap::<_, 4, 3>::from([1, 2, 3, 4]);
+    ///
+    /// // Print 1, 2, 3, 4 in arbitrary order
+    /// for x in heap.iter() {
+    ///     println!("{}", x);
+    /// }
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            iter: self.as_slice().iter(),
+        }
+    }
+
+    /// Returns the greatest item in the *d*-ary heap, or `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::array::ArrayDaryHeap;
+    /// let mut heap = ArrayDaryHeap::<_, 4, 2>::new();
+    /// assert_eq!(heap.peek(), None);
+    ///
+    /// heap.push(1).unwrap();
+    /// heap.push(5).unwrap();
+    /// heap.push(2).unwrap();
+    /// assert_eq!(heap.peek(), Some(&5));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Cost is *O*(1) in the worst case.
+    #[must_use]
+    pub fn peek(&self) -> Option<&T> {
+        self.as_slice().first()
+    }
+
+    /// Returns the number of elements the *d*-ary heap can hold, which is `N`.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns a slice of all values in the underlying array, in arbitrary
+    /// order.
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        // SAFETY: the first `len` elements are initialized
+        unsafe { slice::from_raw_parts(self.data.as_ptr() as *const T, self.len) }
+    }
+
+    /// Returns the length of the *d*-ary heap.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if the *d*-ary heap is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Checks if the *d*-ary heap is full, in which case [`push`] fails.
+    ///
+    /// [`push`]: ArrayDaryHeap::push
+    #[must_use]
+    pub fn is_full(&self) -> bool {
+        self.len() == N
+    }
+
+    /// Drops all items from the *d*-ary heap.
+    pub fn clear(&mut self) {
+        let len = self.len;
+        // Set the length first, so a panicking destructor leaks instead of
+        // leaving dropped elements in the heap.
+        self.len = 0;
+        // SAFETY: the first `len` elements were initialized
+        unsafe {
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(
+                self.data.as_mut_ptr() as *mut T,
+                len,
+            ))
+        };
+    }
+}
+
+/// An owning iterator over the elements of an `ArrayDaryHeap`.
+///
+/// This `struct` is created by [`ArrayDaryHeap::into_iter()`]
+/// (provided by the [`IntoIterator`] trait). See its documentation for more.
+///
+/// [`into_iter`]: ArrayDaryHeap::into_iter
+pub struct IntoIter<T, const N: usize> {
+    data: [MaybeUninit<T>; N],
+    start: usize,
+    end: usize,
+}
+
+impl<T, const N: usize> IntoIter<T, N> {
+    fn as_slice(&self) -> &[T] {
+        // SAFETY: the elements in `start..end` are initialized
+        unsafe {
+            slice::from_raw_parts(
+                (self.data.as_ptr() as *const T).add(self.start),
+                self.end - self.start,
+            )
+        }
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for IntoIter<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IntoIter").field(&self.as_slice()).finish()
+    }
+}
+
+impl<T, const N: usize> Drop for IntoIter<T, N> {
+    fn drop(&mut self) {
+        let remaining = self.as_slice() as *const [T] as *mut [T];
+        // SAFETY: the remaining elements are initialized and not used again
+        unsafe { ptr::drop_in_place(remaining) };
+    }
+}
+
+impl<T, const N: usize> Iterator for IntoIter<T, N> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        if self.start == self.end {
+            return None;
+        }
+        self.start += 1;
+        // SAFETY: the element was initialized and is no longer in `start..end`
+        Some(unsafe { ptr::read(self.data[self.start - 1].as_ptr()) })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.start;
+        (len, Some(len))
+    }
+}
+
+impl<T, const N: usize> DoubleEndedIterator for IntoIter<T, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        if self.start == self.end {
+            return None;
+        }
+        self.end -= 1;
+        // SAFETY: the element was initialized and is no longer in `start..end`
+        Some(unsafe { ptr::read(self.data[self.end].as_ptr()) })
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for IntoIter<T, N> {}
+
+impl<T, const N: usize> FusedIterator for IntoIter<T, N> {}
+
+impl<T: Ord, const N: usize, const D: usize> From<[T; N]> for ArrayDaryHeap<T, N, D> {
+    /// Converts a `[T; N]` into a full `ArrayDaryHeap<T, N, D>`.
+    ///
+    /// This conversion has *O*(*n*) time complexity.
+    ///
+    /// ```
+    /// use dary_heap::array::ArrayDaryHeap;
+    ///
+    /// let mut h1 = ArrayDaryHeap::<_, 4, 3>::from([1, 4, 2, 3]);
+    /// let mut h2 = ArrayDaryHeap::<_, 4, 3>::new();
+    /// for &x in &[1, 4, 2, 3] {
+    ///     h2.push(x).unwrap();
+    /// }
+    /// while let Some((a, b)) = h1.pop().zip(h2.pop()) {
+    ///     assert_eq!(a, b);
+    /// }
+    /// ```
+    fn from(arr: [T; N]) -> ArrayDaryHeap<T, N, D> {
+        let arr = ManuallyDrop::new(arr);
+        let mut heap = ArrayDaryHeap::empty();
+        // SAFETY: `[T; N]` and `[MaybeUninit<T>; N]` have the same layout, and
+        //  the original array is not dropped
+        heap.data = unsafe { ptr::read(&*arr as *const [T; N] as *const [MaybeUninit<T>; N]) };
+        heap.len = N;
+        sift::rebuild(heap.as_mut_slice(), sift::Const::<D>, &Natural);
+        heap
+    }
+}
+
+impl<T, const N: usize, const D: usize> IntoIterator for ArrayDaryHeap<T, N, D> {
+    type Item = T;
+    type IntoIter = IntoIter<T, N>;
+
+    /// Creates a consuming iterator, that is, one that moves each value out of
+    /// the *d*-ary heap in arbitrary order.
+    fn into_iter(self) -> IntoIter<T, N> {
+        let heap = ManuallyDrop::new(self);
+        IntoIter {
+            // SAFETY: the heap is not dropped, so ownership of its elements is
+            //  transferred to the iterator
+            data: unsafe { ptr::read(&heap.data) },
+            start: 0,
+            end: heap.len,
+        }
+    }
+}
+
+impl<'a, T, const N: usize, const D: usize> IntoIterator for &'a ArrayDaryHeap<T, N, D> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::DaryHeap;
+    use alloc::vec::Vec;
+    use rand::{seq::SliceRandom, thread_rng};
+
+    #[test]
+    fn push_pop_full() {
+        let mut data: Vec<_> = (0..100).collect();
+        data.shuffle(&mut thread_rng());
+
+        let mut heap = ArrayDaryHeap::<_, 64, 3>::new();
+        let mut reference = DaryHeap::<_, 3>::new();
+        for (i, &x) in data.iter().enumerate() {
+            if i < 64 {
+                assert_eq!(heap.push(x), Ok(()));
+                reference.push(x);
+            } else {
+                assert_eq!(heap.push(x), Err(x));
+            }
+        }
+        assert!(heap.is_full());
+        for _ in 0..32 {
+            assert_eq!(heap.pop(), reference.pop());
+        }
+        let mut rest: Vec<_> = heap.clone().into_iter().collect();
+        rest.sort_unstable();
+        assert_eq!(rest, reference.into_sorted_vec());
+        heap.clear();
+        assert_eq!(heap.pop(), None);
+    }
+}
@@ -0,0 +1,372 @@
+//! An asynchronous *d*-ary heap.
+//!
+//! The [`AsyncDaryHeap`] type in this module is a priority queue whose
+//! [`pop`] method returns a future that resolves once an item is available.
+//! It does not depend on a specific runtime, and can be shared between tasks,
+//! e.g. using an [`Arc`].
+//!
+//! [`pop`]: AsyncDaryHeap::pop
+//! [`Arc`]: std::sync::Arc
+
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+
+use alloc::collections::VecDeque;
+
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use crate::DaryHeap;
+
+/// An asynchronous priority queue implemented with a *d*-ary heap.
+///
+/// This will be a max-heap. Awaiting [`pop`] suspends the current task while
+/// the heap is empty, until another task or thread pushes an item. Every
+/// pushed item wakes up one waiting task, and a task that is woken up but
+/// dropped before taking the item passes the wake-up on to the next waiting
+/// task, so no wake-ups are lost.
+///
+/// [`pop`]: AsyncDaryHeap::pop
+///
+/// # Examples
+///
+/// ```
+/// use dary_heap::async_heap::AsyncDaryHeap;
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// let heap = Arc::new(AsyncDaryHeap::<_, 4>::new());
+///
+/// let producer = {
+///     let heap = Arc::clone(&heap);
+///     thread::spawn(move || heap.push(5))
+/// };
+///
+/// assert_eq!(pollster::block_on(heap.pop()), 5);
+/// producer.join().unwrap();
+/// ```
+pub struct AsyncDaryHeap<T, const D: usize> {
+    state: Mutex<State<T, D>>,
+}
+
+struct State<T, const D: usize> {
+    heap: DaryHeap<T, D>,
+    /// Wakers of the pending [`Pop`] futures, in order of registration.
+    waiters: VecDeque<(u64, Waker)>,
+    /// The identifier for the next [`Pop`] future that registers a waker.
+    next_id: u64,
+}
+
+impl<T, const D: usize> State<T, D> {
+    /// Removes the waker of the first waiting task, if any, to be woken up
+    /// after unlocking.
+    fn wake_one(&mut self) -> Option<Waker> {
+        self.waiters.pop_front().map(|(_, waker)| waker)
+    }
+}
+
+impl<T, const D: usize> AsyncDaryHeap<T, D> {
+    /// Creates an empty `AsyncDaryHeap<T, D>`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::async_heap::AsyncDaryHeap;
+    /// let heap = AsyncDaryHeap::<_, 4>::new();
+    /// heap.push(4);
+    /// ```
+    #[must_use]
+    pub fn new() -> AsyncDaryHeap<T, D> {
+        AsyncDaryHeap::from(DaryHeap::new())
+    }
+
+    /// Returns the length of the *d*-ary heap.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lock().heap.len()
+    }
+
+    /// Checks if the *d*-ary heap is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.lock().heap.is_empty()
+    }
+
+    /// Consumes the `AsyncDaryHeap<T, D>` and returns the underlying
+    /// `DaryHeap<T, D>`.
+    pub fn into_inner(self) -> DaryHeap<T, D> {
+        let state = self
+            .state
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner);
+        state.heap
+    }
+
+    /// Locks the state, ignoring poisoning. A panic while the lock is held can
+    /// only come from comparing items, which leaves the heap in an unspecified
+    /// but valid state.
+    fn lock(&self) -> MutexGuard<'_, State<T, D>> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T: Ord, const D: usize> AsyncDaryHeap<T, D> {
+    /// Pushes an item onto the *d*-ary heap, waking up one task waiting in
+    /// [`pop`].
+    ///
+    /// [`pop`]: AsyncDaryHeap::pop
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::async_heap::AsyncDaryHeap;
+    /// let heap = AsyncDaryHeap::<_, 2>::new();
+    /// heap.push(3);
+    /// heap.push(5);
+    ///
+    /// assert_eq!(heap.len(), 2);
+    /// assert_eq!(heap.try_pop(), Some(5));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Apart from waiting for the lock, the cost is that of
+    /// [`DaryHeap::push`].
+    pub fn push(&self, item: T) {
+        let mut state = self.lock();
+        state.heap.push(item);
+        let waker = state.wake_one();
+        drop(state);
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+
+    /// Returns a future that removes the greatest item from the *d*-ary heap
+    /// once it is not empty, and resolves to it.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::async_heap::AsyncDaryHeap;
+    /// let heap = AsyncDaryHeap::<_, 3>::new();
+    /// heap.push(1);
+    /// heap.push(3);
+    ///
+    /// pollster::block_on(async {
+    ///     assert_eq!(heap.pop().await, 3);
+    ///     assert_eq!(heap.pop().await, 1);
+    /// });
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Apart from waiting, the cost is that of [`DaryHeap::pop`].
+    pub fn pop(&self) -> Pop<'_, T, D> {
+        Pop {
+            heap: self,
+            id: None,
+        }
+    }
+
+    /// Removes the greatest item from the *d*-ary heap and returns it, or
+    /// `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::async_heap::AsyncDaryHeap;
+    /// let heap = AsyncDaryHeap::<_, 2>::new();
+    /// assert_eq!(heap.try_pop(), None);
+    ///
+    /// heap.push(1);
+    /// assert_eq!(heap.try_pop(), Some(1));
+    /// ```
+    pub fn try_pop(&self) -> Option<T> {
+        self.lock().heap.pop()
+    }
+}
+
+impl<T, const D: usize> Default for AsyncDaryHeap<T, D> {
+    /// Creates an empty `AsyncDaryHeap<T, D>`.
+    #[inline]
+    fn default() -> AsyncDaryHeap<T, D> {
+        AsyncDaryHeap::new()
+    }
+}
+
+impl<T: fmt::Debug, const D: usize> fmt::Debug for AsyncDaryHeap<T, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsyncDaryHeap")
+            .field("heap", &self.lock().heap)
+            .finish()
+    }
+}
+
+impl<T, const D: usize> From<DaryHeap<T, D>> for AsyncDaryHeap<T, D> {
+    /// Converts a `DaryHeap<T, D>` into an `AsyncDaryHeap<T, D>`.
+    ///
+    /// This conversion does not move any items.
+    fn from(heap: DaryHeap<T, D>) -> AsyncDaryHeap<T, D> {
+        AsyncDaryHeap {
+            state: Mutex::new(State {
+                heap,
+                waiters: VecDeque::new(),
+                next_id: 0,
+            }),
+        }
+    }
+}
+
+/// Future that removes the greatest item from an `AsyncDaryHeap`.
+///
+/// This `struct` is created by [`AsyncDaryHeap::pop()`]. See its
+/// documentation for more.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Pop<'a, T: Ord, const D: usize> {
+    heap: &'a AsyncDaryHeap<T, D>,
+    /// The identifier of the waker of this future, if it has registered one.
+    id: Option<u64>,
+}
+
+impl<T: Ord, const D: usize> Future for Pop<'_, T, D> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let mut state = self.heap.lock();
+        if let Some(item) = state.heap.pop() {
+            if let Some(id) = self.id.take() {
+                state.waiters.retain(|(other, _)| *other != id);
+            }
+            return Poll::Ready(item);
+        }
+
+        let registered = self
+            .id
+            .and_then(|id| state.waiters.iter_mut().find(|(other, _)| *other == id));
+        match registered {
+            Some((_, waker)) => {
+                if !waker.will_wake(cx.waker()) {
+                    *waker = cx.waker().clone();
+                }
+            }
+            None => {
+                // Either this future has not registered yet, or it was woken
+                // up but another task took the item first.
+                let id = state.next_id;
+                state.next_id += 1;
+                state.waiters.push_back((id, cx.waker().clone()));
+                self.id = Some(id);
+            }
+        }
+        Poll::Pending
+    }
+}
+
+impl<T: Ord, const D: usize> Drop for Pop<'_, T, D> {
+    fn drop(&mut self) {
+        if let Some(id) = self.id {
+            let mut state = self.heap.lock();
+            let len = state.waiters.len();
+            state.waiters.retain(|(other, _)| *other != id);
+            // If this future was woken up for an item it never took, pass the
+            // wake-up on to the next waiting task.
+            let waker = if state.waiters.len() == len && !state.heap.is_empty() {
+                state.wake_one()
+            } else {
+                None
+            };
+            drop(state);
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        }
+    }
+}
+
+impl<T: Ord + fmt::Debug, const D: usize> fmt::Debug for Pop<'_, T, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pop").field("heap", &self.heap).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::sync::Arc;
+    use alloc::vec::Vec;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+    use std::task::Wake;
+    use std::thread;
+
+    struct CountingWaker(AtomicUsize);
+
+    impl Wake for CountingWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn cancelled_pop_passes_wake_up_on() {
+        let heap = AsyncDaryHeap::<u32, 2>::new();
+        let wakers: Vec<_> = (0..2)
+            .map(|_| Arc::new(CountingWaker(AtomicUsize::new(0))))
+            .collect();
+        let mut pops: Vec<_> = (0..2).map(|_| heap.pop()).collect();
+        for (pop, waker) in pops.iter_mut().zip(&wakers) {
+            let waker = Waker::from(Arc::clone(waker));
+            let mut cx = Context::from_waker(&waker);
+            assert!(Pin::new(pop).poll(&mut cx).is_pending());
+        }
+
+        heap.push(1);
+        assert_eq!(wakers[0].0.load(Ordering::SeqCst), 1);
+        assert_eq!(wakers[1].0.load(Ordering::SeqCst), 0);
+
+        // Dropping the woken future wakes up the other one.
+        drop(pops.remove(0));
+        assert_eq!(wakers[1].0.load(Ordering::SeqCst), 1);
+        let waker = Waker::from(Arc::clone(&wakers[1]));
+        let mut cx = Context::from_waker(&waker);
+        assert_eq!(Pin::new(&mut pops[0]).poll(&mut cx), Poll::Ready(1));
+    }
+
+    #[test]
+    fn producers_consumers() {
+        let heap = Arc::new(AsyncDaryHeap::<u32, 4>::new());
+        let consumers: Vec<_> = (0..4)
+            .map(|_| {
+                let heap = Arc::clone(&heap);
+                thread::spawn(move || {
+                    pollster::block_on(async {
+                        let mut popped = Vec::new();
+                        for _ in 0..250 {
+                            popped.push(heap.pop().await);
+                        }
+                        popped
+                    })
+                })
+            })
+            .collect();
+        for i in 0..1000 {
+            heap.push(i);
+        }
+
+        let mut popped = Vec::new();
+        for consumer in consumers {
+            popped.extend(consumer.join().unwrap());
+        }
+        popped.sort_unstable();
+        assert_eq!(popped, (0..1000).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,614 @@
+//! A *d*-ary heap with a memory layout that groups subtrees into blocks.
+//!
+//! In the usual layout of a *d*-ary heap, the items on a path from the root
+//! to a leaf are spread out further and further apart, so that every level of
+//! a large heap touches a different cache line and eventually a different
+//! memory page. The [`BlockedDaryHeap`] type in this module instead stores the
+//! heap as a tree of blocks, in the style of a B-heap. Every block is a small
+//! complete *d*-ary tree stored contiguously, and the children of the leaves
+//! of a block are the roots of other blocks. A path from the root to a leaf
+//! then only visits one block per few levels.
+//!
+//! By default, blocks are sized to fit in a memory page of 4 KiB, which
+//! reduces TLB misses on heaps with tens of millions of items. Smaller blocks,
+//! e.g. fitting in a cache line, can be chosen with
+//! [`BlockedDaryHeap::with_block_levels`]. On small heaps, the usual layout
+//! of [`DaryHeap`] is typically faster.
+//!
+//! [`DaryHeap`]: crate::DaryHeap
+
+use core::fmt;
+use core::iter::FromIterator;
+use core::mem;
+
+use alloc::vec::Vec;
+
+use crate::{IntoIter, Iter};
+
+/// The size of a memory page in bytes that blocks are sized to by default.
+const PAGE_SIZE: usize = 4096;
+
+/// The shape of the blocks of a [`BlockedDaryHeap`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Layout {
+    /// Number of items in a block.
+    block_len: usize,
+    /// Position within a block of its first leaf.
+    leaf_start: usize,
+    /// Number of child blocks of a block.
+    fanout: usize,
+}
+
+impl Layout {
+    /// Returns the layout of blocks containing a complete *d*-ary tree with
+    /// `levels` levels, or `None` if a block would be too large.
+    fn new<const D: usize>(levels: usize) -> Option<Layout> {
+        assert_ne!(D, 0, "Arity should be greater than zero");
+        assert_ne!(levels, 0, "Blocks should have at least one level");
+        let mut block_len = 0usize;
+        let mut leaves = 1usize;
+        for level in 0..levels {
+            if level > 0 {
+                leaves = leaves.checked_mul(D)?;
+            }
+            block_len = block_len.checked_add(leaves)?;
+        }
+        Some(Layout {
+            block_len,
+            leaf_start: block_len - leaves,
+            fanout: leaves.checked_mul(D)?,
+        })
+    }
+
+    /// Returns the layout with the largest blocks of items of type `T` that
+    /// fit in a memory page.
+    fn for_page<T, const D: usize>() -> Layout {
+        let size = mem::size_of::<T>().max(1);
+        let mut levels = 1;
+        let mut layout = Layout::new::<D>(1).unwrap();
+        while let Some(next) = Layout::new::<D>(levels + 1) {
+            match next.block_len.checked_mul(size) {
+                Some(bytes) if bytes <= PAGE_SIZE => {
+                    layout = next;
+                    levels += 1;
+                }
+                _ => break,
+            }
+        }
+        layout
+    }
+
+    /// Returns the position of the parent of the item at `pos > 0`.
+    fn parent<const D: usize>(self, pos: usize) -> usize {
+        let (block, local) = (pos / self.block_len, pos % self.block_len);
+        if local > 0 {
+            block * self.block_len + (local - 1) / D
+        } else {
+            // The root of a block is a child of a leaf of the parent block.
+            let index = block - 1;
+            let leaf = index % self.fanout / D;
+            index / self.fanout * self.block_len + self.leaf_start + leaf
+        }
+    }
+
+    /// Returns the position of the first child of the item at `pos` and the
+    /// distance between consecutive children, or `None` if the children
+    /// cannot be addressed.
+    fn children<const D: usize>(self, pos: usize) -> Option<(usize, usize)> {
+        let (block, local) = (pos / self.block_len, pos % self.block_len);
+        if local < self.leaf_start {
+            Some((block * self.block_len + D * local + 1, 1))
+        } else {
+            // The children of a leaf are the roots of consecutive blocks.
+            let leaf = local - self.leaf_start;
+            let child_block = block.checked_mul(self.fanout)?.checked_add(leaf * D + 1)?;
+            Some((child_block.checked_mul(self.block_len)?, self.block_len))
+        }
+    }
+}
+
+/// A priority queue implemented with a *d*-ary heap whose subtrees are
+/// grouped into blocks.
+///
+/// This will be a max-heap. It has the same asymptotic costs as [`DaryHeap`],
+/// but a different memory layout that makes operations on very large heaps
+/// touch fewer cache lines and memory pages, see the [module
+/// documentation](crate::blocked) for more information.
+///
+/// [`DaryHeap`]: crate::DaryHeap
+///
+/// # Examples
+///
+/// ```
+/// use dary_heap::blocked::BlockedDaryHeap;
+///
+/// let mut heap = BlockedDaryHeap::<_, 4>::new();
+/// heap.push(3);
+/// heap.push(1);
+/// heap.push(5);
+///
+/// assert_eq!(heap.peek(), Some(&5));
+/// assert_eq!(heap.pop(), Some(5));
+/// assert_eq!(heap.pop(), Some(3));
+/// assert_eq!(heap.pop(), Some(1));
+/// assert_eq!(heap.pop(), None);
+/// ```
+pub struct BlockedDaryHeap<T, const D: usize> {
+    data: Vec<T>,
+    layout: Layout,
+}
+
+impl<T: Clone, const D: usize> Clone for BlockedDaryHeap<T, D> {
+    fn clone(&self) -> Self {
+        BlockedDaryHeap {
+            data: self.data.clone(),
+            layout: self.layout,
+        }
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        self.data.clone_from(&source.data);
+        self.layout = source.layout;
+    }
+}
+
+impl<T: Ord, const D: usize> Default for BlockedDaryHeap<T, D> {
+    /// Creates an empty `BlockedDaryHeap<T, D>`.
+    #[inline]
+    fn default() -> BlockedDaryHeap<T, D> {
+        BlockedDaryHeap::new()
+    }
+}
+
+impl<T: fmt::Debug, const D: usize> fmt::Debug for BlockedDaryHeap<T, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Ord, const D: usize> BlockedDaryHeap<T, D> {
+    /// Creates an empty `BlockedDaryHeap` with blocks that fit in a memory
+    /// page.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::blocked::BlockedDaryHeap;
+    /// let mut heap = BlockedDaryHeap::<_, 4>::new();
+    /// heap.push(4);
+    ///
+    /// // A block of 4-byte items is a quaternary tree of five levels.
+    /// assert_eq!(heap.block_len(), 1 + 4 + 16 + 64 + 256);
+    /// ```
+    #[must_use]
+    pub fn new() -> BlockedDaryHeap<T, D> {
+        BlockedDaryHeap {
+            data: Vec::new(),
+            layout: Layout::for_page::<T, D>(),
+        }
+    }
+
+    /// Creates an empty `BlockedDaryHeap` with at least the specified
+    /// capacity and blocks that fit in a memory page.
+    ///
+    /// The *d*-ary heap will be able to hold at least `capacity` elements without
+    /// reallocating. This method is allowed to allocate for more elements than
+    /// `capacity`. If `capacity` is 0, the *d*-ary heap will not allocate.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::blocked::BlockedDaryHeap;
+    /// let mut heap = BlockedDaryHeap::<_, 4>::with_capacity(10);
+    /// heap.push(4);
+    /// ```
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> BlockedDaryHeap<T, D> {
+        BlockedDaryHeap {
+            data: Vec::with_capacity(capacity),
+            layout: Layout::for_page::<T, D>(),
+        }
+    }
+
+    /// Creates an empty `BlockedDaryHeap` whose blocks are complete *d*-ary
+    /// trees with `levels` levels.
+    ///
+    /// With a single level per block, the layout is the same as that of
+    /// [`DaryHeap`].
+    ///
+    /// [`DaryHeap`]: crate::DaryHeap
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::blocked::BlockedDaryHeap;
+    /// // Blocks of 15 items fit in a 64-byte cache line.
+    /// let mut heap = BlockedDaryHeap::<u32, 2>::with_block_levels(4);
+    /// heap.push(4);
+    ///
+    /// assert_eq!(heap.block_len(), 15);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `levels` is zero or if a block would contain more than
+    /// [`usize::MAX`] items.
+    #[must_use]
+    pub fn with_block_levels(levels: usize) -> BlockedDaryHeap<T, D> {
+        BlockedDaryHeap {
+            data: Vec::new(),
+            layout: Layout::new::<D>(levels).expect("block size overflows usize"),
+        }
+    }
+
+    /// Pushes an item onto the *d*-ary heap.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::blocked::BlockedDaryHeap;
+    /// let mut heap = BlockedDaryHeap::<_, 3>::new();
+    /// heap.push(3);
+    /// heap.push(5);
+    /// heap.push(1);
+    ///
+    /// assert_eq!(heap.len(), 3);
+    /// assert_eq!(heap.peek(), Some(&5));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The worst case cost of `push` on a heap containing *n* elements is
+    /// *O*(log(*n*)).
+    pub fn push(&mut self, item: T) {
+        self.data.push(item);
+        self.sift_up(self.data.len() - 1);
+    }
+
+    /// Removes the greatest item from the *d*-ary heap and returns it, or
+    /// `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::blocked::BlockedDaryHeap;
+    /// let mut heap = BlockedDaryHeap::<_, 3>::from(vec![1, 3]);
+    ///
+    /// assert_eq!(heap.pop(), Some(3));
+    /// assert_eq!(heap.pop(), Some(1));
+    /// assert_eq!(heap.pop(), None);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The worst case cost of `pop` on a heap containing *n* elements is
+    /// *O*(*d* log(*n*) / log(*d*)).
+    pub fn pop(&mut self) -> Option<T> {
+        if self.data.is_empty() {
+            return None;
+        }
+        let item = self.data.swap_remove(0);
+        if !self.data.is_empty() {
+            self.sift_down(0);
+        }
+        Some(item)
+    }
+
+    /// Consumes the `BlockedDaryHeap` and returns a vector in sorted
+    /// (ascending) order.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::blocked::BlockedDaryHeap;
+    ///
+    /// let mut heap = BlockedDaryHeap::<_, 4>::from(vec![1, 2, 4, 5, 7]);
+    /// heap.push(6);
+    /// heap.push(3);
+    ///
+    /// let vec = heap.into_sorted_vec();
+    /// assert_eq!(vec, [1, 2, 3, 4, 5, 6, 7]);
+    /// ```
+    #[must_use = "`self` will be dropped if the result is not used"]
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        let mut vec = self.data;
+        vec.sort();
+        vec
+    }
+
+    /// Moves the item at `pos` up to its place, assuming the rest of the
+    /// heap is valid.
+    fn sift_up(&mut self, mut pos: usize) {
+        while pos > 0 {
+            let parent = self.layout.parent::<D>(pos);
+            if self.data[pos] <= self.data[parent] {
+                break;
+            }
+            self.data.swap(pos, parent);
+            pos = parent;
+        }
+    }
+
+    /// Moves the item at `pos` down to its place, assuming its subtrees are
+    /// valid heaps.
+    fn sift_down(&mut self, mut pos: usize) {
+        let len = self.data.len();
+        while let Some((first, stride)) = self.layout.children::<D>(pos) {
+            if first >= len {
+                break;
+            }
+            let mut child = first;
+            let mut sibling = first;
+            for _ in 1..D {
+                sibling = match sibling.checked_add(stride) {
+                    Some(sibling) if sibling < len => sibling,
+                    _ => break,
+                };
+                if self.data[sibling] > self.data[child] {
+                    child = sibling;
+                }
+            }
+            if self.data[child] <= self.data[pos] {
+                break;
+            }
+            self.data.swap(pos, child);
+            pos = child;
+        }
+    }
+
+    /// Restores the heap property, processing every item after its
+    /// descendants, which are stored after it.
+    fn rebuild(&mut self) {
+        let mut pos = self.data.len();
+        while pos > 0 {
+            pos -= 1;
+            self.sift_down(pos);
+        }
+    }
+}
+
+impl<T, const D: usize> BlockedDaryHeap<T, D> {
+    /// Returns the greatest item in the *d*-ary heap, or `None` if it is
+    /// empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::blocked::BlockedDaryHeap;
+    /// let mut heap = BlockedDaryHeap::<_, 2>::new();
+    /// assert_eq!(heap.peek(), None);
+    ///
+    /// heap.push(1);
+    /// heap.push(5);
+    /// heap.push(2);
+    /// assert_eq!(heap.peek(), Some(&5));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Cost is *O*(1) in the worst case.
+    #[must_use]
+    pub fn peek(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    /// Returns the number of items in a block.
+    #[must_use]
+    pub fn block_len(&self) -> usize {
+        self.layout.block_len
+    }
+
+    /// Returns an iterator visiting all values in the underlying vector, in
+    /// arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::blocked::BlockedDaryHeap;
+    /// let heap = BlockedDaryHeap::<_, 4>::from(vec![1, 2, 3, 4]);
+    ///
+    /// // Print 1, 2, 3, 4 in arbitrary order
+    /// for x in heap.iter() {
+    ///     println!("{}", x);
+    /// }
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            iter: self.data.iter(),
+        }
+    }
+
+    /// Returns the number of elements the heap can hold without reallocating.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.data.capacity()
+    }
+
+    /// Reserves capacity for at least `additional` elements more than the
+    /// current length. See [`DaryHeap::reserve`] for more information.
+    ///
+    /// [`DaryHeap::reserve`]: crate::DaryHeap::reserve
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity overflows [`usize`].
+    pub fn reserve(&mut self, additional: usize) {
+        self.data.reserve(additional);
+    }
+
+    /// Returns a slice of all values in the underlying vector, in arbitrary
+    /// order.
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        self.data.as_slice()
+    }
+
+    /// Consumes the `BlockedDaryHeap` and returns the underlying vector
+    /// in arbitrary order.
+    #[must_use = "`self` will be dropped if the result is not used"]
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    /// Returns the length of the *d*-ary heap.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Checks if the *d*-ary heap is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drops all items from the *d*-ary heap.
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+}
+
+impl<T: Ord, const D: usize> From<Vec<T>> for BlockedDaryHeap<T, D> {
+    /// Converts a `Vec<T>` into a `BlockedDaryHeap<T, D>` with blocks that fit
+    /// in a memory page.
+    ///
+    /// This conversion happens in-place, and has *O*(*n*) time complexity.
+    fn from(vec: Vec<T>) -> BlockedDaryHeap<T, D> {
+        let mut heap = BlockedDaryHeap {
+            data: vec,
+            layout: Layout::for_page::<T, D>(),
+        };
+        heap.rebuild();
+        heap
+    }
+}
+
+impl<T, const D: usize> From<BlockedDaryHeap<T, D>> for Vec<T> {
+    /// Converts a `BlockedDaryHeap<T, D>` into a `Vec<T>`.
+    ///
+    /// This conversion requires no data movement or allocation, and has
+    /// constant time complexity.
+    fn from(heap: BlockedDaryHeap<T, D>) -> Vec<T> {
+        heap.data
+    }
+}
+
+impl<T: Ord, const D: usize> FromIterator<T> for BlockedDaryHeap<T, D> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> BlockedDaryHeap<T, D> {
+        BlockedDaryHeap::from(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl<T, const D: usize> IntoIterator for BlockedDaryHeap<T, D> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    /// Creates a consuming iterator, that is, one that moves each value out of
+    /// the *d*-ary heap in arbitrary order.
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter {
+            iter: self.data.into_iter(),
+        }
+    }
+}
+
+impl<'a, T, const D: usize> IntoIterator for &'a BlockedDaryHeap<T, D> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<T: Ord, const D: usize> Extend<T> for BlockedDaryHeap<T, D> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        iter.for_each(move |elem| self.push(elem));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{seq::SliceRandom, thread_rng, Rng};
+
+    fn assert_valid<T: Ord, const D: usize>(heap: &BlockedDaryHeap<T, D>) {
+        for pos in 1..heap.len() {
+            let parent = heap.layout.parent::<D>(pos);
+            assert!(heap.data[pos] <= heap.data[parent]);
+        }
+    }
+
+    fn check<const D: usize>() {
+        for levels in 1..4 {
+            let layout = Layout::new::<D>(levels).unwrap();
+            for pos in 1..1000 {
+                let parent = layout.parent::<D>(pos);
+                assert!(parent < pos);
+                let (first, stride) = layout.children::<D>(parent).unwrap();
+                assert_eq!((pos - first) % stride, 0);
+                assert!((pos - first) / stride < D);
+                if levels == 1 {
+                    assert_eq!(parent, (pos - 1) / D);
+                }
+            }
+
+            let mut rng = thread_rng();
+            let mut data: Vec<_> = (0..200).collect();
+            data.shuffle(&mut rng);
+            let mut heap = BlockedDaryHeap::<_, D>::with_block_levels(levels);
+            heap.extend(data[..100].iter().copied());
+            assert_valid(&heap);
+            let mut reference: Vec<_> = data[..100].to_vec();
+            reference.sort_unstable();
+            for &x in &data[100..] {
+                heap.push(x);
+                let pos = reference.binary_search(&x).unwrap_err();
+                reference.insert(pos, x);
+                if rng.gen() {
+                    assert_eq!(heap.pop(), reference.pop());
+                }
+                assert_eq!(heap.peek(), reference.last());
+            }
+            assert_valid(&heap);
+            while let Some(x) = reference.pop() {
+                assert_eq!(heap.pop(), Some(x));
+            }
+            assert!(heap.is_empty());
+        }
+
+        let mut data: Vec<u64> = (0..10_000).collect();
+        data.shuffle(&mut thread_rng());
+        let heap = BlockedDaryHeap::<_, D>::from(data);
+        assert!(heap.block_len() * mem::size_of::<u64>() <= PAGE_SIZE);
+        assert_valid(&heap);
+        assert_eq!(heap.into_sorted_vec(), (0..10_000).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn matches_sorted_reference() {
+        check::<1>();
+        check::<2>();
+        check::<3>();
+        check::<4>();
+        check::<7>();
+    }
+}
@@ -0,0 +1,698 @@
+//! Heaps ordered by a comparator instead of the [`Ord`] implementation.
+//!
+//! The [`DaryHeapBy`] type in this module is a *d*-ary heap that decides the
+//! order of its items with a comparator implementing [`Compare`], which is
+//! chosen when the heap is constructed. Closures of the form
+//! `Fn(&T, &T) -> Ordering` implement [`Compare`], so the order can be defined
+//! inline without wrapping items in newtypes.
+
+use core::cmp::Ordering;
+#[cfg(feature = "alloc")]
+use core::fmt;
+#[cfg(feature = "alloc")]
+use core::iter::FusedIterator;
+#[cfg(feature = "alloc")]
+use core::mem::swap;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use crate::sift::{self, Const};
+#[cfg(feature = "alloc")]
+use crate::{Drain, IntoIter, Iter};
+
+/// A comparator that determines the order of items in a heap.
+///
+/// This is implemented for closures `Fn(&T, &T) -> Ordering`, and for
+/// [`Natural`], which uses the [`Ord`] implementation of `T`.
+pub trait Compare<T: ?Sized> {
+    /// Compares two items, like [`Ord::cmp`].
+    fn compare(&self, l: &T, r: &T) -> Ordering;
+
+    /// Returns `true` if `l` is less than or equal to `r`.
+    #[inline]
+    fn compares_le(&self, l: &T, r: &T) -> bool {
+        self.compare(l, r) != Ordering::Greater
+    }
+
+    /// Returns `true` if `l` is less than `r`.
+    #[inline]
+    fn compares_lt(&self, l: &T, r: &T) -> bool {
+        self.compare(l, r) == Ordering::Less
+    }
+}
+
+impl<T: ?Sized, F: Fn(&T, &T) -> Ordering> Compare<T> for F {
+    #[inline]
+    fn compare(&self, l: &T, r: &T) -> Ordering {
+        self(l, r)
+    }
+}
+
+/// Comparator using the [`Ord`] implementation of the items.
+///
+/// A [`DaryHeapBy`] with this comparator is a max-heap, and behaves like a
+/// [`DaryHeap`].
+///
+/// [`DaryHeap`]: crate::DaryHeap
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Natural;
+
+impl<T: Ord + ?Sized> Compare<T> for Natural {
+    #[inline]
+    fn compare(&self, l: &T, r: &T) -> Ordering {
+        l.cmp(r)
+    }
+
+    #[inline]
+    fn compares_le(&self, l: &T, r: &T) -> bool {
+        l <= r
+    }
+
+    #[inline]
+    fn compares_lt(&self, l: &T, r: &T) -> bool {
+        l < r
+    }
+}
+
+/// Comparator ordering items by a key extracted with a function.
+///
+/// The key is extracted again for every comparison, so extraction should be
+/// cheap. Otherwise, consider storing [`CachedKey`] items in a heap instead.
+///
+/// This is normally constructed through [`DaryHeapBy::new_by_key`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct KeyComparator<F>(pub F);
+
+impl<T: ?Sized, K: Ord, F: Fn(&T) -> K> Compare<T> for KeyComparator<F> {
+    #[inline]
+    fn compare(&self, l: &T, r: &T) -> Ordering {
+        (self.0)(l).cmp(&(self.0)(r))
+    }
+
+    #[inline]
+    fn compares_le(&self, l: &T, r: &T) -> bool {
+        (self.0)(l) <= (self.0)(r)
+    }
+
+    #[inline]
+    fn compares_lt(&self, l: &T, r: &T) -> bool {
+        (self.0)(l) < (self.0)(r)
+    }
+}
+
+/// An item stored together with its key, ordered by the key only.
+///
+/// Storing `CachedKey` items in a heap extracts the key once per item instead
+/// of on every comparison, which is worthwhile if extracting the key is
+/// expensive.
+///
+/// # Examples
+///
+/// ```
+/// use dary_heap::compare::CachedKey;
+/// use dary_heap::QuaternaryHeap;
+///
+/// let mut heap = QuaternaryHeap::new();
+/// for &word in &["apple", "fig", "banana"] {
+///     heap.push(CachedKey::new(word, |w| w.len()));
+/// }
+///
+/// assert_eq!(heap.pop().map(CachedKey::into_item), Some("banana"));
+/// assert_eq!(heap.peek().map(|k| k.key), Some(5));
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CachedKey<K, T> {
+    /// The key the item is ordered by.
+    pub key: K,
+    /// The item itself.
+    pub item: T,
+}
+
+impl<K, T> CachedKey<K, T> {
+    /// Extracts the key of `item` with `f` and stores it alongside the item.
+    pub fn new<F: FnOnce(&T) -> K>(item: T, f: F) -> CachedKey<K, T> {
+        CachedKey {
+            key: f(&item),
+            item,
+        }
+    }
+
+    /// Returns the item, discarding the key.
+    pub fn into_item(self) -> T {
+        self.item
+    }
+}
+
+impl<K: PartialEq, T> PartialEq for CachedKey<K, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<K: Eq, T> Eq for CachedKey<K, T> {}
+
+impl<K: PartialOrd, T> PartialOrd for CachedKey<K, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.key.partial_cmp(&other.key)
+    }
+}
+
+impl<K: Ord, T> Ord for CachedKey<K, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+/// A priority queue implemented with a *d*-ary heap, ordered by a comparator.
+///
+/// This will be a max-heap with respect to the comparator: the item that
+/// compares greatest is popped first. Apart from the ordering, it behaves like
+/// [`DaryHeap`]; see its documentation for more information.
+///
+/// It is a logic error for the comparator to not define a total order, or for
+/// an item to be modified in such a way that its order relative to any other
+/// item changes while it is in the heap.
+///
+/// [`DaryHeap`]: crate::DaryHeap
+///
+/// # Examples
+///
+/// ```
+/// use dary_heap::compare::DaryHeapBy;
+///
+/// // A min-heap on the second element of tuples
+/// let mut heap = DaryHeapBy::<_, 4, _>::new_by(|a: &(char, i32), b: &(char, i32)| b.1.cmp(&a.1));
+/// heap.push(('a', 5));
+/// heap.push(('b', 1));
+/// heap.push(('c', 2));
+///
+/// assert_eq!(heap.pop(), Some(('b', 1)));
+/// assert_eq!(heap.pop(), Some(('c', 2)));
+/// assert_eq!(heap.pop(), Some(('a', 5)));
+/// assert_eq!(heap.pop(), None);
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Clone)]
+pub struct DaryHeapBy<T, const D: usize, C> {
+    data: Vec<T>,
+    cmp: C,
+}
+
+#[cfg(feature = "alloc")]
+impl<T: fmt::Debug, const D: usize, C> fmt::Debug for DaryHeapBy<T, D, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, const D: usize, C: Compare<T> + Default> Default for DaryHeapBy<T, D, C> {
+    /// Creates an empty `DaryHeapBy<T, D, C>` with the default comparator.
+    #[inline]
+    fn default() -> DaryHeapBy<T, D, C> {
+        DaryHeapBy::new_by(C::default())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, const D: usize, C: Compare<T>> DaryHeapBy<T, D, C> {
+    /// Creates an empty `DaryHeapBy` ordered by `cmp`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::compare::DaryHeapBy;
+    /// let mut heap = DaryHeapBy::<_, 3, _>::new_by(|a: &i32, b: &i32| b.cmp(a));
+    /// heap.push(4);
+    /// ```
+    #[must_use]
+    pub fn new_by(cmp: C) -> DaryHeapBy<T, D, C> {
+        DaryHeapBy::with_capacity_by(0, cmp)
+    }
+
+    /// Creates an empty `DaryHeapBy` ordered by `cmp` with at least the
+    /// specified capacity.
+    ///
+    /// The *d*-ary heap will be able to hold at least `capacity` elements without
+    /// reallocating. This method is allowed to allocate for more elements than
+    /// `capacity`. If `capacity` is 0, the *d*-ary heap will not allocate.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::compare::DaryHeapBy;
+    /// let mut heap = DaryHeapBy::<_, 3, _>::with_capacity_by(10, |a: &i32, b: &i32| b.cmp(a));
+    /// heap.push(4);
+    /// ```
+    #[must_use]
+    pub fn with_capacity_by(capacity: usize, cmp: C) -> DaryHeapBy<T, D, C> {
+        DaryHeapBy {
+            data: Vec::with_capacity(capacity),
+            cmp,
+        }
+    }
+
+    /// Converts a vector into a `DaryHeapBy` ordered by `cmp`.
+    ///
+    /// This conversion happens in-place, and has *O*(*n*) time complexity.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::compare::DaryHeapBy;
+    /// let heap = DaryHeapBy::<_, 2, _>::from_vec_by(vec![3, 1, 2], |a: &i32, b: &i32| b.cmp(a));
+    /// assert_eq!(heap.peek(), Some(&1));
+    /// ```
+    #[must_use]
+    pub fn from_vec_by(vec: Vec<T>, cmp: C) -> DaryHeapBy<T, D, C> {
+        let mut heap = DaryHeapBy { data: vec, cmp };
+        heap.rebuild();
+        heap
+    }
+
+    /// Removes the greatest item from the *d*-ary heap and returns it, or `None` if it
+    /// is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::compare::{DaryHeapBy, Natural};
+    /// let mut heap = DaryHeapBy::<_, 2, _>::from_vec_by(vec![1, 3], Natural);
+    ///
+    /// assert_eq!(heap.pop(), Some(3));
+    /// assert_eq!(heap.pop(), Some(1));
+    /// assert_eq!(heap.pop(), None);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The worst case cost of `pop` on a heap containing *n* elements is *O*(log(*n*)).
+    pub fn pop(&mut self) -> Option<T> {
+        self.data.pop().map(|mut item| {
+            if !self.is_empty() {
+                swap(&mut item, &mut self.data[0]);
+                // SAFETY: !self.is_empty() means that self.len() > 0
+                unsafe { sift::sift_down_to_bottom(&mut self.data, Const::<D>, &self.cmp, 0) };
+            }
+            item
+        })
+    }
+
+    /// Pushes an item onto the *d*-ary heap.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::compare::DaryHeapBy;
+    /// let mut heap = DaryHeapBy::<_, 4, _>::new_by(|a: &i32, b: &i32| b.cmp(a));
+    /// heap.push(3);
+    /// heap.push(5);
+    /// heap.push(1);
+    ///
+    /// assert_eq!(heap.len(), 3);
+    /// assert_eq!(heap.peek(), Some(&1));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The expected cost of `push`, averaged over every possible ordering of
+    /// the elements being pushed, and over a sufficiently large number of
+    /// pushes, is *O*(1). See [`DaryHeap::push`] for a more detailed analysis.
+    ///
+    /// [`DaryHeap::push`]: crate::DaryHeap::push
+    pub fn push(&mut self, item: T) {
+        let old_len = self.len();
+        self.data.push(item);
+        // SAFETY: Since we pushed a new item it means that
+        //  old_len = self.len() - 1 < self.len()
+        unsafe { sift::sift_up(&mut self.data, Const::<D>, &self.cmp, 0, old_len) };
+    }
+
+    /// Consumes the `DaryHeapBy` and returns a vector in sorted
+    /// (ascending) order with respect to the comparator.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::compare::DaryHeapBy;
+    ///
+    /// let cmp = |a: &i32, b: &i32| b.cmp(a);
+    /// let mut heap = DaryHeapBy::<_, 3, _>::from_vec_by(vec![1, 2, 4, 5, 7], cmp);
+    /// heap.push(6);
+    /// heap.push(3);
+    ///
+    /// let vec = heap.into_sorted_vec();
+    /// assert_eq!(vec, [7, 6, 5, 4, 3, 2, 1]);
+    /// ```
+    #[must_use = "`self` will be dropped if the result is not used"]
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        sift::sort_heap(&mut self.data, Const::<D>, &self.cmp);
+        self.into_vec()
+    }
+
+    /// Returns an iterator which retrieves elements in heap order.
+    /// This method consumes the original heap.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::compare::DaryHeapBy;
+    /// let cmp = |a: &i32, b: &i32| b.cmp(a);
+    /// let heap = DaryHeapBy::<_, 2, _>::from_vec_by(vec![1, 2, 3, 4, 5], cmp);
+    ///
+    /// assert_eq!(heap.into_iter_sorted().take(2).collect::<Vec<_>>(), [1, 2]);
+    /// ```
+    pub fn into_iter_sorted(self) -> IntoIterSorted<T, D, C> {
+        IntoIterSorted { inner: self }
+    }
+
+    /// Moves all the elements of `other` into `self`, leaving `other` empty.
+    ///
+    /// The items of `other` are ordered by the comparator of `self` afterwards.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::compare::{DaryHeapBy, Natural};
+    ///
+    /// let mut a = DaryHeapBy::<_, 3, _>::from_vec_by(vec![-10, 1, 2, 3, 3], Natural);
+    /// let mut b = DaryHeapBy::<_, 3, _>::from_vec_by(vec![-20, 5, 43], Natural);
+    ///
+    /// a.append(&mut b);
+    ///
+    /// assert_eq!(a.into_sorted_vec(), [-20, -10, 1, 2, 3, 3, 5, 43]);
+    /// assert!(b.is_empty());
+    /// ```
+    pub fn append(&mut self, other: &mut Self) {
+        let start = self.data.len();
+        self.data.append(&mut other.data);
+        sift::rebuild_tail(&mut self.data, Const::<D>, &self.cmp, start);
+    }
+
+    fn rebuild(&mut self) {
+        sift::rebuild(&mut self.data, Const::<D>, &self.cmp);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, const D: usize, K: Ord, F: Fn(&T) -> K> DaryHeapBy<T, D, KeyComparator<F>> {
+    /// Creates an empty `DaryHeapBy` ordered by the key that `f` extracts
+    /// from each item.
+    ///
+    /// The key is extracted on every comparison; see [`CachedKey`] for an
+    /// alternative that extracts it only once per item.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::compare::DaryHeapBy;
+    /// let mut heap = DaryHeapBy::<_, 3, _>::new_by_key(|task: &(&str, u32)| task.1);
+    /// heap.push(("write", 2));
+    /// heap.push(("test", 5));
+    /// heap.push(("review", 1));
+    ///
+    /// assert_eq!(heap.pop(), Some(("test", 5)));
+    /// ```
+    #[must_use]
+    pub fn new_by_key(f: F) -> DaryHeapBy<T, D, KeyComparator<F>> {
+        DaryHeapBy::new_by(KeyComparator(f))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, const D: usize, C> DaryHeapBy<T, D, C> {
+    /// Returns a reference to the comparator of the *d*-ary heap.
+    #[must_use]
+    pub fn comparator(&self) -> &C {
+        &self.cmp
+    }
+
+    /// Returns an iterator visiting all values in the underlying vector, in
+    /// arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::compare::{DaryHeapBy, Natural};
+    /// let heap = DaryHeapBy::<_, 3, _>::from_vec_by(vec![1, 2, 3, 4], Natural);
+    ///
+    /// // Print 1, 2, 3, 4 in arbitrary order
+    /// for x in heap.iter() {
+    ///     println!("{}", x);
+    /// }
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            iter: self.data.iter(),
+        }
+    }
+
+    /// Returns the greatest item in the *d*-ary heap, or `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::compare::DaryHeapBy;
+    /// let mut heap = DaryHeapBy::<_, 2, _>::new_by(|a: &i32, b: &i32| b.cmp(a));
+    /// assert_eq!(heap.peek(), None);
+    ///
+    /// heap.push(1);
+    /// heap.push(5);
+    /// heap.push(2);
+    /// assert_eq!(heap.peek(), Some(&1));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Cost is *O*(1) in the worst case.
+    #[must_use]
+    pub fn peek(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    /// Returns the number of elements the *d*-ary heap can hold without reallocating.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.data.capacity()
+    }
+
+    /// Reserves the minimum capacity for at least `additional` elements more than
+    /// the current length. See [`DaryHeap::reserve_exact`] for more information.
+    ///
+    /// [`DaryHeap::reserve_exact`]: crate::DaryHeap::reserve_exact
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity overflows [`usize`].
+    pub fn reserve_exact(&mut self, additional: usize) {
+        self.data.reserve_exact(additional);
+    }
+
+    /// Reserves capacity for at least `additional` elements more than the
+    /// current length. See [`DaryHeap::reserve`] for more information.
+    ///
+    /// [`DaryHeap::reserve`]: crate::DaryHeap::reserve
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity overflows [`usize`].
+    pub fn reserve(&mut self, additional: usize) {
+        self.data.reserve(additional);
+    }
+
+    /// Discards as much additional capacity as possible.
+    pub fn shrink_to_fit(&mut self) {
+        self.data.shrink_to_fit();
+    }
+
+    /// Returns a slice of all values in the underlying vector, in arbitrary
+    /// order.
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        self.data.as_slice()
+    }
+
+    /// Consumes the `DaryHeapBy` and returns the underlying vector
+    /// in arbitrary order.
+    #[must_use = "`self` will be dropped if the result is not used"]
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    /// Returns the length of the *d*-ary heap.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Checks if the *d*-ary heap is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Clears the *d*-ary heap, returning an iterator over the removed elements
+    /// in arbitrary order. If the iterator is dropped before being fully
+    /// consumed, it drops the remaining elements in arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::compare::{DaryHeapBy, Natural};
+    /// let mut heap = DaryHeapBy::<_, 4, _>::from_vec_by(vec![1, 3], Natural);
+    ///
+    /// assert!(!heap.is_empty());
+    ///
+    /// for x in heap.drain() {
+    ///     println!("{}", x);
+    /// }
+    ///
+    /// assert!(heap.is_empty());
+    /// ```
+    #[inline]
+    pub fn drain(&mut self) -> Drain<'_, T> {
+        Drain {
+            iter: self.data.drain(..),
+        }
+    }
+
+    /// Drops all items from the *d*-ary heap.
+    pub fn clear(&mut self) {
+        self.drain();
+    }
+}
+
+/// An owning iterator over the elements of a `DaryHeapBy` in heap order.
+///
+/// This `struct` is created by [`DaryHeapBy::into_iter_sorted()`]. See its
+/// documentation for more.
+///
+/// [`into_iter_sorted`]: DaryHeapBy::into_iter_sorted
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[derive(Clone, Debug)]
+pub struct IntoIterSorted<T, const D: usize, C> {
+    inner: DaryHeapBy<T, D, C>,
+}
+
+#[cfg(feature = "alloc")]
+impl<T, const D: usize, C: Compare<T>> Iterator for IntoIterSorted<T, D, C> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.inner.pop()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let exact = self.inner.len();
+        (exact, Some(exact))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, const D: usize, C: Compare<T>> ExactSizeIterator for IntoIterSorted<T, D, C> {}
+
+#[cfg(feature = "alloc")]
+impl<T, const D: usize, C: Compare<T>> FusedIterator for IntoIterSorted<T, D, C> {}
+
+#[cfg(feature = "alloc")]
+impl<T, const D: usize, C> From<DaryHeapBy<T, D, C>> for Vec<T> {
+    /// Converts a `DaryHeapBy<T, D, C>` into a `Vec<T>`.
+    ///
+    /// This conversion requires no data movement or allocation, and has
+    /// constant time complexity.
+    fn from(heap: DaryHeapBy<T, D, C>) -> Vec<T> {
+        heap.data
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, const D: usize, C> IntoIterator for DaryHeapBy<T, D, C> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    /// Creates a consuming iterator, that is, one that moves each value out of
+    /// the *d*-ary heap in arbitrary order.
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter {
+            iter: self.data.into_iter(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T, const D: usize, C> IntoIterator for &'a DaryHeapBy<T, D, C> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, const D: usize, C: Compare<T>> Extend<T> for DaryHeapBy<T, D, C> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let start = self.data.len();
+        self.data.extend(iter);
+        sift::rebuild_tail(&mut self.data, Const::<D>, &self.cmp, start);
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use crate::DaryHeap;
+    use core::cmp::Reverse;
+    use rand::{seq::SliceRandom, thread_rng};
+
+    #[test]
+    fn matches_reverse() {
+        let mut data: Vec<_> = (0..200).collect();
+        data.shuffle(&mut thread_rng());
+
+        let cmp = |a: &i32, b: &i32| b.cmp(a);
+        let mut heap = DaryHeapBy::<_, 3, _>::from_vec_by(data[..100].to_vec(), cmp);
+        let mut reference: DaryHeap<_, 3> = data[..100].iter().copied().map(Reverse).collect();
+        for &x in &data[100..150] {
+            heap.push(x);
+            reference.push(Reverse(x));
+        }
+        heap.extend(data[150..].iter().copied());
+        reference.extend(data[150..].iter().copied().map(Reverse));
+        while let Some(Reverse(x)) = reference.pop() {
+            assert_eq!(heap.pop(), Some(x));
+        }
+        assert_eq!(heap.pop(), None);
+    }
+}
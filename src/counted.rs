@@ -0,0 +1,500 @@
+//! A *d*-ary heap that stores equal items only once.
+//!
+//! The [`CountedDaryHeap`] type in this module keeps a count for every
+//! distinct item, so pushing an item that is already in the heap only
+//! increments its count instead of growing the heap. This keeps the heap small
+//! for workloads with many duplicates, such as event systems in which the same
+//! events are scheduled over and over again.
+
+use core::fmt;
+use core::iter::FusedIterator;
+
+use alloc::collections::{btree_map, BTreeMap};
+use alloc::vec::Vec;
+
+use crate::DaryHeap;
+
+/// A priority queue implemented with a *d*-ary heap, which stores equal items
+/// only once together with their count.
+///
+/// This will be a max-heap. The distinct items are stored in a [`DaryHeap`],
+/// and their counts in a [`BTreeMap`]. Pushing an item that is already in the
+/// heap therefore only costs a lookup in the map, and popping an item of which
+/// more copies remain only decrements its count. The items are cloned to be
+/// stored in both, and when popping an item of which copies remain.
+///
+/// [`BTreeMap`]: alloc::collections::BTreeMap
+///
+/// # Examples
+///
+/// ```
+/// use dary_heap::counted::CountedDaryHeap;
+///
+/// let mut heap = CountedDaryHeap::<_, 4>::new();
+/// for _ in 0..1000 {
+///     heap.push("tick");
+/// }
+/// heap.push("alarm");
+///
+/// assert_eq!(heap.len(), 1001);
+/// assert_eq!(heap.distinct_len(), 2);
+/// assert_eq!(heap.pop(), Some("tick"));
+/// assert_eq!(heap.pop_all(), Some(("tick", 999)));
+/// assert_eq!(heap.pop(), Some("alarm"));
+/// assert_eq!(heap.pop(), None);
+/// ```
+pub struct CountedDaryHeap<T, const D: usize> {
+    heap: DaryHeap<T, D>,
+    counts: BTreeMap<T, usize>,
+    len: usize,
+}
+
+impl<T: Clone, const D: usize> Clone for CountedDaryHeap<T, D> {
+    fn clone(&self) -> Self {
+        CountedDaryHeap {
+            heap: self.heap.clone(),
+            counts: self.counts.clone(),
+            len: self.len,
+        }
+    }
+}
+
+impl<T: Ord + Clone, const D: usize> Default for CountedDaryHeap<T, D> {
+    /// Creates an empty `CountedDaryHeap<T, D>`.
+    #[inline]
+    fn default() -> CountedDaryHeap<T, D> {
+        CountedDaryHeap::new()
+    }
+}
+
+impl<T: fmt::Debug, const D: usize> fmt::Debug for CountedDaryHeap<T, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.counts.iter()).finish()
+    }
+}
+
+impl<T: Ord + Clone, const D: usize> CountedDaryHeap<T, D> {
+    /// Creates an empty `CountedDaryHeap` as a max-heap.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::counted::CountedDaryHeap;
+    /// let mut heap = CountedDaryHeap::<_, 3>::new();
+    /// heap.push(4);
+    /// ```
+    #[must_use]
+    pub fn new() -> CountedDaryHeap<T, D> {
+        CountedDaryHeap {
+            heap: DaryHeap::new(),
+            counts: BTreeMap::new(),
+            len: 0,
+        }
+    }
+
+    /// Pushes an item onto the *d*-ary heap.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::counted::CountedDaryHeap;
+    /// let mut heap = CountedDaryHeap::<_, 2>::new();
+    /// heap.push(3);
+    /// heap.push(5);
+    /// heap.push(3);
+    ///
+    /// assert_eq!(heap.len(), 3);
+    /// assert_eq!(heap.count(&3), 2);
+    /// assert_eq!(heap.peek(), Some(&5));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// If an equal item is already in the heap, the cost of `push` on a heap
+    /// containing *m* distinct items is *O*(log(*m*)) for looking up its
+    /// count. Otherwise, the item is also pushed onto the underlying
+    /// [`DaryHeap`], see [`DaryHeap::push`].
+    pub fn push(&mut self, item: T) {
+        self.push_n(item, 1);
+    }
+
+    /// Pushes `n` copies of an item onto the *d*-ary heap. Nothing is pushed
+    /// if `n` is zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the total number of items overflows [`usize`].
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::counted::CountedDaryHeap;
+    /// let mut heap = CountedDaryHeap::<_, 2>::new();
+    /// heap.push_n(3, 1000);
+    ///
+    /// assert_eq!(heap.len(), 1000);
+    /// assert_eq!(heap.distinct_len(), 1);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The cost of `push_n` is the same as the cost of [`push`], regardless
+    /// of `n`.
+    ///
+    /// [`push`]: CountedDaryHeap::push
+    pub fn push_n(&mut self, item: T, n: usize) {
+        if n == 0 {
+            return;
+        }
+        self.len = self.len.checked_add(n).expect("length overflow");
+        match self.counts.get_mut(&item) {
+            Some(count) => *count += n,
+            None => {
+                self.counts.insert(item.clone(), n);
+                self.heap.push(item);
+            }
+        }
+    }
+
+    /// Removes one copy of the greatest item from the *d*-ary heap and
+    /// returns it, or `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::counted::CountedDaryHeap;
+    /// let mut heap = CountedDaryHeap::<_, 2>::new();
+    /// heap.push(1);
+    /// heap.push(3);
+    /// heap.push(3);
+    ///
+    /// assert_eq!(heap.pop(), Some(3));
+    /// assert_eq!(heap.pop(), Some(3));
+    /// assert_eq!(heap.pop(), Some(1));
+    /// assert_eq!(heap.pop(), None);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// If more copies of the greatest item remain, the cost of `pop` on a heap
+    /// containing *m* distinct items is *O*(log(*m*)) for decrementing its
+    /// count. Otherwise, the item is also popped from the underlying
+    /// [`DaryHeap`], see [`DaryHeap::pop`].
+    pub fn pop(&mut self) -> Option<T> {
+        let top = self.heap.peek()?;
+        let count = self
+            .counts
+            .get_mut(top)
+            .expect("item in heap should have a count");
+        if *count > 1 {
+            *count -= 1;
+            self.len -= 1;
+            Some(top.clone())
+        } else {
+            self.pop_all().map(|(item, _)| item)
+        }
+    }
+
+    /// Removes all copies of the greatest item from the *d*-ary heap and
+    /// returns it together with its count, or `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::counted::CountedDaryHeap;
+    /// let mut heap = CountedDaryHeap::<_, 2>::new();
+    /// heap.push_n(1, 2);
+    /// heap.push_n(3, 4);
+    ///
+    /// assert_eq!(heap.pop_all(), Some((3, 4)));
+    /// assert_eq!(heap.pop_all(), Some((1, 2)));
+    /// assert_eq!(heap.pop_all(), None);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The cost of `pop_all` on a heap containing *m* distinct items is
+    /// *O*(log(*m*)) for removing its count, plus the cost of
+    /// [`DaryHeap::pop`].
+    pub fn pop_all(&mut self) -> Option<(T, usize)> {
+        let item = self.heap.pop()?;
+        let count = self
+            .counts
+            .remove(&item)
+            .expect("item in heap should have a count");
+        self.len -= count;
+        Some((item, count))
+    }
+
+    /// Returns the number of copies of `item` in the *d*-ary heap.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::counted::CountedDaryHeap;
+    /// let mut heap = CountedDaryHeap::<_, 2>::new();
+    /// heap.push_n(1, 2);
+    ///
+    /// assert_eq!(heap.count(&1), 2);
+    /// assert_eq!(heap.count(&2), 0);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The cost of `count` on a heap containing *m* distinct items is
+    /// *O*(log(*m*)).
+    #[must_use]
+    pub fn count(&self, item: &T) -> usize {
+        self.counts.get(item).copied().unwrap_or(0)
+    }
+
+    /// Returns the greatest item in the *d*-ary heap together with its count,
+    /// or `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::counted::CountedDaryHeap;
+    /// let mut heap = CountedDaryHeap::<_, 2>::new();
+    /// heap.push_n(5, 3);
+    /// heap.push(1);
+    ///
+    /// assert_eq!(heap.peek_with_count(), Some((&5, 3)));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The cost of `peek_with_count` on a heap containing *m* distinct items
+    /// is *O*(log(*m*)).
+    #[must_use]
+    pub fn peek_with_count(&self) -> Option<(&T, usize)> {
+        let top = self.heap.peek()?;
+        Some((top, self.count(top)))
+    }
+}
+
+impl<T, const D: usize> CountedDaryHeap<T, D> {
+    /// Returns the greatest item in the *d*-ary heap, or `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::counted::CountedDaryHeap;
+    /// let mut heap = CountedDaryHeap::<_, 2>::new();
+    /// assert_eq!(heap.peek(), None);
+    ///
+    /// heap.push(1);
+    /// heap.push(5);
+    /// heap.push(2);
+    /// assert_eq!(heap.peek(), Some(&5));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Cost is *O*(1) in the worst case.
+    #[must_use]
+    pub fn peek(&self) -> Option<&T> {
+        self.heap.peek()
+    }
+
+    /// Returns an iterator visiting all distinct items and their counts in
+    /// ascending order.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::counted::CountedDaryHeap;
+    /// let mut heap = CountedDaryHeap::<_, 3>::new();
+    /// heap.push_n(2, 3);
+    /// heap.push(1);
+    ///
+    /// assert!(heap.iter().eq([(&1, 1), (&2, 3)]));
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            iter: self.counts.iter(),
+        }
+    }
+
+    /// Returns the number of items in the *d*-ary heap, counting every copy.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the number of distinct items in the *d*-ary heap.
+    #[must_use]
+    pub fn distinct_len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Checks if the *d*-ary heap is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Drops all items from the *d*-ary heap.
+    pub fn clear(&mut self) {
+        self.heap.clear();
+        self.counts.clear();
+        self.len = 0;
+    }
+
+    /// Consumes the `CountedDaryHeap` and returns a vector of its distinct
+    /// items and their counts in ascending order.
+    #[must_use = "`self` will be dropped if the result is not used"]
+    pub fn into_vec(self) -> Vec<(T, usize)> {
+        self.counts.into_iter().collect()
+    }
+}
+
+/// An iterator over the distinct items and counts of a `CountedDaryHeap`.
+///
+/// This `struct` is created by [`CountedDaryHeap::iter()`]. See its
+/// documentation for more.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Iter<'a, T: 'a> {
+    iter: btree_map::Iter<'a, T, usize>,
+}
+
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Iter {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Iter<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Iter").field(&self.iter).finish()
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = (&'a T, usize);
+
+    #[inline]
+    fn next(&mut self) -> Option<(&'a T, usize)> {
+        self.iter.next().map(|(item, &count)| (item, count))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<(&'a T, usize)> {
+        self.iter.next_back().map(|(item, &count)| (item, count))
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+impl<'a, T, const D: usize> IntoIterator for &'a CountedDaryHeap<T, D> {
+    type Item = (&'a T, usize);
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<T: Ord + Clone, const D: usize> Extend<T> for CountedDaryHeap<T, D> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.push(item);
+        }
+    }
+}
+
+impl<T: Ord + Clone, const D: usize> core::iter::FromIterator<T> for CountedDaryHeap<T, D> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> CountedDaryHeap<T, D> {
+        let mut heap = CountedDaryHeap::new();
+        heap.extend(iter);
+        heap
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{thread_rng, Rng};
+
+    fn check<const D: usize>() {
+        let mut rng = thread_rng();
+        let mut heap = CountedDaryHeap::<u32, D>::new();
+        let mut reference: Vec<u32> = Vec::new();
+        for _ in 0..2000 {
+            match rng.gen_range(0..4) {
+                0 => {
+                    let x = rng.gen_range(0..20);
+                    heap.push(x);
+                    reference.push(x);
+                }
+                1 => {
+                    let x = rng.gen_range(0..20);
+                    let n = rng.gen_range(0..5);
+                    heap.push_n(x, n);
+                    reference.extend(core::iter::repeat(x).take(n));
+                }
+                2 => {
+                    reference.sort_unstable();
+                    assert_eq!(heap.pop(), reference.pop());
+                }
+                _ => {
+                    reference.sort_unstable();
+                    let expected = reference.pop().map(|max| {
+                        let count = 1 + reference.iter().rev().take_while(|&&x| x == max).count();
+                        reference.truncate(reference.len() + 1 - count);
+                        (max, count)
+                    });
+                    assert_eq!(heap.pop_all(), expected);
+                }
+            }
+            assert_eq!(heap.len(), reference.len());
+            assert_eq!(heap.peek(), reference.iter().max());
+            for x in 0..20 {
+                let count = reference.iter().filter(|&&y| y == x).count();
+                assert_eq!(heap.count(&x), count);
+            }
+            let mut distinct = reference.clone();
+            distinct.sort_unstable();
+            distinct.dedup();
+            assert_eq!(heap.distinct_len(), distinct.len());
+        }
+    }
+
+    #[test]
+    fn random_operations() {
+        check::<1>();
+        check::<2>();
+        check::<3>();
+        check::<4>();
+        check::<7>();
+    }
+}
@@ -0,0 +1,420 @@
+//! A queue of items that become available after a deadline.
+//!
+//! The [`DelayQueue`] type in this module stores every item together with a
+//! deadline at which it expires, and returns the items in order of their
+//! deadlines once they have expired. Inserted items can be cancelled or given
+//! a new deadline using the returned [`Key`], which makes it suitable for
+//! managing timers.
+//!
+//! Deadlines can be of any type that implements [`Ord`], such as the ticks of
+//! a hardware timer. The current time can be read using a [`Clock`]. With the
+//! `std` feature, deadlines default to [`std::time::Instant`], and the queue
+//! can block the current thread until the next item expires.
+
+use core::cmp::Ordering;
+use core::fmt;
+
+#[cfg(feature = "std")]
+use std::thread;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+use crate::addressable::{AddressableDaryHeap, Handle};
+
+/// A source of the current time, expressed as a tick of type
+/// [`Clock::Tick`].
+///
+/// The ticks returned by a clock must never decrease, and must be comparable
+/// to the deadlines of the items in the [`DelayQueue`] they are used with. This
+/// trait is implemented for closures returning a tick, so e.g. a function
+/// reading a hardware timer counter can be used as a clock.
+///
+/// # Examples
+///
+/// ```
+/// use dary_heap::delay::Clock;
+/// use std::cell::Cell;
+///
+/// let ticks = Cell::new(0u32);
+/// let clock = || ticks.get();
+///
+/// ticks.set(3);
+/// assert_eq!(clock.now(), 3);
+/// ```
+pub trait Clock {
+    /// The type of the ticks of the clock.
+    type Tick: Ord;
+
+    /// Returns the current tick.
+    fn now(&self) -> Self::Tick;
+}
+
+impl<K: Ord, F: Fn() -> K> Clock for F {
+    type Tick = K;
+
+    fn now(&self) -> K {
+        self()
+    }
+}
+
+/// A key to an item in a [`DelayQueue`].
+///
+/// A key stays valid until its item is removed from the queue, and does not
+/// refer to any item afterwards. Using a key with a queue other than the one
+/// that created it is a logic error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Key(Handle);
+
+/// An item with its deadline, ordered such that the earliest deadline is the
+/// greatest.
+struct Expiration<T, K> {
+    deadline: K,
+    value: T,
+}
+
+impl<T, K: Ord> PartialEq for Expiration<T, K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.deadline == other.deadline
+    }
+}
+
+impl<T, K: Ord> Eq for Expiration<T, K> {}
+
+impl<T, K: Ord> PartialOrd for Expiration<T, K> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T, K: Ord> Ord for Expiration<T, K> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.deadline.cmp(&self.deadline)
+    }
+}
+
+/// A queue of items that expire at a deadline of type `K`, implemented with a
+/// quaternary heap.
+///
+/// Items with the same deadline are returned in arbitrary order. With the
+/// `std` feature, `K` defaults to [`Instant`].
+///
+/// # Examples
+///
+/// ```
+/// use dary_heap::delay::DelayQueue;
+///
+/// let mut queue = DelayQueue::new();
+/// queue.insert("later", 60_000u64);
+/// let key = queue.insert("cancelled", 0);
+/// queue.insert("soon", 1);
+///
+/// assert_eq!(queue.cancel(key), Some("cancelled"));
+/// assert_eq!(queue.pop_expired(0), None);
+/// assert_eq!(queue.pop_expired(5), Some("soon"));
+/// assert_eq!(queue.len(), 1);
+/// ```
+#[cfg(feature = "std")]
+pub struct DelayQueue<T, K = Instant> {
+    heap: AddressableDaryHeap<Expiration<T, K>, 4>,
+}
+
+/// A queue of items that expire at a deadline of type `K`, implemented with a
+/// quaternary heap.
+///
+/// Items with the same deadline are returned in arbitrary order. With the
+/// `std` feature, `K` defaults to `Instant`.
+///
+/// # Examples
+///
+/// ```
+/// use dary_heap::delay::DelayQueue;
+///
+/// let mut queue = DelayQueue::new();
+/// queue.insert("later", 60_000u64);
+/// let key = queue.insert("cancelled", 0);
+/// queue.insert("soon", 1);
+///
+/// assert_eq!(queue.cancel(key), Some("cancelled"));
+/// assert_eq!(queue.pop_expired(0), None);
+/// assert_eq!(queue.pop_expired(5), Some("soon"));
+/// assert_eq!(queue.len(), 1);
+/// ```
+#[cfg(not(feature = "std"))]
+pub struct DelayQueue<T, K> {
+    heap: AddressableDaryHeap<Expiration<T, K>, 4>,
+}
+
+impl<T, K: Ord> Default for DelayQueue<T, K> {
+    /// Creates an empty `DelayQueue<T, K>`.
+    #[inline]
+    fn default() -> DelayQueue<T, K> {
+        DelayQueue::new()
+    }
+}
+
+impl<T: fmt::Debug, K: fmt::Debug> fmt::Debug for DelayQueue<T, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(
+                self.heap
+                    .iter()
+                    .map(|(_, expiration)| (&expiration.value, &expiration.deadline)),
+            )
+            .finish()
+    }
+}
+
+impl<T, K: Ord> DelayQueue<T, K> {
+    /// Creates an empty `DelayQueue<T, K>`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::delay::DelayQueue;
+    /// let mut queue = DelayQueue::new();
+    /// queue.insert(4, 10u32);
+    /// ```
+    #[must_use]
+    pub fn new() -> DelayQueue<T, K> {
+        DelayQueue {
+            heap: AddressableDaryHeap::new(),
+        }
+    }
+
+    /// Creates an empty `DelayQueue<T, K>` with at least the specified
+    /// capacity.
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> DelayQueue<T, K> {
+        DelayQueue {
+            heap: AddressableDaryHeap::with_capacity(capacity),
+        }
+    }
+
+    /// Inserts an item that expires at `deadline`, and returns a key to it.
+    ///
+    /// # Time complexity
+    ///
+    /// The worst case cost of `insert` on a queue containing *n* items is
+    /// *O*(log(*n*)).
+    pub fn insert(&mut self, value: T, deadline: K) -> Key {
+        Key(self.heap.push(Expiration { deadline, value }))
+    }
+
+    /// Removes the item that `key` refers to and returns it, or `None` if the
+    /// key is no longer valid.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::delay::DelayQueue;
+    /// let mut queue = DelayQueue::new();
+    /// let key = queue.insert(1, 10u32);
+    ///
+    /// assert_eq!(queue.cancel(key), Some(1));
+    /// assert_eq!(queue.cancel(key), None);
+    /// assert!(queue.is_empty());
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The worst case cost of `cancel` on a queue containing *n* items is
+    /// *O*(log(*n*)).
+    pub fn cancel(&mut self, key: Key) -> Option<T> {
+        self.heap.remove(key.0).map(|expiration| expiration.value)
+    }
+
+    /// Changes the deadline of the item that `key` refers to, and returns
+    /// whether the key is still valid.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::delay::DelayQueue;
+    /// let mut queue = DelayQueue::new();
+    /// let key = queue.insert(1, 60u32);
+    ///
+    /// assert!(queue.reset(key, 10));
+    /// assert_eq!(queue.pop_expired(10), Some(1));
+    /// assert!(!queue.reset(key, 10));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The worst case cost of `reset` on a queue containing *n* items is
+    /// *O*(log(*n*)).
+    pub fn reset(&mut self, key: Key, deadline: K) -> bool {
+        self.heap
+            .modify(key.0, |expiration| expiration.deadline = deadline)
+    }
+
+    /// Returns the deadline of the item that `key` refers to, or `None` if
+    /// the key is no longer valid.
+    #[must_use]
+    pub fn deadline(&self, key: Key) -> Option<&K> {
+        self.heap.get(key.0).map(|expiration| &expiration.deadline)
+    }
+
+    /// Returns the earliest deadline of all items, or `None` if the queue is
+    /// empty.
+    ///
+    /// # Time complexity
+    ///
+    /// Cost is *O*(1) in the worst case.
+    #[must_use]
+    pub fn peek_deadline(&self) -> Option<&K> {
+        self.heap.peek().map(|expiration| &expiration.deadline)
+    }
+
+    /// Removes the item with the earliest deadline and returns it if it has
+    /// expired at `now`, or returns `None` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::delay::DelayQueue;
+    /// let mut queue = DelayQueue::new();
+    /// queue.insert(1, 10u32);
+    /// queue.insert(2, 60);
+    ///
+    /// assert_eq!(queue.pop_expired(10), Some(1));
+    /// assert_eq!(queue.pop_expired(10), None);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The worst case cost of `pop_expired` on a queue containing *n* items
+    /// is *O*(log(*n*)).
+    pub fn pop_expired(&mut self, now: K) -> Option<T> {
+        if *self.peek_deadline()? <= now {
+            self.heap.pop().map(|expiration| expiration.value)
+        } else {
+            None
+        }
+    }
+
+    /// Removes the item with the earliest deadline and returns it if it has
+    /// expired at the current tick of `clock`, or returns `None` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::delay::DelayQueue;
+    /// use std::cell::Cell;
+    /// let ticks = Cell::new(0u32);
+    /// let clock = || ticks.get();
+    /// let mut queue = DelayQueue::new();
+    /// queue.insert(1, 10);
+    ///
+    /// assert_eq!(queue.pop_expired_by(&clock), None);
+    /// ticks.set(10);
+    /// assert_eq!(queue.pop_expired_by(&clock), Some(1));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The worst case cost of `pop_expired_by` on a queue containing *n*
+    /// items is *O*(log(*n*)).
+    pub fn pop_expired_by<C: Clock<Tick = K>>(&mut self, clock: &C) -> Option<T> {
+        self.pop_expired(clock.now())
+    }
+
+    /// Returns the number of items in the queue.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Checks if the queue is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Drops all items from the queue. All keys become invalid.
+    pub fn clear(&mut self) {
+        self.heap.clear();
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl<T> DelayQueue<T, Instant> {
+    /// Removes the item with the earliest deadline and returns it, blocking
+    /// the current thread until it has expired. Returns `None` if the queue
+    /// is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::delay::DelayQueue;
+    /// use std::time::{Duration, Instant};
+    /// let deadline = Instant::now() + Duration::from_millis(10);
+    /// let mut queue = DelayQueue::new();
+    /// queue.insert(1, deadline);
+    ///
+    /// assert_eq!(queue.next(), Some(1));
+    /// assert!(Instant::now() >= deadline);
+    /// assert_eq!(queue.next(), None);
+    /// ```
+    // Implementing `Iterator` would make a blocking iterator out of the queue,
+    // which is easy to misuse.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<T> {
+        loop {
+            let deadline = *self.peek_deadline()?;
+            let now = Instant::now();
+            if deadline <= now {
+                return self.pop_expired(now);
+            }
+            thread::sleep(deadline - now);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+    use rand::{thread_rng, Rng};
+
+    #[test]
+    fn expires_in_order() {
+        let mut rng = thread_rng();
+        let mut queue = DelayQueue::new();
+        let mut keys = Vec::new();
+        for i in 0..100u64 {
+            let deadline = rng.gen_range(0..1000);
+            let key = queue.insert((deadline, i), deadline);
+            keys.push((key, deadline, i));
+        }
+        for &(key, deadline, i) in keys.iter().step_by(3) {
+            assert_eq!(queue.cancel(key), Some((deadline, i)));
+        }
+        for &(key, _, i) in keys.iter().skip(1).step_by(3) {
+            assert!(queue.reset(key, 1000 + i));
+        }
+
+        let mut previous = None;
+        let mut count = 0;
+        while let Some((deadline, i)) = queue.pop_expired(2000) {
+            let deadline = if i % 3 == 1 { 1000 + i } else { deadline };
+            assert!(previous <= Some(deadline));
+            previous = Some(deadline);
+            count += 1;
+        }
+        assert_eq!(count, 66);
+        assert!(queue.is_empty());
+    }
+}
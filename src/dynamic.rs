@@ -0,0 +1,463 @@
+//! A *d*-ary heap with an arity chosen at run time.
+//!
+//! The [`DynDaryHeap`] type in this module stores its arity as a field instead
+//! of as a const generic parameter. This is useful when the arity comes from
+//! configuration or is determined by benchmarking at run time. Heaps with an
+//! arity known at compile time can use specialized code for small arities, so
+//! [`DaryHeap`] should be preferred where possible.
+//!
+//! [`DaryHeap`]: crate::DaryHeap
+
+use core::fmt;
+use core::mem::swap;
+
+use alloc::vec::Vec;
+
+use crate::compare::Natural;
+use crate::{sift, Drain, IntoIter, Iter};
+
+/// A priority queue implemented with a *d*-ary heap, with *d* chosen at run
+/// time.
+///
+/// This will be a max-heap. Apart from the arity, it behaves like
+/// [`DaryHeap`]; see its documentation for more information.
+///
+/// [`DaryHeap`]: crate::DaryHeap
+///
+/// # Examples
+///
+/// ```
+/// use dary_heap::dynamic::DynDaryHeap;
+///
+/// let arity = "3".parse().unwrap();
+/// let mut heap = DynDaryHeap::new(arity);
+/// heap.push(1);
+/// heap.push(5);
+/// heap.push(2);
+///
+/// assert_eq!(heap.arity(), 3);
+/// assert_eq!(heap.pop(), Some(5));
+/// assert_eq!(heap.pop(), Some(2));
+/// assert_eq!(heap.pop(), Some(1));
+/// assert_eq!(heap.pop(), None);
+/// ```
+#[derive(Clone)]
+pub struct DynDaryHeap<T> {
+    data: Vec<T>,
+    arity: usize,
+}
+
+impl<T: fmt::Debug> fmt::Debug for DynDaryHeap<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Ord> DynDaryHeap<T> {
+    /// Creates an empty `DynDaryHeap` as a max-heap with arity `arity`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `arity` is zero.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::dynamic::DynDaryHeap;
+    /// let mut heap = DynDaryHeap::new(4);
+    /// heap.push(4);
+    /// ```
+    #[must_use]
+    pub fn new(arity: usize) -> DynDaryHeap<T> {
+        DynDaryHeap::with_capacity(arity, 0)
+    }
+
+    /// Creates an empty `DynDaryHeap` with arity `arity` and at least the
+    /// specified capacity.
+    ///
+    /// The *d*-ary heap will be able to hold at least `capacity` elements without
+    /// reallocating. This method is allowed to allocate for more elements than
+    /// `capacity`. If `capacity` is 0, the *d*-ary heap will not allocate.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `arity` is zero.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::dynamic::DynDaryHeap;
+    /// let mut heap = DynDaryHeap::with_capacity(4, 10);
+    /// heap.push(4);
+    /// ```
+    #[must_use]
+    pub fn with_capacity(arity: usize, capacity: usize) -> DynDaryHeap<T> {
+        assert_ne!(arity, 0, "Arity should be greater than zero");
+        DynDaryHeap {
+            data: Vec::with_capacity(capacity),
+            arity,
+        }
+    }
+
+    /// Converts a vector into a `DynDaryHeap` with arity `arity`.
+    ///
+    /// This conversion happens in-place, and has *O*(*n*) time complexity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `arity` is zero.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::dynamic::DynDaryHeap;
+    /// let heap = DynDaryHeap::from_vec(vec![1, 5, 2], 5);
+    /// assert_eq!(heap.peek(), Some(&5));
+    /// ```
+    #[must_use]
+    pub fn from_vec(vec: Vec<T>, arity: usize) -> DynDaryHeap<T> {
+        assert_ne!(arity, 0, "Arity should be greater than zero");
+        let mut heap = DynDaryHeap { data: vec, arity };
+        sift::rebuild(&mut heap.data, heap.arity, &Natural);
+        heap
+    }
+
+    /// Removes the greatest item from the *d*-ary heap and returns it, or `None` if it
+    /// is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::dynamic::DynDaryHeap;
+    /// let mut heap = DynDaryHeap::from_vec(vec![1, 3], 2);
+    ///
+    /// assert_eq!(heap.pop(), Some(3));
+    /// assert_eq!(heap.pop(), Some(1));
+    /// assert_eq!(heap.pop(), None);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The worst case cost of `pop` on a heap containing *n* elements is *O*(log(*n*)).
+    pub fn pop(&mut self) -> Option<T> {
+        let arity = self.arity;
+        self.data.pop().map(|mut item| {
+            if !self.is_empty() {
+                swap(&mut item, &mut self.data[0]);
+                // SAFETY: !self.is_empty() means that self.len() > 0
+                unsafe { sift::sift_down_to_bottom(&mut self.data, arity, &Natural, 0) };
+            }
+            item
+        })
+    }
+
+    /// Pushes an item onto the *d*-ary heap.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::dynamic::DynDaryHeap;
+    /// let mut heap = DynDaryHeap::new(3);
+    /// heap.push(3);
+    /// heap.push(5);
+    /// heap.push(1);
+    ///
+    /// assert_eq!(heap.len(), 3);
+    /// assert_eq!(heap.peek(), Some(&5));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The expected cost of `push`, averaged over every possible ordering of
+    /// the elements being pushed, and over a sufficiently large number of
+    /// pushes, is *O*(1). See [`DaryHeap::push`] for a more detailed analysis.
+    ///
+    /// [`DaryHeap::push`]: crate::DaryHeap::push
+    pub fn push(&mut self, item: T) {
+        let old_len = self.len();
+        self.data.push(item);
+        // SAFETY: Since we pushed a new item it means that
+        //  old_len = self.len() - 1 < self.len()
+        unsafe { sift::sift_up(&mut self.data, self.arity, &Natural, 0, old_len) };
+    }
+
+    /// Consumes the `DynDaryHeap` and returns a vector in sorted
+    /// (ascending) order.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::dynamic::DynDaryHeap;
+    ///
+    /// let mut heap = DynDaryHeap::from_vec(vec![1, 2, 4, 5, 7], 6);
+    /// heap.push(6);
+    /// heap.push(3);
+    ///
+    /// let vec = heap.into_sorted_vec();
+    /// assert_eq!(vec, [1, 2, 3, 4, 5, 6, 7]);
+    /// ```
+    #[must_use = "`self` will be dropped if the result is not used"]
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        sift::sort_heap(&mut self.data, self.arity, &Natural);
+        self.into_vec()
+    }
+
+    /// Moves all the elements of `other` into `self`, leaving `other` empty.
+    ///
+    /// The arity of `other` does not need to match that of `self`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::dynamic::DynDaryHeap;
+    ///
+    /// let mut a = DynDaryHeap::from_vec(vec![-10, 1, 2, 3, 3], 3);
+    /// let mut b = DynDaryHeap::from_vec(vec![-20, 5, 43], 7);
+    ///
+    /// a.append(&mut b);
+    ///
+    /// assert_eq!(a.into_sorted_vec(), [-20, -10, 1, 2, 3, 3, 5, 43]);
+    /// assert!(b.is_empty());
+    /// ```
+    pub fn append(&mut self, other: &mut Self) {
+        let start = self.data.len();
+        self.data.append(&mut other.data);
+        sift::rebuild_tail(&mut self.data, self.arity, &Natural, start);
+    }
+}
+
+impl<T> DynDaryHeap<T> {
+    /// Returns the arity *d* of the *d*-ary heap.
+    #[must_use]
+    pub fn arity(&self) -> usize {
+        self.arity
+    }
+
+    /// Returns an iterator visiting all values in the underlying vector, in
+    /// arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::dynamic::DynDaryHeap;
+    /// let heap = DynDaryHeap::from_vec(vec![1, 2, 3, 4], 3);
+    ///
+    /// // Print 1, 2, 3, 4 in arbitrary order
+    /// for x in heap.iter() {
+    ///     println!("{}", x);
+    /// }
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            iter: self.data.iter(),
+        }
+    }
+
+    /// Returns the greatest item in the *d*-ary heap, or `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::dynamic::DynDaryHeap;
+    /// let mut heap = DynDaryHeap::new(2);
+    /// assert_eq!(heap.peek(), None);
+    ///
+    /// heap.push(1);
+    /// heap.push(5);
+    /// heap.push(2);
+    /// assert_eq!(heap.peek(), Some(&5));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Cost is *O*(1) in the worst case.
+    #[must_use]
+    pub fn peek(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    /// Returns the number of elements the *d*-ary heap can hold without reallocating.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.data.capacity()
+    }
+
+    /// Reserves the minimum capacity for at least `additional` elements more than
+    /// the current length. See [`DaryHeap::reserve_exact`] for more information.
+    ///
+    /// [`DaryHeap::reserve_exact`]: crate::DaryHeap::reserve_exact
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity overflows [`usize`].
+    pub fn reserve_exact(&mut self, additional: usize) {
+        self.data.reserve_exact(additional);
+    }
+
+    /// Reserves capacity for at least `additional` elements more than the
+    /// current length. See [`DaryHeap::reserve`] for more information.
+    ///
+    /// [`DaryHeap::reserve`]: crate::DaryHeap::reserve
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity overflows [`usize`].
+    pub fn reserve(&mut self, additional: usize) {
+        self.data.reserve(additional);
+    }
+
+    /// Discards as much additional capacity as possible.
+    pub fn shrink_to_fit(&mut self) {
+        self.data.shrink_to_fit();
+    }
+
+    /// Returns a slice of all values in the underlying vector, in arbitrary
+    /// order.
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        self.data.as_slice()
+    }
+
+    /// Consumes the `DynDaryHeap` and returns the underlying vector
+    /// in arbitrary order.
+    #[must_use = "`self` will be dropped if the result is not used"]
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    /// Returns the length of the *d*-ary heap.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Checks if the *d*-ary heap is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Clears the *d*-ary heap, returning an iterator over the removed elements
+    /// in arbitrary order. If the iterator is dropped before being fully
+    /// consumed, it drops the remaining elements in arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::dynamic::DynDaryHeap;
+    /// let mut heap = DynDaryHeap::from_vec(vec![1, 3], 4);
+    ///
+    /// assert!(!heap.is_empty());
+    ///
+    /// for x in heap.drain() {
+    ///     println!("{}", x);
+    /// }
+    ///
+    /// assert!(heap.is_empty());
+    /// ```
+    #[inline]
+    pub fn drain(&mut self) -> Drain<'_, T> {
+        Drain {
+            iter: self.data.drain(..),
+        }
+    }
+
+    /// Drops all items from the *d*-ary heap.
+    pub fn clear(&mut self) {
+        self.drain();
+    }
+}
+
+impl<T> From<DynDaryHeap<T>> for Vec<T> {
+    /// Converts a `DynDaryHeap<T>` into a `Vec<T>`.
+    ///
+    /// This conversion requires no data movement or allocation, and has
+    /// constant time complexity.
+    fn from(heap: DynDaryHeap<T>) -> Vec<T> {
+        heap.data
+    }
+}
+
+impl<T> IntoIterator for DynDaryHeap<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    /// Creates a consuming iterator, that is, one that moves each value out of
+    /// the *d*-ary heap in arbitrary order.
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter {
+            iter: self.data.into_iter(),
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a DynDaryHeap<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<T: Ord> Extend<T> for DynDaryHeap<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let start = self.data.len();
+        self.data.extend(iter);
+        sift::rebuild_tail(&mut self.data, self.arity, &Natural, start);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DaryHeap;
+    use rand::{seq::SliceRandom, thread_rng};
+
+    #[test]
+    fn matches_const_arity() {
+        let mut data: Vec<_> = (0..200).collect();
+        data.shuffle(&mut thread_rng());
+
+        let mut heap = DynDaryHeap::from_vec(data[..100].to_vec(), 5);
+        let mut reference = DaryHeap::<_, 5>::from(data[..100].to_vec());
+        assert_eq!(heap.as_slice(), reference.as_slice());
+        for &x in &data[100..150] {
+            heap.push(x);
+            reference.push(x);
+        }
+        heap.extend(data[150..].iter().copied());
+        reference.extend(data[150..].iter().copied());
+        assert_eq!(heap.as_slice(), reference.as_slice());
+        while let Some(x) = reference.pop() {
+            assert_eq!(heap.pop(), Some(x));
+        }
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Arity should be greater than zero")]
+    fn zero_arity() {
+        let _ = DynDaryHeap::<i32>::new(0);
+    }
+}
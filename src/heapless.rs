@@ -0,0 +1,394 @@
+//! A fixed-capacity *d*-ary heap backed by [`heapless::Vec`].
+//!
+//! The [`HeaplessDaryHeap`] type in this module is like
+//! [`ArrayDaryHeap`](crate::array::ArrayDaryHeap), but stores its elements in
+//! a [`heapless::Vec`]. It never allocates, and converts to and from the
+//! vector type used throughout the [`heapless`] ecosystem without moving any
+//! items.
+//!
+//! [`heapless`]: https://docs.rs/heapless
+//! [`heapless::Vec`]: https://docs.rs/heapless/0.8/heapless/struct.Vec.html
+
+use core::fmt;
+use core::iter::FusedIterator;
+use core::mem::swap;
+
+use ::heapless::Vec;
+
+use crate::compare::Natural;
+use crate::{sift, Iter};
+
+/// A fixed-capacity priority queue implemented with a *d*-ary heap, backed by
+/// a `heapless::Vec<T, N>`.
+///
+/// This will be a max-heap that can hold at most `N` elements, stored inline
+/// without allocating. Apart from [`push`] being fallible, it behaves like
+/// [`DaryHeap`]; see its documentation for more information.
+///
+/// [`push`]: HeaplessDaryHeap::push
+/// [`DaryHeap`]: crate::DaryHeap
+///
+/// # Examples
+///
+/// ```
+/// use dary_heap::heapless::HeaplessDaryHeap;
+///
+/// let mut heap = HeaplessDaryHeap::<_, 3, 4>::new();
+/// assert_eq!(heap.push(1), Ok(()));
+/// assert_eq!(heap.push(5), Ok(()));
+/// assert_eq!(heap.push(2), Ok(()));
+///
+/// // The heap is full now
+/// assert_eq!(heap.push(3), Err(3));
+///
+/// assert_eq!(heap.pop(), Some(5));
+/// assert_eq!(heap.pop(), Some(2));
+/// assert_eq!(heap.pop(), Some(1));
+/// assert_eq!(heap.pop(), None);
+/// ```
+pub struct HeaplessDaryHeap<T, const N: usize, const D: usize> {
+    data: Vec<T, N>,
+}
+
+impl<T: Clone, const N: usize, const D: usize> Clone for HeaplessDaryHeap<T, N, D> {
+    fn clone(&self) -> Self {
+        HeaplessDaryHeap {
+            data: self.data.clone(),
+        }
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        self.data.clone_from(&source.data);
+    }
+}
+
+impl<T: Ord, const N: usize, const D: usize> Default for HeaplessDaryHeap<T, N, D> {
+    /// Creates an empty `HeaplessDaryHeap<T, N, D>`.
+    #[inline]
+    fn default() -> HeaplessDaryHeap<T, N, D> {
+        HeaplessDaryHeap::new()
+    }
+}
+
+impl<T: fmt::Debug, const N: usize, const D: usize> fmt::Debug for HeaplessDaryHeap<T, N, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Ord, const N: usize, const D: usize> HeaplessDaryHeap<T, N, D> {
+    /// Creates an empty `HeaplessDaryHeap` as a max-heap.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::heapless::HeaplessDaryHeap;
+    /// let mut heap = HeaplessDaryHeap::<_, 16, 3>::new();
+    /// heap.push(4).unwrap();
+    /// ```
+    #[must_use]
+    pub const fn new() -> HeaplessDaryHeap<T, N, D> {
+        HeaplessDaryHeap { data: Vec::new() }
+    }
+
+    /// Removes the greatest item from the *d*-ary heap and returns it, or `None` if it
+    /// is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::heapless::HeaplessDaryHeap;
+    /// let mut heap = HeaplessDaryHeap::<_, 2, 2>::new();
+    /// heap.push(1).unwrap();
+    /// heap.push(3).unwrap();
+    ///
+    /// assert_eq!(heap.pop(), Some(3));
+    /// assert_eq!(heap.pop(), Some(1));
+    /// assert_eq!(heap.pop(), None);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The worst case cost of `pop` on a heap containing *n* elements is *O*(log(*n*)).
+    pub fn pop(&mut self) -> Option<T> {
+        self.data.pop().map(|mut item| {
+            if !self.is_empty() {
+                swap(&mut item, &mut self.data[0]);
+                // SAFETY: !self.is_empty() means that self.len() > 0
+                unsafe { sift::sift_down_to_bottom(&mut self.data, sift::Const::<D>, &Natural, 0) };
+            }
+            item
+        })
+    }
+
+    /// Pushes an item onto the *d*-ary heap, or returns it as an error if the
+    /// heap is full.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::heapless::HeaplessDaryHeap;
+    /// let mut heap = HeaplessDaryHeap::<_, 3, 4>::new();
+    /// heap.push(3).unwrap();
+    /// heap.push(5).unwrap();
+    /// heap.push(1).unwrap();
+    ///
+    /// assert_eq!(heap.len(), 3);
+    /// assert_eq!(heap.peek(), Some(&5));
+    /// assert_eq!(heap.push(7), Err(7));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The expected cost of `push`, averaged over every possible ordering of
+    /// the elements being pushed, and over a sufficiently large number of
+    /// pushes, is *O*(1). The worst case cost is *O*(log(*n*)).
+    pub fn push(&mut self, item: T) -> Result<(), T> {
+        let old_len = self.len();
+        self.data.push(item)?;
+        // SAFETY: Since we pushed a new item it means that
+        //  old_len = self.len() - 1 < self.len()
+        unsafe { sift::sift_up(&mut self.data, sift::Const::<D>, &Natural, 0, old_len) };
+        Ok(())
+    }
+}
+
+impl<T, const N: usize, const D: usize> HeaplessDaryHeap<T, N, D> {
+    /// Returns an iterator visiting all values in the underlying vector, in
+    /// arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::heapless::HeaplessDaryHeap;
+    /// let heap = HeaplessDaryHeap::<_, 4, 3>::from(heapless::Vec::from_slice(&[1, 2, 3, 4]).unwrap());
+    ///
+    /// // Print 1, 2, 3, 4 in arbitrary order
+    /// for x in heap.iter() {
+    ///     println!("{}", x);
+    /// }
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            iter: self.data.iter(),
+        }
+    }
+
+    /// Returns the greatest item in the *d*-ary heap, or `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::heapless::HeaplessDaryHeap;
+    /// let mut heap = HeaplessDaryHeap::<_, 4, 2>::new();
+    /// assert_eq!(heap.peek(), None);
+    ///
+    /// heap.push(1).unwrap();
+    /// heap.push(5).unwrap();
+    /// heap.push(2).unwrap();
+    /// assert_eq!(heap.peek(), Some(&5));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Cost is *O*(1) in the worst case.
+    #[must_use]
+    pub fn peek(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    /// Returns the number of elements the *d*-ary heap can hold, which is `N`.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns a slice of all values in the underlying vector, in arbitrary
+    /// order.
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    /// Consumes the `HeaplessDaryHeap` and returns the underlying vector
+    /// in arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::heapless::HeaplessDaryHeap;
+    /// let mut heap = HeaplessDaryHeap::<_, 8, 2>::new();
+    /// for &x in &[1, 2, 3, 4, 5, 6, 7] {
+    ///     heap.push(x).unwrap();
+    /// }
+    /// let vec = heap.into_vec();
+    ///
+    /// // Will print in some order
+    /// for x in vec {
+    ///     println!("{}", x);
+    /// }
+    /// ```
+    #[must_use = "`self` will be dropped if the result is not used"]
+    pub fn into_vec(self) -> Vec<T, N> {
+        self.data
+    }
+
+    /// Returns the length of the *d*-ary heap.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Checks if the *d*-ary heap is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Checks if the *d*-ary heap is full, in which case [`push`] fails.
+    ///
+    /// [`push`]: HeaplessDaryHeap::push
+    #[must_use]
+    pub fn is_full(&self) -> bool {
+        self.data.is_full()
+    }
+
+    /// Drops all items from the *d*-ary heap.
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+}
+
+impl<T: Ord, const N: usize, const D: usize> From<Vec<T, N>> for HeaplessDaryHeap<T, N, D> {
+    /// Converts a `heapless::Vec<T, N>` into a `HeaplessDaryHeap<T, N, D>`.
+    ///
+    /// This conversion happens in-place, and has *O*(*n*) time complexity.
+    fn from(vec: Vec<T, N>) -> HeaplessDaryHeap<T, N, D> {
+        let mut heap = HeaplessDaryHeap { data: vec };
+        sift::rebuild(&mut heap.data, sift::Const::<D>, &Natural);
+        heap
+    }
+}
+
+impl<T, const N: usize, const D: usize> From<HeaplessDaryHeap<T, N, D>> for Vec<T, N> {
+    /// Converts a `HeaplessDaryHeap<T, N, D>` into a `heapless::Vec<T, N>`.
+    ///
+    /// This conversion requires no data movement or allocation, and has
+    /// constant time complexity.
+    fn from(heap: HeaplessDaryHeap<T, N, D>) -> Vec<T, N> {
+        heap.data
+    }
+}
+
+impl<T, const N: usize, const D: usize> IntoIterator for HeaplessDaryHeap<T, N, D> {
+    type Item = T;
+    type IntoIter = IntoIter<T, N>;
+
+    /// Creates a consuming iterator, that is, one that moves each value out of
+    /// the *d*-ary heap in arbitrary order.
+    fn into_iter(self) -> IntoIter<T, N> {
+        IntoIter { data: self.data }
+    }
+}
+
+/// An owning iterator over the elements of a `HeaplessDaryHeap`.
+///
+/// This `struct` is created by [`HeaplessDaryHeap::into_iter()`]
+/// (provided by the [`IntoIterator`] trait). See its documentation for more.
+pub struct IntoIter<T, const N: usize> {
+    data: Vec<T, N>,
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for IntoIter<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IntoIter")
+            .field(&self.data.as_slice())
+            .finish()
+    }
+}
+
+impl<T, const N: usize> Iterator for IntoIter<T, N> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.data.pop()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.data.len(), Some(self.data.len()))
+    }
+}
+
+impl<T, const N: usize> ExactSizeIterator for IntoIter<T, N> {}
+
+impl<T, const N: usize> FusedIterator for IntoIter<T, N> {}
+
+impl<'a, T, const N: usize, const D: usize> IntoIterator for &'a HeaplessDaryHeap<T, N, D> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::slice::is_heap;
+    use rand::{seq::SliceRandom, thread_rng};
+
+    #[test]
+    fn from_vec_round_trip() {
+        fn check<const D: usize>() {
+            let mut vec: Vec<u32, 64> = (0..64).collect();
+            vec.shuffle(&mut thread_rng());
+
+            let mut heap = HeaplessDaryHeap::<_, 64, D>::from(vec);
+            assert!(is_heap::<_, D>(heap.as_slice()));
+            assert!(heap.is_full());
+            assert_eq!(heap.push(64), Err(64));
+            assert_eq!(heap.pop(), Some(63));
+
+            let mut vec = heap.into_vec();
+            assert_eq!(vec.len(), 63);
+            assert!(is_heap::<_, D>(&vec));
+            assert_eq!(vec.push(100), Ok(()));
+
+            let heap = HeaplessDaryHeap::<_, 64, D>::from(vec);
+            assert_eq!(heap.peek(), Some(&100));
+            let mut vec = Vec::from(heap);
+            vec.sort_unstable();
+            assert!(vec.iter().copied().eq((0..63).chain([100])));
+        }
+        check::<1>();
+        check::<2>();
+        check::<3>();
+        check::<4>();
+        check::<7>();
+    }
+
+    #[test]
+    fn into_vec_keeps_layout() {
+        let mut heap = HeaplessDaryHeap::<_, 16, 4>::new();
+        for x in [5, 1, 8, 3, 9, 2] {
+            heap.push(x).unwrap();
+        }
+        let layout: Vec<i32, 16> = heap.iter().copied().collect();
+        assert_eq!(heap.into_vec(), layout);
+    }
+}
@@ -0,0 +1,671 @@
+//! A *d*-ary heap of priorities for dense integer ids.
+//!
+//! The [`IndexedDaryHeap`] type in this module stores a priority for each of
+//! a set of ids `0..n`, like the node ids of a graph. The position of every id
+//! in the heap is kept in a vector indexed by id, so looking up, changing or
+//! removing the priority of an id needs no hashing. This is a good fit for
+//! Dijkstra's algorithm and A* search over graphs with integer node ids.
+
+use core::fmt;
+use core::iter::FusedIterator;
+use core::slice;
+
+use alloc::vec::Vec;
+
+#[derive(Clone, Debug)]
+struct Entry<P> {
+    id: usize,
+    priority: P,
+}
+
+/// Position of an id that is not in the heap.
+const ABSENT: usize = usize::MAX;
+
+/// A priority queue of ids implemented with a *d*-ary heap.
+///
+/// This will be a max-heap, ordered by priority. Every id is in the heap at
+/// most once. The memory used for the positions of the ids is proportional to
+/// the greatest id pushed so far, so ids should be dense, e.g. indexes into a
+/// vector of graph nodes. For arbitrary keys or items, use
+/// [`AddressableDaryHeap`] instead.
+///
+/// [`AddressableDaryHeap`]: crate::addressable::AddressableDaryHeap
+///
+/// # Examples
+///
+/// Dijkstra's algorithm, using [`Reverse`] to pop the smallest distance
+/// first:
+///
+/// ```
+/// use dary_heap::indexed::IndexedDaryHeap;
+/// use std::cmp::Reverse;
+///
+/// let edges: &[&[(usize, u32)]] = &[&[(1, 4), (2, 1)], &[(3, 1)], &[(1, 2), (3, 5)], &[]];
+/// let mut dist = vec![u32::MAX; edges.len()];
+/// let mut heap = IndexedDaryHeap::<_, 4>::with_capacity(edges.len());
+/// heap.push(0, Reverse(0));
+///
+/// while let Some((node, Reverse(d))) = heap.pop() {
+///     dist[node] = d;
+///     for &(next, weight) in edges[node] {
+///         if dist[next] == u32::MAX {
+///             heap.push_increase(next, Reverse(d + weight));
+///         }
+///     }
+/// }
+/// assert_eq!(dist, [0, 3, 1, 4]);
+/// ```
+///
+/// [`Reverse`]: core::cmp::Reverse
+pub struct IndexedDaryHeap<P, const D: usize> {
+    data: Vec<Entry<P>>,
+    positions: Vec<usize>,
+}
+
+impl<P: Clone, const D: usize> Clone for IndexedDaryHeap<P, D> {
+    fn clone(&self) -> Self {
+        IndexedDaryHeap {
+            data: self.data.clone(),
+            positions: self.positions.clone(),
+        }
+    }
+}
+
+impl<P: Ord, const D: usize> Default for IndexedDaryHeap<P, D> {
+    /// Creates an empty `IndexedDaryHeap<P, D>`.
+    #[inline]
+    fn default() -> IndexedDaryHeap<P, D> {
+        IndexedDaryHeap::new()
+    }
+}
+
+impl<P: fmt::Debug, const D: usize> fmt::Debug for IndexedDaryHeap<P, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<P: Ord, const D: usize> IndexedDaryHeap<P, D> {
+    /// Creates an empty `IndexedDaryHeap` as a max-heap.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::indexed::IndexedDaryHeap;
+    /// let mut heap = IndexedDaryHeap::<_, 3>::new();
+    /// heap.push(0, 4);
+    /// ```
+    #[must_use]
+    pub fn new() -> IndexedDaryHeap<P, D> {
+        IndexedDaryHeap::with_capacity(0)
+    }
+
+    /// Creates an empty `IndexedDaryHeap` with room for the ids
+    /// `0..capacity`.
+    ///
+    /// The *d*-ary heap will be able to hold all ids smaller than `capacity`
+    /// without reallocating. If `capacity` is 0, the *d*-ary heap will not
+    /// allocate.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::indexed::IndexedDaryHeap;
+    /// let mut heap = IndexedDaryHeap::<_, 3>::with_capacity(10);
+    /// heap.push(9, 4);
+    /// ```
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> IndexedDaryHeap<P, D> {
+        IndexedDaryHeap {
+            data: Vec::with_capacity(capacity),
+            positions: alloc::vec![ABSENT; capacity],
+        }
+    }
+
+    /// Pushes `id` with priority `priority` onto the *d*-ary heap. If `id` is
+    /// already in the heap, its priority is replaced and the old priority is
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::indexed::IndexedDaryHeap;
+    /// let mut heap = IndexedDaryHeap::<_, 2>::new();
+    ///
+    /// assert_eq!(heap.push(3, 10), None);
+    /// assert_eq!(heap.push(5, 20), None);
+    /// assert_eq!(heap.push(3, 30), Some(10));
+    /// assert_eq!(heap.len(), 2);
+    /// assert_eq!(heap.peek(), Some((3, &30)));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` is `usize::MAX`.
+    ///
+    /// # Time complexity
+    ///
+    /// The worst case cost of `push` on a heap containing *n* elements is
+    /// *O*(log(*n*)), plus the cost of growing the position map if `id` is
+    /// greater than all ids pushed before.
+    pub fn push(&mut self, id: usize, priority: P) -> Option<P> {
+        match self.position(id) {
+            Some(pos) => {
+                let old = core::mem::replace(&mut self.data[pos].priority, priority);
+                let pos = self.sift_up(pos);
+                self.sift_down(pos);
+                Some(old)
+            }
+            None => {
+                self.push_new(id, priority);
+                None
+            }
+        }
+    }
+
+    /// Pushes `id` with priority `priority` onto the *d*-ary heap, or, if
+    /// `id` is already in the heap, increases its priority to `priority` if
+    /// that is greater. Returns `true` if the heap was changed.
+    ///
+    /// This is the relaxation step of Dijkstra's algorithm when used with
+    /// [`Reverse`] priorities.
+    ///
+    /// [`Reverse`]: core::cmp::Reverse
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::indexed::IndexedDaryHeap;
+    /// let mut heap = IndexedDaryHeap::<_, 2>::new();
+    ///
+    /// assert!(heap.push_increase(3, 10));
+    /// assert!(!heap.push_increase(3, 5));
+    /// assert!(heap.push_increase(3, 20));
+    /// assert_eq!(heap.get(3), Some(&20));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` is `usize::MAX`.
+    ///
+    /// # Time complexity
+    ///
+    /// The worst case cost of `push_increase` on a heap containing *n*
+    /// elements is *O*(log(*n*)), plus the cost of growing the position map
+    /// if `id` is greater than all ids pushed before.
+    pub fn push_increase(&mut self, id: usize, priority: P) -> bool {
+        match self.position(id) {
+            Some(pos) if priority > self.data[pos].priority => {
+                self.data[pos].priority = priority;
+                self.sift_up(pos);
+                true
+            }
+            Some(_) => false,
+            None => {
+                self.push_new(id, priority);
+                true
+            }
+        }
+    }
+
+    /// Removes the id with the greatest priority from the *d*-ary heap and
+    /// returns it with its priority, or `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::indexed::IndexedDaryHeap;
+    /// let mut heap = IndexedDaryHeap::<_, 2>::new();
+    /// heap.push(0, 1);
+    /// heap.push(1, 3);
+    ///
+    /// assert_eq!(heap.pop(), Some((1, 3)));
+    /// assert_eq!(heap.pop(), Some((0, 1)));
+    /// assert_eq!(heap.pop(), None);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The worst case cost of `pop` on a heap containing *n* elements is *O*(log(*n*)).
+    pub fn pop(&mut self) -> Option<(usize, P)> {
+        if self.data.is_empty() {
+            None
+        } else {
+            let entry = self.remove_pos(0);
+            Some((entry.id, entry.priority))
+        }
+    }
+
+    /// Removes `id` from the *d*-ary heap and returns its priority, or `None`
+    /// if it is not in the heap.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::indexed::IndexedDaryHeap;
+    /// let mut heap = IndexedDaryHeap::<_, 2>::new();
+    /// heap.push(0, 1);
+    /// heap.push(1, 3);
+    ///
+    /// assert_eq!(heap.remove(0), Some(1));
+    /// assert_eq!(heap.remove(0), None);
+    /// assert_eq!(heap.len(), 1);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The worst case cost of `remove` on a heap containing *n* elements is
+    /// *O*(log(*n*)).
+    pub fn remove(&mut self, id: usize) -> Option<P> {
+        let pos = self.position(id)?;
+        Some(self.remove_pos(pos).priority)
+    }
+
+    /// Replaces the priority of `id` by `priority`, moves it to its new place
+    /// in the *d*-ary heap and returns the old priority.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` is not in the heap.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::indexed::IndexedDaryHeap;
+    /// let mut heap = IndexedDaryHeap::<_, 2>::new();
+    /// heap.push(0, 1);
+    /// heap.push(1, 3);
+    ///
+    /// assert_eq!(heap.update(0, 5), 1);
+    /// assert_eq!(heap.peek(), Some((0, &5)));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The worst case cost of `update` on a heap containing *n* elements is
+    /// *O*(log(*n*)).
+    pub fn update(&mut self, id: usize, priority: P) -> P {
+        let pos = self.expect_position(id);
+        let old = core::mem::replace(&mut self.data[pos].priority, priority);
+        let pos = self.sift_up(pos);
+        self.sift_down(pos);
+        old
+    }
+
+    /// Replaces the priority of `id` by a greater or equal `priority` and
+    /// returns the old priority.
+    ///
+    /// This only has to move the id towards the top of the heap, which is
+    /// cheaper than [`update`]. It is a logic error for `priority` to be
+    /// smaller than the priority it replaces.
+    ///
+    /// [`update`]: IndexedDaryHeap::update
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` is not in the heap.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::indexed::IndexedDaryHeap;
+    /// let mut heap = IndexedDaryHeap::<_, 4>::new();
+    /// heap.push(0, 1);
+    /// heap.push(1, 3);
+    ///
+    /// heap.increase_key(0, 4);
+    /// assert_eq!(heap.peek(), Some((0, &4)));
+    /// ```
+    pub fn increase_key(&mut self, id: usize, priority: P) -> P {
+        let pos = self.expect_position(id);
+        debug_assert!(priority >= self.data[pos].priority);
+        let old = core::mem::replace(&mut self.data[pos].priority, priority);
+        self.sift_up(pos);
+        old
+    }
+
+    /// Replaces the priority of `id` by a smaller or equal `priority` and
+    /// returns the old priority.
+    ///
+    /// This only has to move the id towards the bottom of the heap, which is
+    /// cheaper than [`update`]. It is a logic error for `priority` to be
+    /// greater than the priority it replaces.
+    ///
+    /// [`update`]: IndexedDaryHeap::update
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` is not in the heap.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::indexed::IndexedDaryHeap;
+    /// let mut heap = IndexedDaryHeap::<_, 4>::new();
+    /// heap.push(0, 5);
+    /// heap.push(1, 3);
+    ///
+    /// heap.decrease_key(0, 2);
+    /// assert_eq!(heap.peek(), Some((1, &3)));
+    /// ```
+    pub fn decrease_key(&mut self, id: usize, priority: P) -> P {
+        let pos = self.expect_position(id);
+        debug_assert!(priority <= self.data[pos].priority);
+        let old = core::mem::replace(&mut self.data[pos].priority, priority);
+        self.sift_down(pos);
+        old
+    }
+
+    /// Pushes an id that is not in the heap yet.
+    fn push_new(&mut self, id: usize, priority: P) {
+        assert_ne!(id, ABSENT, "id should be smaller than usize::MAX");
+        if id >= self.positions.len() {
+            self.positions.resize(id + 1, ABSENT);
+        }
+        let pos = self.data.len();
+        self.positions[id] = pos;
+        self.data.push(Entry { id, priority });
+        self.sift_up(pos);
+    }
+
+    /// Removes the entry at position `pos`, which must be in bounds.
+    fn remove_pos(&mut self, pos: usize) -> Entry<P> {
+        let entry = self.data.swap_remove(pos);
+        self.positions[entry.id] = ABSENT;
+        if pos < self.data.len() {
+            self.positions[self.data[pos].id] = pos;
+            let pos = self.sift_up(pos);
+            self.sift_down(pos);
+        }
+        entry
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        self.data.swap(a, b);
+        self.positions[self.data[a].id] = a;
+        self.positions[self.data[b].id] = b;
+    }
+
+    /// Moves the entry at `pos` up while its parent is smaller, and returns
+    /// its new position.
+    fn sift_up(&mut self, mut pos: usize) -> usize {
+        assert_ne!(D, 0, "Arity should be greater than zero");
+        while pos > 0 {
+            let parent = (pos - 1) / D;
+            if self.data[pos].priority <= self.data[parent].priority {
+                break;
+            }
+            self.swap(pos, parent);
+            pos = parent;
+        }
+        pos
+    }
+
+    /// Moves the entry at `pos` down while one of its children is larger.
+    fn sift_down(&mut self, mut pos: usize) {
+        assert_ne!(D, 0, "Arity should be greater than zero");
+        let len = self.data.len();
+        loop {
+            let first_child = D * pos + 1;
+            if first_child >= len {
+                return;
+            }
+            let mut child = first_child;
+            for other in first_child + 1..len.min(first_child + D) {
+                if self.data[child].priority <= self.data[other].priority {
+                    child = other;
+                }
+            }
+            if self.data[pos].priority >= self.data[child].priority {
+                return;
+            }
+            self.swap(pos, child);
+            pos = child;
+        }
+    }
+}
+
+impl<P, const D: usize> IndexedDaryHeap<P, D> {
+    fn position(&self, id: usize) -> Option<usize> {
+        match self.positions.get(id) {
+            Some(&pos) if pos != ABSENT => Some(pos),
+            _ => None,
+        }
+    }
+
+    fn expect_position(&self, id: usize) -> usize {
+        self.position(id).expect("id is not in the heap")
+    }
+
+    /// Returns the priority of `id`, or `None` if it is not in the heap.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::indexed::IndexedDaryHeap;
+    /// let mut heap = IndexedDaryHeap::<_, 2>::new();
+    /// heap.push(7, 1);
+    ///
+    /// assert_eq!(heap.get(7), Some(&1));
+    /// heap.pop();
+    /// assert_eq!(heap.get(7), None);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Cost is *O*(1) in the worst case.
+    #[must_use]
+    pub fn get(&self, id: usize) -> Option<&P> {
+        self.position(id).map(|pos| &self.data[pos].priority)
+    }
+
+    /// Returns `true` if `id` is in the *d*-ary heap.
+    ///
+    /// # Time complexity
+    ///
+    /// Cost is *O*(1) in the worst case.
+    #[must_use]
+    pub fn contains(&self, id: usize) -> bool {
+        self.position(id).is_some()
+    }
+
+    /// Returns the id with the greatest priority in the *d*-ary heap together
+    /// with its priority, or `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::indexed::IndexedDaryHeap;
+    /// let mut heap = IndexedDaryHeap::<_, 2>::new();
+    /// assert_eq!(heap.peek(), None);
+    ///
+    /// heap.push(0, 1);
+    /// heap.push(1, 5);
+    /// heap.push(2, 2);
+    /// assert_eq!(heap.peek(), Some((1, &5)));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Cost is *O*(1) in the worst case.
+    #[must_use]
+    pub fn peek(&self) -> Option<(usize, &P)> {
+        self.data.first().map(|entry| (entry.id, &entry.priority))
+    }
+
+    /// Returns an iterator visiting all ids and priorities in arbitrary
+    /// order.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::indexed::IndexedDaryHeap;
+    /// let mut heap = IndexedDaryHeap::<_, 3>::new();
+    /// heap.push(0, 1);
+    /// heap.push(4, 2);
+    ///
+    /// assert!(heap.iter().any(|(id, &priority)| id == 4 && priority == 2));
+    /// ```
+    pub fn iter(&self) -> Iter<'_, P> {
+        Iter {
+            iter: self.data.iter(),
+        }
+    }
+
+    /// Returns the number of elements the *d*-ary heap can hold without reallocating.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.data.capacity()
+    }
+
+    /// Returns the length of the *d*-ary heap.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Checks if the *d*-ary heap is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drops all ids and priorities from the *d*-ary heap. The memory of the
+    /// position map is kept.
+    pub fn clear(&mut self) {
+        for entry in self.data.drain(..) {
+            self.positions[entry.id] = ABSENT;
+        }
+    }
+}
+
+/// An iterator over the ids and priorities of an `IndexedDaryHeap`.
+///
+/// This `struct` is created by [`IndexedDaryHeap::iter()`]. See its
+/// documentation for more.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Iter<'a, P: 'a> {
+    iter: slice::Iter<'a, Entry<P>>,
+}
+
+impl<P> Clone for Iter<'_, P> {
+    fn clone(&self) -> Self {
+        Iter {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<P: fmt::Debug> fmt::Debug for Iter<'_, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Iter").field(&self.iter.as_slice()).finish()
+    }
+}
+
+impl<'a, P> Iterator for Iter<'a, P> {
+    type Item = (usize, &'a P);
+
+    #[inline]
+    fn next(&mut self) -> Option<(usize, &'a P)> {
+        self.iter.next().map(|entry| (entry.id, &entry.priority))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<P> ExactSizeIterator for Iter<'_, P> {}
+
+impl<P> FusedIterator for Iter<'_, P> {}
+
+impl<'a, P, const D: usize> IntoIterator for &'a IndexedDaryHeap<P, D> {
+    type Item = (usize, &'a P);
+    type IntoIter = Iter<'a, P>;
+
+    fn into_iter(self) -> Iter<'a, P> {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{thread_rng, Rng};
+
+    fn check<const D: usize>() {
+        let mut rng = thread_rng();
+        let mut heap = IndexedDaryHeap::<u32, D>::new();
+        let mut reference: Vec<Option<u32>> = alloc::vec![None; 100];
+        for _ in 0..2000 {
+            let id = rng.gen_range(0..reference.len());
+            let x = rng.gen_range(0..1000);
+            match rng.gen_range(0..5) {
+                0 => assert_eq!(heap.push(id, x), reference[id].replace(x)),
+                1 => {
+                    let changed = reference[id].map_or(true, |old| x > old);
+                    assert_eq!(heap.push_increase(id, x), changed);
+                    if changed {
+                        reference[id] = Some(x);
+                    }
+                }
+                2 if reference[id].is_some() => {
+                    assert_eq!(heap.update(id, x), reference[id].replace(x).unwrap());
+                }
+                3 => assert_eq!(heap.remove(id), reference[id].take()),
+                _ => {
+                    let max = reference.iter().flatten().max().copied();
+                    match heap.pop() {
+                        Some((id, x)) => {
+                            assert_eq!(Some(x), max);
+                            assert_eq!(reference[id].take(), Some(x));
+                        }
+                        None => assert_eq!(max, None),
+                    }
+                }
+            }
+            assert_eq!(heap.len(), reference.iter().flatten().count());
+            for (id, x) in reference.iter().enumerate() {
+                assert_eq!(heap.get(id), x.as_ref());
+                assert_eq!(heap.contains(id), x.is_some());
+            }
+        }
+        heap.clear();
+        assert!(heap.is_empty());
+        assert!((0..reference.len()).all(|id| !heap.contains(id)));
+    }
+
+    #[test]
+    fn random_operations() {
+        check::<1>();
+        check::<2>();
+        check::<3>();
+        check::<4>();
+        check::<7>();
+    }
+}
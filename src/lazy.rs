@@ -0,0 +1,477 @@
+//! A *d*-ary heap with lazy removal of items.
+//!
+//! The [`LazyDeleteHeap`] type in this module removes arbitrary items by
+//! recording them as stale instead of searching for them. Stale items are
+//! skipped when they reach the top of the heap, and all of them are dropped at
+//! once when there are too many. This packages the common pattern of pushing
+//! updated duplicates in Dijkstra's algorithm, without letting the heap grow
+//! without bound.
+
+use core::fmt;
+use core::mem;
+
+use alloc::vec::Vec;
+
+use crate::DaryHeap;
+
+/// Default value of [`LazyDeleteHeap::max_stale_ratio`].
+const DEFAULT_MAX_STALE_RATIO: f64 = 1.0;
+
+/// A priority queue implemented with a *d*-ary heap, which removes items
+/// lazily.
+///
+/// This will be a max-heap. Removed items are kept in a second heap of stale
+/// items, ordered the same way, until the equal item in the heap reaches the
+/// top. When the number of stale items exceeds [`max_stale_ratio`] times the
+/// number of live items, the heap is compacted by dropping all stale items.
+///
+/// It is a logic error to remove an item that is not in the heap, as a later
+/// pushed equal item would be removed instead. Equal items cannot be told
+/// apart, so it is unspecified which of them is removed.
+///
+/// [`max_stale_ratio`]: LazyDeleteHeap::max_stale_ratio
+///
+/// # Examples
+///
+/// ```
+/// use dary_heap::lazy::LazyDeleteHeap;
+///
+/// let mut heap = LazyDeleteHeap::<_, 4>::new();
+/// heap.push((1, 'a'));
+/// heap.push((5, 'b'));
+/// heap.push((3, 'c'));
+///
+/// heap.remove((5, 'b'));
+/// heap.update((1, 'a'), (4, 'a'));
+///
+/// assert_eq!(heap.len(), 2);
+/// assert_eq!(heap.pop(), Some((4, 'a')));
+/// assert_eq!(heap.pop(), Some((3, 'c')));
+/// assert_eq!(heap.pop(), None);
+/// ```
+pub struct LazyDeleteHeap<T, const D: usize> {
+    heap: DaryHeap<T, D>,
+    stale: DaryHeap<T, D>,
+    max_stale_ratio: f64,
+}
+
+impl<T: Clone, const D: usize> Clone for LazyDeleteHeap<T, D> {
+    fn clone(&self) -> Self {
+        LazyDeleteHeap {
+            heap: self.heap.clone(),
+            stale: self.stale.clone(),
+            max_stale_ratio: self.max_stale_ratio,
+        }
+    }
+}
+
+impl<T: Ord, const D: usize> Default for LazyDeleteHeap<T, D> {
+    /// Creates an empty `LazyDeleteHeap<T, D>`.
+    #[inline]
+    fn default() -> LazyDeleteHeap<T, D> {
+        LazyDeleteHeap::new()
+    }
+}
+
+impl<T: fmt::Debug, const D: usize> fmt::Debug for LazyDeleteHeap<T, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LazyDeleteHeap")
+            .field("heap", &self.heap)
+            .field("stale", &self.stale)
+            .finish()
+    }
+}
+
+impl<T: Ord, const D: usize> LazyDeleteHeap<T, D> {
+    /// Creates an empty `LazyDeleteHeap` as a max-heap.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::lazy::LazyDeleteHeap;
+    /// let mut heap = LazyDeleteHeap::<_, 3>::new();
+    /// heap.push(4);
+    /// ```
+    #[must_use]
+    pub fn new() -> LazyDeleteHeap<T, D> {
+        LazyDeleteHeap::with_capacity(0)
+    }
+
+    /// Creates an empty `LazyDeleteHeap` with at least the specified capacity
+    /// for live items.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::lazy::LazyDeleteHeap;
+    /// let mut heap = LazyDeleteHeap::<_, 3>::with_capacity(10);
+    /// heap.push(4);
+    /// ```
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> LazyDeleteHeap<T, D> {
+        LazyDeleteHeap {
+            heap: DaryHeap::with_capacity(capacity),
+            stale: DaryHeap::new(),
+            max_stale_ratio: DEFAULT_MAX_STALE_RATIO,
+        }
+    }
+
+    /// Pushes an item onto the *d*-ary heap.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::lazy::LazyDeleteHeap;
+    /// let mut heap = LazyDeleteHeap::<_, 2>::new();
+    /// heap.push(3);
+    /// heap.push(5);
+    ///
+    /// assert_eq!(heap.len(), 2);
+    /// assert_eq!(heap.peek(), Some(&5));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The expected cost of `push` is *O*(1), and the worst case cost is
+    /// *O*(log(*n*)), like for [`DaryHeap::push`].
+    pub fn push(&mut self, item: T) {
+        self.heap.push(item);
+    }
+
+    /// Removes the greatest live item from the *d*-ary heap and returns it, or
+    /// `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::lazy::LazyDeleteHeap;
+    /// let mut heap = LazyDeleteHeap::<_, 2>::new();
+    /// heap.push(1);
+    /// heap.push(3);
+    /// heap.remove(3);
+    ///
+    /// assert_eq!(heap.pop(), Some(1));
+    /// assert_eq!(heap.pop(), None);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The amortized cost of `pop` on a heap containing *n* items, live or
+    /// stale, is *O*(log(*n*)).
+    pub fn pop(&mut self) -> Option<T> {
+        let item = self.heap.pop();
+        self.skip_stale();
+        self.compact_if_needed();
+        item
+    }
+
+    /// Marks an item equal to `item` as removed from the *d*-ary heap.
+    ///
+    /// The item is only dropped once it reaches the top of the heap, or when
+    /// the heap is compacted. It is a logic error if there is no live item
+    /// equal to `item` in the heap.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::lazy::LazyDeleteHeap;
+    /// let mut heap = LazyDeleteHeap::<_, 2>::new();
+    /// heap.push(1);
+    /// heap.push(3);
+    /// heap.push(2);
+    /// heap.remove(2);
+    ///
+    /// assert_eq!(heap.len(), 2);
+    /// assert_eq!(heap.stale_len(), 1);
+    /// assert_eq!(heap.pop(), Some(3));
+    /// assert_eq!(heap.pop(), Some(1));
+    /// assert_eq!(heap.stale_len(), 0);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The amortized cost of `remove` on a heap containing *n* items, live or
+    /// stale, is *O*(log(*n*)).
+    pub fn remove(&mut self, item: T) {
+        debug_assert!(self.heap.len() > self.stale.len());
+        self.stale.push(item);
+        self.skip_stale();
+        self.compact_if_needed();
+    }
+
+    /// Replaces an item equal to `old` by `new`.
+    ///
+    /// This is equivalent to removing `old` and pushing `new`. It is a logic
+    /// error if there is no live item equal to `old` in the heap.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::lazy::LazyDeleteHeap;
+    /// let mut heap = LazyDeleteHeap::<_, 2>::new();
+    /// heap.push(1);
+    /// heap.push(3);
+    /// heap.update(1, 5);
+    ///
+    /// assert_eq!(heap.pop(), Some(5));
+    /// assert_eq!(heap.pop(), Some(3));
+    /// assert_eq!(heap.pop(), None);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The amortized cost of `update` on a heap containing *n* items, live or
+    /// stale, is *O*(log(*n*)).
+    pub fn update(&mut self, old: T, new: T) {
+        self.heap.push(new);
+        self.remove(old);
+    }
+
+    /// Drops all stale items from the *d*-ary heap.
+    ///
+    /// This is done automatically when the number of stale items exceeds
+    /// [`max_stale_ratio`] times the number of live items.
+    ///
+    /// [`max_stale_ratio`]: LazyDeleteHeap::max_stale_ratio
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::lazy::LazyDeleteHeap;
+    /// let mut heap = LazyDeleteHeap::<_, 2>::new();
+    /// heap.extend([1, 2, 3, 4]);
+    /// heap.remove(2);
+    ///
+    /// assert_eq!(heap.stale_len(), 1);
+    /// heap.compact();
+    /// assert_eq!(heap.stale_len(), 0);
+    /// assert_eq!(heap.len(), 3);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The cost of `compact` on a heap containing *n* items, live or stale,
+    /// is *O*(*n* log(*n*)).
+    pub fn compact(&mut self) {
+        if self.stale.is_empty() {
+            return;
+        }
+        let mut items = mem::take(&mut self.heap).into_sorted_vec_desc();
+        let mut stale = mem::take(&mut self.stale).into_sorted_vec_desc();
+        // Both vectors are sorted in descending order, so every stale item is
+        // matched with the first equal item while walking them in lockstep.
+        // Stale items without an equal item, which only exist after a logic
+        // error, are dropped.
+        let mut stale_iter = stale.drain(..).peekable();
+        items.retain(|item| {
+            while stale_iter.next_if(|stale| stale > item).is_some() {}
+            stale_iter.next_if(|stale| stale == item).is_none()
+        });
+        drop(stale_iter);
+        self.heap = DaryHeap::from_sorted_desc(items);
+        self.stale = DaryHeap::from(stale);
+    }
+
+    /// Sets the ratio of stale to live items above which the *d*-ary heap is
+    /// compacted.
+    ///
+    /// A greater ratio means fewer compactions, but more memory for stale
+    /// items. The default ratio is 1, i.e. the heap is compacted once it
+    /// contains more stale than live items.
+    ///
+    /// The amortized costs of the other methods assume a ratio greater than
+    /// zero. With a ratio of zero, every removal compacts the heap.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ratio` is negative or NaN.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::lazy::LazyDeleteHeap;
+    /// let mut heap = LazyDeleteHeap::<_, 2>::new();
+    /// heap.set_max_stale_ratio(0.0);
+    /// heap.extend([1, 2, 3]);
+    /// heap.remove(2);
+    ///
+    /// assert_eq!(heap.stale_len(), 0);
+    /// ```
+    pub fn set_max_stale_ratio(&mut self, ratio: f64) {
+        assert!(ratio >= 0.0, "ratio should be non-negative");
+        self.max_stale_ratio = ratio;
+    }
+
+    fn compact_if_needed(&mut self) {
+        if self.stale.len() as f64 > self.max_stale_ratio * self.len() as f64 {
+            self.compact();
+        }
+    }
+
+    /// Pops the top of the heap as long as it is stale.
+    fn skip_stale(&mut self) {
+        while let (Some(top), Some(stale)) = (self.heap.peek(), self.stale.peek()) {
+            if top != stale {
+                break;
+            }
+            self.heap.pop();
+            self.stale.pop();
+        }
+    }
+}
+
+impl<T, const D: usize> LazyDeleteHeap<T, D> {
+    /// Returns the greatest live item in the *d*-ary heap, or `None` if it is
+    /// empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::lazy::LazyDeleteHeap;
+    /// let mut heap = LazyDeleteHeap::<_, 2>::new();
+    /// assert_eq!(heap.peek(), None);
+    ///
+    /// heap.push(1);
+    /// heap.push(5);
+    /// heap.remove(5);
+    /// assert_eq!(heap.peek(), Some(&1));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Cost is *O*(1) in the worst case.
+    #[must_use]
+    pub fn peek(&self) -> Option<&T> {
+        self.heap.peek()
+    }
+
+    /// Returns the ratio of stale to live items above which the *d*-ary heap
+    /// is compacted. See [`set_max_stale_ratio`].
+    ///
+    /// [`set_max_stale_ratio`]: LazyDeleteHeap::set_max_stale_ratio
+    #[must_use]
+    pub fn max_stale_ratio(&self) -> f64 {
+        self.max_stale_ratio
+    }
+
+    /// Returns the number of live items in the *d*-ary heap.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.heap.len().saturating_sub(self.stale.len())
+    }
+
+    /// Checks if the *d*-ary heap contains no live items.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of removed items that are still stored in the
+    /// *d*-ary heap.
+    #[must_use]
+    pub fn stale_len(&self) -> usize {
+        self.stale.len()
+    }
+
+    /// Drops all live and stale items from the *d*-ary heap.
+    pub fn clear(&mut self) {
+        self.heap.clear();
+        self.stale.clear();
+    }
+}
+
+impl<T: Ord, const D: usize> Extend<T> for LazyDeleteHeap<T, D> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.heap.extend(iter);
+    }
+}
+
+impl<T: Ord, const D: usize> From<Vec<T>> for LazyDeleteHeap<T, D> {
+    /// Converts a `Vec<T>` into a `LazyDeleteHeap<T, D>` without stale items.
+    ///
+    /// This conversion happens in-place, and has *O*(*n*) time complexity.
+    fn from(vec: Vec<T>) -> LazyDeleteHeap<T, D> {
+        LazyDeleteHeap {
+            heap: DaryHeap::from(vec),
+            stale: DaryHeap::new(),
+            max_stale_ratio: DEFAULT_MAX_STALE_RATIO,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{thread_rng, Rng};
+
+    fn check<const D: usize>(ratio: f64) {
+        let mut rng = thread_rng();
+        let mut heap = LazyDeleteHeap::<u32, D>::new();
+        heap.set_max_stale_ratio(ratio);
+        let mut reference: Vec<u32> = Vec::new();
+        for _ in 0..2000 {
+            match rng.gen_range(0..5) {
+                0 | 1 => {
+                    let x = rng.gen_range(0..100);
+                    heap.push(x);
+                    reference.push(x);
+                }
+                2 if !reference.is_empty() => {
+                    let x = reference.swap_remove(rng.gen_range(0..reference.len()));
+                    heap.remove(x);
+                }
+                3 if !reference.is_empty() => {
+                    let i = rng.gen_range(0..reference.len());
+                    let x = rng.gen_range(0..100);
+                    heap.update(reference[i], x);
+                    reference[i] = x;
+                }
+                _ => {
+                    let max = reference.iter().max().copied();
+                    assert_eq!(heap.pop(), max);
+                    if let Some(max) = max {
+                        let i = reference.iter().position(|&x| x == max).unwrap();
+                        reference.swap_remove(i);
+                    }
+                }
+            }
+            assert_eq!(heap.len(), reference.len());
+            assert_eq!(heap.peek(), reference.iter().max());
+            assert!(heap.stale_len() as f64 <= ratio * heap.len() as f64);
+        }
+        heap.compact();
+        assert_eq!(heap.stale_len(), 0);
+        reference.sort_unstable();
+        assert_eq!(heap.heap.into_sorted_vec(), reference);
+    }
+
+    #[test]
+    fn random_operations() {
+        for &ratio in &[0.0, 0.5, 1.0, 4.0] {
+            check::<1>(ratio);
+            check::<2>(ratio);
+            check::<3>(ratio);
+            check::<4>(ratio);
+            check::<7>(ratio);
+        }
+    }
+}
@@ -46,15 +46,23 @@
 //! automatically when using [`DaryHeap`] directly. It is therefore more
 //! ergonomic to  use one of the type aliases to select the desired arity:
 //!
-//! | Name               | Arity   |
-//! |--------------------|---------|
-//! | [`BinaryHeap`]     | *d* = 2 |
-//! | [`TernaryHeap`]    | *d* = 3 |
-//! | [`QuaternaryHeap`] | *d* = 4 |
-//! | [`QuinaryHeap`]    | *d* = 5 |
-//! | [`SenaryHeap`]     | *d* = 6 |
-//! | [`SeptenaryHeap`]  | *d* = 7 |
-//! | [`OctonaryHeap`]   | *d* = 8 |
+//! | Name                   | Arity    |
+//! |------------------------|----------|
+//! | [`BinaryHeap`]         | *d* = 2  |
+//! | [`TernaryHeap`]        | *d* = 3  |
+//! | [`QuaternaryHeap`]     | *d* = 4  |
+//! | [`QuinaryHeap`]        | *d* = 5  |
+//! | [`SenaryHeap`]         | *d* = 6  |
+//! | [`SeptenaryHeap`]      | *d* = 7  |
+//! | [`OctonaryHeap`]       | *d* = 8  |
+//! | [`NovenaryHeap`]       | *d* = 9  |
+//! | [`DenaryHeap`]         | *d* = 10 |
+//! | [`UndenaryHeap`]       | *d* = 11 |
+//! | [`DuodenaryHeap`]      | *d* = 12 |
+//! | [`TredenaryHeap`]      | *d* = 13 |
+//! | [`QuattuordenaryHeap`] | *d* = 14 |
+//! | [`QuindenaryHeap`]     | *d* = 15 |
+//! | [`SedenaryHeap`]       | *d* = 16 |
 //!
 //! The difference in ergonomics illustrated in the following:
 //!
@@ -91,6 +99,14 @@
 //! [`SenaryHeap`]: type.SenaryHeap.html
 //! [`SeptenaryHeap`]: type.SeptenaryHeap.html
 //! [`OctonaryHeap`]: type.OctonaryHeap.html
+//! [`NovenaryHeap`]: type.NovenaryHeap.html
+//! [`DenaryHeap`]: type.DenaryHeap.html
+//! [`UndenaryHeap`]: type.UndenaryHeap.html
+//! [`DuodenaryHeap`]: type.DuodenaryHeap.html
+//! [`TredenaryHeap`]: type.TredenaryHeap.html
+//! [`QuattuordenaryHeap`]: type.QuattuordenaryHeap.html
+//! [`QuindenaryHeap`]: type.QuindenaryHeap.html
+//! [`SedenaryHeap`]: type.SedenaryHeap.html
 //!
 //! # Examples
 //!
@@ -229,14 +245,11 @@
 #![no_std]
 #![cfg_attr(
     feature = "unstable_nightly",
-    feature(
-        exact_size_is_empty,
-        extend_one,
-        inplace_iteration,
-        min_specialization,
-        trusted_fused,
-        trusted_len
-    )
+    feature(exact_size_is_empty, min_specialization, portable_simd)
+)]
+#![cfg_attr(
+    all(feature = "unstable_nightly", feature = "alloc"),
+    feature(extend_one, inplace_iteration, trusted_fused, trusted_len)
 )]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![allow(
@@ -246,39 +259,195 @@
     clippy::needless_doctest_main
 )]
 
+#[cfg(any(feature = "alloc", test))]
 extern crate alloc;
-
-use core::iter::{FromIterator, FusedIterator};
-use core::mem::{size_of, swap, ManuallyDrop};
+#[cfg(feature = "std")]
+extern crate std;
+
+mod sift;
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod addressable;
+pub mod array;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod blocked;
+pub mod compare;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod counted;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod delay;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod dynamic;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod indexed;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod lazy;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod median;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod merge;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod min;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod minmax;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod observer;
+pub mod slice;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod topk;
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub mod view;
+
+#[cfg(feature = "allocator-api2")]
+#[cfg_attr(docsrs, doc(cfg(feature = "allocator-api2")))]
+pub mod allocator;
+
+#[cfg(feature = "heapless")]
+#[cfg_attr(docsrs, doc(cfg(feature = "heapless")))]
+pub mod heapless;
+
+#[cfg(all(feature = "alloc", feature = "proptest"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "alloc", feature = "proptest"))))]
+pub mod proptest;
+
+#[cfg(feature = "async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+pub mod async_heap;
+
+#[cfg(all(feature = "alloc", feature = "rayon"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "alloc", feature = "rayon"))))]
+pub mod rayon;
+
+#[cfg(all(feature = "alloc", feature = "rkyv"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "alloc", feature = "rkyv"))))]
+pub mod archived;
+
+#[cfg(all(feature = "alloc", feature = "serde"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "alloc", feature = "serde"))))]
+pub mod serde_helpers;
+
+#[cfg(all(feature = "alloc", feature = "stats"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "alloc", feature = "stats"))))]
+pub mod stats;
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub mod sync;
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub mod tune;
+
+use core::fmt;
+#[cfg(feature = "alloc")]
+use core::iter::FromIterator;
+use core::iter::FusedIterator;
+#[cfg(feature = "alloc")]
+use core::mem::swap;
+#[cfg(feature = "alloc")]
 use core::num::NonZeroUsize;
-use core::ops::{Deref, DerefMut};
-use core::{fmt, ptr, slice};
+#[cfg(feature = "alloc")]
+use core::ops::{BitOr, BitOrAssign, Deref, DerefMut};
 
-#[cfg(feature = "extra")]
+#[cfg(all(feature = "alloc", feature = "extra"))]
 use alloc::collections::TryReserveError;
+#[cfg(feature = "alloc")]
 use alloc::{vec, vec::Vec};
 
+#[cfg(feature = "alloc")]
+use compare::Natural;
+#[cfg(all(feature = "alloc", feature = "stats"))]
+use stats::HeapStats;
+
 /// A binary heap (*d* = 2).
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 pub type BinaryHeap<T> = DaryHeap<T, 2>;
 
 /// A ternary heap (*d* = 3).
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 pub type TernaryHeap<T> = DaryHeap<T, 3>;
 
 /// A quaternary heap (*d* = 4).
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 pub type QuaternaryHeap<T> = DaryHeap<T, 4>;
 
 /// A quinary heap (*d* = 5).
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 pub type QuinaryHeap<T> = DaryHeap<T, 5>;
 
 /// A senary heap (*d* = 6).
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 pub type SenaryHeap<T> = DaryHeap<T, 6>;
 
 /// A septenary heap (*d* = 7).
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 pub type SeptenaryHeap<T> = DaryHeap<T, 7>;
 
 /// An octonary heap (*d* = 8).
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 pub type OctonaryHeap<T> = DaryHeap<T, 8>;
 
+/// A novenary heap (*d* = 9).
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub type NovenaryHeap<T> = DaryHeap<T, 9>;
+
+/// A denary heap (*d* = 10).
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub type DenaryHeap<T> = DaryHeap<T, 10>;
+
+/// An undenary heap (*d* = 11).
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub type UndenaryHeap<T> = DaryHeap<T, 11>;
+
+/// A duodenary heap (*d* = 12).
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub type DuodenaryHeap<T> = DaryHeap<T, 12>;
+
+/// A tredenary heap (*d* = 13).
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub type TredenaryHeap<T> = DaryHeap<T, 13>;
+
+/// A quattuordenary heap (*d* = 14).
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub type QuattuordenaryHeap<T> = DaryHeap<T, 14>;
+
+/// A quindenary heap (*d* = 15).
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub type QuindenaryHeap<T> = DaryHeap<T, 15>;
+
+/// A sedenary heap (*d* = 16).
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub type SedenaryHeap<T> = DaryHeap<T, 16>;
+
 /// A priority queue implemented with a *d*-ary heap.
 ///
 /// This will be a max-heap.
@@ -300,6 +469,11 @@ pub type OctonaryHeap<T> = DaryHeap<T, 8>;
 /// even in the presence of closures getting unwinded out of, iterators getting
 /// leaked, and similar foolishness.
 ///
+/// A panic in the [`Ord`] implementation of the items is different: no items
+/// are lost or duplicated, but the heap property may no longer hold. After
+/// recovering from such a panic, [`validate`] can be used to check the heap,
+/// and [`as_vec_mut`] to rebuild it.
+///
 ///
 /// # Usage
 ///
@@ -406,15 +580,52 @@ pub type OctonaryHeap<T> = DaryHeap<T, 8>;
 /// [`core::cmp::Reverse`]: core::cmp::Reverse
 /// [`Cell`]: core::cell::Cell
 /// [`RefCell`]: core::cell::RefCell
+/// [`validate`]: DaryHeap::validate
+/// [`as_vec_mut`]: DaryHeap::as_vec_mut
 /// [push]: DaryHeap::push
 /// [pop]: DaryHeap::pop
 /// [peek]: DaryHeap::peek
 /// [peek\_mut]: DaryHeap::peek_mut
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 pub struct DaryHeap<T, const D: usize> {
     data: Vec<T>,
+    #[cfg(feature = "stats")]
+    stats: HeapStats,
+}
+
+#[cfg(all(feature = "alloc", feature = "bincode"))]
+mod bincode_impl {
+    use super::{DaryHeap, Vec};
+    use bincode::de::{BorrowDecode, BorrowDecoder, Decode, Decoder};
+    use bincode::enc::{Encode, Encoder};
+    use bincode::error::{DecodeError, EncodeError};
+
+    impl<T: Encode, const D: usize> Encode for DaryHeap<T, D> {
+        fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+            self.data.encode(encoder)
+        }
+    }
+
+    impl<Context, T: Ord + Decode<Context>, const D: usize> Decode<Context> for DaryHeap<T, D> {
+        fn decode<De: Decoder<Context = Context>>(decoder: &mut De) -> Result<Self, DecodeError> {
+            Vec::decode(decoder).map(Into::into)
+        }
+    }
+
+    impl<'de, Context, T, const D: usize> BorrowDecode<'de, Context> for DaryHeap<T, D>
+    where
+        T: Ord + BorrowDecode<'de, Context>,
+    {
+        fn borrow_decode<De: BorrowDecoder<'de, Context = Context>>(
+            decoder: &mut De,
+        ) -> Result<Self, DecodeError> {
+            Vec::borrow_decode(decoder).map(Into::into)
+        }
+    }
 }
 
-#[cfg(feature = "serde")]
+#[cfg(all(feature = "alloc", feature = "serde"))]
 mod serde_impl {
     use super::{DaryHeap, Vec};
     use serde::{Deserialize, Deserializer, Serialize, Serializer};
@@ -455,6 +666,8 @@ mod serde_impl {
 /// its documentation for more.
 ///
 /// [`peek_mut`]: DaryHeap::peek_mut
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 pub struct PeekMut<'a, T: 'a + Ord, const D: usize> {
     heap: &'a mut DaryHeap<T, D>,
     // If a set_len + sift_down are required, this is Some. If a &mut T has not
@@ -462,12 +675,14 @@ pub struct PeekMut<'a, T: 'a + Ord, const D: usize> {
     original_len: Option<NonZeroUsize>,
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Ord + fmt::Debug, const D: usize> fmt::Debug for PeekMut<'_, T, D> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_tuple("PeekMut").field(&self.heap.data[0]).finish()
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Ord, const D: usize> Drop for PeekMut<'_, T, D> {
     fn drop(&mut self) {
         if let Some(original_len) = self.original_len {
@@ -480,10 +695,12 @@ impl<T: Ord, const D: usize> Drop for PeekMut<'_, T, D> {
 
             // SAFETY: PeekMut is only instantiated for non-empty heaps.
             unsafe { self.heap.sift_down(0) };
+            self.heap.paranoid_check();
         }
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Ord, const D: usize> Deref for PeekMut<'_, T, D> {
     type Target = T;
     fn deref(&self) -> &T {
@@ -493,6 +710,7 @@ impl<T: Ord, const D: usize> Deref for PeekMut<'_, T, D> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Ord, const D: usize> DerefMut for PeekMut<'_, T, D> {
     fn deref_mut(&mut self) -> &mut T {
         debug_assert!(!self.heap.is_empty());
@@ -521,6 +739,7 @@ impl<T: Ord, const D: usize> DerefMut for PeekMut<'_, T, D> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'a, T: Ord, const D: usize> PeekMut<'a, T, D> {
     /// Removes the peeked value from the heap and returns it.
     pub fn pop(mut this: PeekMut<'a, T, D>) -> T {
@@ -535,12 +754,165 @@ impl<'a, T: Ord, const D: usize> PeekMut<'a, T, D> {
         }
         this.heap.pop().unwrap()
     }
+
+    /// Removes the peeked value from the heap and returns it if `f` returns
+    /// `true`, or keeps it in the heap and returns `None` otherwise.
+    ///
+    /// `f` may change the peeked value, which is then moved to its new place
+    /// in the heap if it is kept.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::{BinaryHeap, PeekMut};
+    /// let mut heap = BinaryHeap::from([(5, 2), (3, 1)]);
+    ///
+    /// // Pop the task with the greatest priority once it has no work left,
+    /// // and lower its priority after doing some work otherwise.
+    /// let mut work = |task: &mut (u32, u32)| {
+    ///     task.1 -= 1;
+    ///     task.0 -= 3;
+    ///     task.1 == 0
+    /// };
+    /// let top = heap.peek_mut().unwrap();
+    /// assert_eq!(PeekMut::pop_if(top, &mut work), None);
+    /// assert_eq!(heap.peek(), Some(&(3, 1)));
+    /// let top = heap.peek_mut().unwrap();
+    /// assert_eq!(PeekMut::pop_if(top, &mut work), Some((0, 0)));
+    /// assert_eq!(heap.into_vec(), [(2, 1)]);
+    /// ```
+    pub fn pop_if<F>(mut this: PeekMut<'a, T, D>, f: F) -> Option<T>
+    where
+        F: FnOnce(&mut T) -> bool,
+    {
+        if f(&mut this) {
+            Some(PeekMut::pop(this))
+        } else {
+            None
+        }
+    }
+
+    /// Moves the peeked value to its place in the heap, which may have
+    /// changed.
+    ///
+    /// This is what happens when the `PeekMut` is dropped, but calling it
+    /// makes the point where the heap is restored explicit.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::{PeekMut, TernaryHeap};
+    /// let mut heap = TernaryHeap::from([1, 5, 3]);
+    ///
+    /// let mut top = heap.peek_mut().unwrap();
+    /// *top = 2;
+    /// PeekMut::commit(top);
+    /// assert_eq!(heap.peek(), Some(&3));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// If the peeked value was changed, the worst case cost of `commit` is
+    /// *O*(log(*n*)) for a heap containing *n* elements, and it is *O*(1)
+    /// otherwise.
+    pub fn commit(this: PeekMut<'a, T, D>) {
+        drop(this);
+    }
+
+    /// Releases the peeked value without moving it, for when it was not
+    /// changed in a way that affects its order.
+    ///
+    /// This skips the comparisons that [`commit`] or dropping the `PeekMut`
+    /// would make to find the place of the peeked value. It is a logic error
+    /// if the peeked value is now smaller than one of its children. The
+    /// behavior resulting from such a logic error is the same as for
+    /// changing the order of an item while it is in the heap.
+    ///
+    /// [`commit`]: PeekMut::commit
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::{PeekMut, QuaternaryHeap};
+    /// use std::cmp::Reverse;
+    /// let mut heap = QuaternaryHeap::from([(Reverse(1), "a"), (Reverse(2), "b")]);
+    ///
+    /// // Changing the payload does not affect the order.
+    /// let mut top = heap.peek_mut().unwrap();
+    /// top.1 = "c";
+    /// PeekMut::cancel(top);
+    /// assert_eq!(heap.peek(), Some(&(Reverse(1), "c")));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Cost is *O*(1) in the worst case.
+    pub fn cancel(mut this: PeekMut<'a, T, D>) {
+        if let Some(original_len) = this.original_len.take() {
+            // SAFETY: This is how many elements were in the Vec at the time of
+            // the BinaryHeap::peek_mut call.
+            unsafe { this.heap.data.set_len(original_len.get()) };
+            this.heap.paranoid_check();
+        }
+    }
+}
+
+/// Structure wrapping a mutable reference to the underlying vector of a
+/// `DaryHeap`, which rebuilds the heap when dropped.
+///
+/// This `struct` is created by the [`as_vec_mut`] method on [`DaryHeap`]. See
+/// its documentation for more.
+///
+/// [`as_vec_mut`]: DaryHeap::as_vec_mut
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub struct HeapGuard<'a, T: 'a + Ord, const D: usize> {
+    heap: &'a mut DaryHeap<T, D>,
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord + fmt::Debug, const D: usize> fmt::Debug for HeapGuard<'_, T, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("HeapGuard").field(&self.heap.data).finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord, const D: usize> Drop for HeapGuard<'_, T, D> {
+    fn drop(&mut self) {
+        self.heap.rebuild();
+        self.heap.paranoid_check();
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord, const D: usize> Deref for HeapGuard<'_, T, D> {
+    type Target = Vec<T>;
+    fn deref(&self) -> &Vec<T> {
+        &self.heap.data
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord, const D: usize> DerefMut for HeapGuard<'_, T, D> {
+    fn deref_mut(&mut self) -> &mut Vec<T> {
+        &mut self.heap.data
+    }
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Clone, const D: usize> Clone for DaryHeap<T, D> {
     fn clone(&self) -> Self {
         DaryHeap {
             data: self.data.clone(),
+            #[cfg(feature = "stats")]
+            stats: self.stats,
         }
     }
 
@@ -552,10 +924,55 @@ impl<T: Clone, const D: usize> Clone for DaryHeap<T, D> {
     /// See [`Vec::clone_from()`] for more details.
     fn clone_from(&mut self, source: &Self) {
         self.data.clone_from(&source.data);
+        #[cfg(feature = "stats")]
+        {
+            self.stats = source.stats;
+        }
+    }
+}
+
+#[cfg(all(feature = "alloc", feature = "extra"))]
+impl<T: Clone, const D: usize> DaryHeap<T, D> {
+    /// Tries to clone the *d*-ary heap, returning an error instead of aborting
+    /// if the buffer for the clone cannot be allocated.
+    ///
+    /// Only the allocation of the new heap's buffer is fallible; cloning the
+    /// individual elements uses their regular [`Clone`] implementation.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dary_heap::QuaternaryHeap;
+    /// use std::collections::TryReserveError;
+    ///
+    /// fn snapshot(heap: &QuaternaryHeap<u32>) -> Result<Vec<u32>, TryReserveError> {
+    ///     Ok(heap.try_clone()?.into_sorted_vec())
+    /// }
+    ///
+    /// let heap = QuaternaryHeap::from([3, 1, 2]);
+    /// assert_eq!(snapshot(&heap).expect("why is the test harness OOMing?"), [1, 2, 3]);
+    /// assert_eq!(heap.len(), 3);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "extra")))]
+    pub fn try_clone(&self) -> Result<Self, TryReserveError> {
+        let mut data = Vec::new();
+        data.try_reserve_exact(self.len())?;
+        data.extend_from_slice(&self.data);
+        Ok(DaryHeap {
+            data,
+            #[cfg(feature = "stats")]
+            stats: HeapStats::new(),
+        })
     }
 }
 
-impl<T: Ord, const D: usize> Default for DaryHeap<T, D> {
+#[cfg(feature = "alloc")]
+impl<T, const D: usize> Default for DaryHeap<T, D> {
     /// Creates an empty `DaryHeap<T, D>`.
     #[inline]
     fn default() -> DaryHeap<T, D> {
@@ -563,51 +980,106 @@ impl<T: Ord, const D: usize> Default for DaryHeap<T, D> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T: fmt::Debug, const D: usize> fmt::Debug for DaryHeap<T, D> {
+    /// Formats the items of the *d*-ary heap as a list in the order of the
+    /// underlying vector.
+    ///
+    /// The alternate form (`{:#?}`) instead shows the heap as a tree, with
+    /// every item on its own line, indented one level deeper than its parent.
+    ///
+    /// ```
+    /// use dary_heap::TernaryHeap;
+    /// let heap = TernaryHeap::from_sorted_desc(vec![5, 4, 3, 2, 1]);
+    ///
+    /// assert_eq!(format!("{:?}", heap), "[5, 4, 3, 2, 1]");
+    /// assert_eq!(
+    ///     format!("{:#?}", heap),
+    ///     "[\n    5\n        4\n            1\n        3\n        2\n]",
+    /// );
+    /// ```
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_list().entries(self.iter()).finish()
+        if !f.alternate() {
+            return f.debug_list().entries(self.iter()).finish();
+        }
+        f.write_str("[\n")?;
+        // Depth-first traversal, with an explicit stack of (position, level)
+        // pairs because unary heaps can be arbitrarily deep.
+        let mut stack = if self.is_empty() {
+            Vec::new()
+        } else {
+            vec![(0, 1)]
+        };
+        while let Some((pos, level)) = stack.pop() {
+            for _ in 0..level {
+                f.write_str("    ")?;
+            }
+            writeln!(f, "{:?}", self.data[pos])?;
+            let first = D.saturating_mul(pos).saturating_add(1);
+            let last = first.saturating_add(D).min(self.len());
+            stack.extend((first..last).rev().map(|child| (child, level + 1)));
+        }
+        f.write_str("]")
     }
 }
 
+#[cfg(feature = "alloc")]
 struct RebuildOnDrop<'a, T: Ord, const D: usize> {
     heap: &'a mut DaryHeap<T, D>,
     rebuild_from: usize,
 }
 
+#[cfg(feature = "alloc")]
 impl<'a, T: Ord, const D: usize> Drop for RebuildOnDrop<'a, T, D> {
     fn drop(&mut self) {
         self.heap.rebuild_tail(self.rebuild_from);
+        self.heap.paranoid_check();
     }
 }
 
-impl<T: Ord, const D: usize> DaryHeap<T, D> {
-    /// Creates an empty `DaryHeap` as a max-heap.
+/// Moves the item at `pos` to its place when dropped, also if the item was
+/// changed by a closure that panicked.
+#[cfg(feature = "alloc")]
+struct SiftOnDrop<'a, T: Ord, const D: usize> {
+    heap: &'a mut DaryHeap<T, D>,
+    pos: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T: Ord, const D: usize> Drop for SiftOnDrop<'a, T, D> {
+    fn drop(&mut self) {
+        // SAFETY: SiftOnDrop is only instantiated with pos < self.heap.len().
+        unsafe { self.heap.sift_up_or_down(self.pos) };
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, const D: usize> DaryHeap<T, D> {
+    /// The maximum number of items a `DaryHeap<T, D>` can contain.
     ///
-    /// # Notes
+    /// This is `usize::MAX / D`, which guarantees that the position of every
+    /// child, `D * pos + 1` for an item at position `pos`, can be computed
+    /// without overflowing. It can be reached before running out of memory on
+    /// 32-bit targets with a high arity or with zero-sized items. Operations
+    /// that would grow the heap beyond this length panic, see [`try_push`]
+    /// for a fallible alternative.
     ///
-    /// This function is `const` on crate feature `extra` only.
+    /// [`try_push`]: DaryHeap::try_push
     ///
     /// # Examples
     ///
     /// Basic usage:
     ///
     /// ```
-    /// use dary_heap::QuaternaryHeap;
-    /// let mut heap = QuaternaryHeap::new();
-    /// heap.push(4);
+    /// use dary_heap::{BinaryHeap, OctonaryHeap};
+    ///
+    /// assert_eq!(BinaryHeap::<u8>::MAX_LEN, usize::MAX / 2);
+    /// assert_eq!(OctonaryHeap::<u8>::MAX_LEN, usize::MAX / 8);
     /// ```
-    #[must_use]
-    #[cfg(not(feature = "extra"))]
-    pub fn new() -> DaryHeap<T, D> {
-        DaryHeap { data: vec![] }
-    }
+    pub const MAX_LEN: usize = usize::MAX / if D == 0 { 1 } else { D };
 
     /// Creates an empty `DaryHeap` as a max-heap.
     ///
-    /// # Notes
-    ///
-    /// This function is `const` on crate feature `extra` only.
-    ///
     /// # Examples
     ///
     /// Basic usage:
@@ -617,10 +1089,24 @@ impl<T: Ord, const D: usize> DaryHeap<T, D> {
     /// let mut heap = QuaternaryHeap::new();
     /// heap.push(4);
     /// ```
+    ///
+    /// Since this function is `const`, it can be used in constants and
+    /// statics:
+    ///
+    /// ```
+    /// use dary_heap::BinaryHeap;
+    ///
+    /// static EMPTY: BinaryHeap<u32> = BinaryHeap::new();
+    ///
+    /// assert!(EMPTY.is_empty());
+    /// ```
     #[must_use]
-    #[cfg(feature = "extra")]
     pub const fn new() -> DaryHeap<T, D> {
-        DaryHeap { data: vec![] }
+        DaryHeap {
+            data: Vec::new(),
+            #[cfg(feature = "stats")]
+            stats: HeapStats::new(),
+        }
     }
 
     /// Creates an empty `DaryHeap` with at least the specific capacity.
@@ -642,9 +1128,168 @@ impl<T: Ord, const D: usize> DaryHeap<T, D> {
     pub fn with_capacity(capacity: usize) -> DaryHeap<T, D> {
         DaryHeap {
             data: Vec::with_capacity(capacity),
+            #[cfg(feature = "stats")]
+            stats: HeapStats::new(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord, const D: usize> DaryHeap<T, D> {
+    /// Creates a `DaryHeap` from a vector sorted in descending order, without
+    /// rearranging it.
+    ///
+    /// A vector sorted in descending order already is a valid *d*-ary heap, so
+    /// unlike [`from`] this does not need to rebuild the heap. If the vector is
+    /// not sorted in descending order, the behavior of the resulting heap is
+    /// unspecified, but safe; use [`try_from_sorted_desc`] to check the order.
+    ///
+    /// [`from`]: DaryHeap::from
+    /// [`try_from_sorted_desc`]: DaryHeap::try_from_sorted_desc
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::TernaryHeap;
+    /// let mut heap = TernaryHeap::from_sorted_desc(vec![9, 7, 7, 3, 1]);
+    ///
+    /// assert_eq!(heap.pop(), Some(9));
+    /// assert_eq!(heap.pop(), Some(7));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Cost is *O*(1) in the worst case.
+    #[must_use]
+    pub fn from_sorted_desc(vec: Vec<T>) -> DaryHeap<T, D> {
+        debug_assert!(vec.windows(2).all(|w| w[0] >= w[1]));
+        Self::check_len(vec.len());
+        DaryHeap {
+            data: vec,
+            #[cfg(feature = "stats")]
+            stats: HeapStats::new(),
+        }
+    }
+
+    /// Creates a `DaryHeap` from a vector sorted in descending order, or
+    /// returns the vector if it is not sorted in descending order.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::TernaryHeap;
+    /// let heap = TernaryHeap::try_from_sorted_desc(vec![9, 7, 3]).unwrap();
+    /// assert_eq!(heap.peek(), Some(&9));
+    ///
+    /// let vec = TernaryHeap::try_from_sorted_desc(vec![3, 7, 9]).unwrap_err();
+    /// assert_eq!(vec, [3, 7, 9]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The cost of `try_from_sorted_desc` is *O*(*n*), but it only compares
+    /// adjacent items, which is cheaper than rebuilding the heap.
+    pub fn try_from_sorted_desc(vec: Vec<T>) -> Result<DaryHeap<T, D>, Vec<T>> {
+        Self::check_len(vec.len());
+        if vec.windows(2).all(|w| w[0] >= w[1]) {
+            Ok(DaryHeap {
+                data: vec,
+                #[cfg(feature = "stats")]
+                stats: HeapStats::new(),
+            })
+        } else {
+            Err(vec)
+        }
+    }
+
+    /// Creates a `DaryHeap` from a vector that is already arranged as a
+    /// *d*-ary heap, without rearranging it.
+    ///
+    /// This is useful to restore a heap from the result of [`into_vec`] or
+    /// from a trusted snapshot without paying for [`from`].
+    ///
+    /// [`into_vec`]: DaryHeap::into_vec
+    /// [`from`]: DaryHeap::from
+    ///
+    /// # Safety
+    ///
+    /// The caller must guarantee that `vec` is a valid *d*-ary max-heap: for
+    /// every index `i > 0`, `vec[i] <= vec[(i - 1) / D]`. The result of
+    /// [`into_vec`] on a `DaryHeap` with the same arity always satisfies this.
+    ///
+    /// The methods of `DaryHeap` do not cause undefined behavior if this
+    /// invariant is violated, but their results are unspecified. The
+    /// invariant is checked with a debug assertion.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::QuaternaryHeap;
+    /// let vec = QuaternaryHeap::from([3, 1, 4, 1, 5]).into_vec();
+    ///
+    /// // SAFETY: `vec` was produced by `into_vec` of a heap with the same arity.
+    /// let mut heap = unsafe { QuaternaryHeap::from_vec_unchecked(vec) };
+    /// assert_eq!(heap.pop(), Some(5));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Cost is *O*(1) in the worst case (without debug assertions).
+    #[must_use]
+    pub unsafe fn from_vec_unchecked(vec: Vec<T>) -> DaryHeap<T, D> {
+        debug_assert!(slice::is_heap::<T, D>(&vec));
+        Self::check_len(vec.len());
+        DaryHeap {
+            data: vec,
+            #[cfg(feature = "stats")]
+            stats: HeapStats::new(),
         }
     }
 
+    /// Creates a `DaryHeap` directly from the raw components of another
+    /// *d*-ary heap, as returned by [`into_raw_parts`].
+    ///
+    /// [`into_raw_parts`]: DaryHeap::into_raw_parts
+    ///
+    /// # Safety
+    ///
+    /// The caller must uphold the safety requirements of both
+    /// [`Vec::from_raw_parts`] and [`from_vec_unchecked`]. Passing the
+    /// unchanged result of [`into_raw_parts`] on a `DaryHeap` with the same
+    /// arity always satisfies them.
+    ///
+    /// [`from_vec_unchecked`]: DaryHeap::from_vec_unchecked
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::QuaternaryHeap;
+    /// let heap = QuaternaryHeap::from([3, 1, 4, 1, 5]);
+    /// let (ptr, len, capacity) = heap.into_raw_parts();
+    ///
+    /// // SAFETY: The parts were produced by `into_raw_parts` of a heap with
+    /// // the same arity.
+    /// let mut heap = unsafe { QuaternaryHeap::from_raw_parts(ptr, len, capacity) };
+    /// assert_eq!(heap.pop(), Some(5));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Cost is *O*(1) in the worst case (without debug assertions).
+    #[must_use]
+    pub unsafe fn from_raw_parts(ptr: *mut T, length: usize, capacity: usize) -> DaryHeap<T, D> {
+        // SAFETY: The caller upholds the requirements of both functions.
+        Self::from_vec_unchecked(Vec::from_raw_parts(ptr, length, capacity))
+    }
+
     /// Returns a mutable reference to the greatest item in the *d*-ary heap, or
     /// `None` if it is empty.
     ///
@@ -686,6 +1331,42 @@ impl<T: Ord, const D: usize> DaryHeap<T, D> {
         }
     }
 
+    /// Returns a guard giving mutable access to the underlying vector, which
+    /// rebuilds the *d*-ary heap when dropped.
+    ///
+    /// This allows arbitrary batch modifications of the items, such as
+    /// adjusting many priorities at once or removing duplicates, at the cost
+    /// of a single rebuild. The heap is rebuilt even if the guard is dropped
+    /// during unwinding from a panic.
+    ///
+    /// Note: If the `HeapGuard` value is leaked, the heap may be left in an
+    /// invalid state; its behavior is then unspecified, but safe.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::TernaryHeap;
+    /// let mut heap = TernaryHeap::from([1, 5, 2, 5, 3]);
+    /// {
+    ///     let mut vec = heap.as_vec_mut();
+    ///     vec.sort_unstable();
+    ///     vec.dedup();
+    ///     for x in vec.iter_mut() {
+    ///         *x = 10 - *x;
+    ///     }
+    /// }
+    /// assert_eq!(heap.into_sorted_vec(), [5, 7, 8, 9]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Dropping the guard costs *O*(*n*) for rebuilding the heap.
+    pub fn as_vec_mut(&mut self) -> HeapGuard<'_, T, D> {
+        HeapGuard { heap: self }
+    }
+
     /// Removes the greatest item from the *d*-ary heap and returns it, or `None` if it
     /// is empty.
     ///
@@ -706,16 +1387,115 @@ impl<T: Ord, const D: usize> DaryHeap<T, D> {
     ///
     /// The worst case cost of `pop` on a heap containing *n* elements is *O*(log(*n*)).
     pub fn pop(&mut self) -> Option<T> {
-        self.data.pop().map(|mut item| {
-            if !self.is_empty() {
-                swap(&mut item, &mut self.data[0]);
+        self.pop_with_strategy(PopStrategy::ToBottom)
+    }
+
+    /// Removes the greatest item from the *d*-ary heap and returns it, or `None` if it
+    /// is empty, restoring the heap with the given strategy.
+    ///
+    /// [`pop`] uses [`PopStrategy::ToBottom`], which is faster for most
+    /// workloads. [`PopStrategy::SiftDown`] can be faster if the last item
+    /// of the heap is often close to the greatest, e.g. if many items are
+    /// equal.
+    ///
+    /// [`pop`]: DaryHeap::pop
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::{PopStrategy, QuaternaryHeap};
+    /// let mut heap = QuaternaryHeap::from([1, 1, 1, 3]);
+    ///
+    /// assert_eq!(heap.pop_with_strategy(PopStrategy::SiftDown), Some(3));
+    /// assert_eq!(heap.pop_with_strategy(PopStrategy::ToBottom), Some(1));
+    /// assert_eq!(heap.len(), 2);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The worst case cost of `pop_with_strategy` on a heap containing *n*
+    /// elements is *O*(log(*n*)).
+    #[inline]
+    pub fn pop_with_strategy(&mut self, strategy: PopStrategy) -> Option<T> {
+        self.data.pop().map(|mut item| {
+            if !self.is_empty() {
+                swap(&mut item, &mut self.data[0]);
                 // SAFETY: !self.is_empty() means that self.len() > 0
-                unsafe { self.sift_down_to_bottom(0) };
+                match strategy {
+                    PopStrategy::ToBottom => unsafe { self.sift_down_to_bottom(0) },
+                    PopStrategy::SiftDown => unsafe {
+                        self.sift_down(0);
+                    },
+                }
+                self.paranoid_check();
             }
             item
         })
     }
 
+    /// Removes the greatest item from the *d*-ary heap and returns it if the
+    /// predicate returns `true`, or `None` if the predicate returns `false` or
+    /// the heap is empty.
+    ///
+    /// The predicate is called with a reference to the greatest item, and is
+    /// not called at all if the heap is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::BinaryHeap;
+    /// let mut heap = BinaryHeap::from([1, 4, 6]);
+    ///
+    /// assert_eq!(heap.pop_if(|&x| x > 5), Some(6));
+    /// assert_eq!(heap.pop_if(|&x| x > 5), None);
+    /// assert_eq!(heap.peek(), Some(&4));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The worst case cost of `pop_if` on a heap containing *n* elements is
+    /// *O*(log(*n*)). If the predicate returns `false`, the cost is *O*(1).
+    pub fn pop_if(&mut self, predicate: impl FnOnce(&T) -> bool) -> Option<T> {
+        if predicate(self.peek()?) {
+            self.pop()
+        } else {
+            None
+        }
+    }
+
+    /// Removes the greatest item and all items equal to it from the *d*-ary
+    /// heap and returns them, or an empty vector if the heap is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::QuaternaryHeap;
+    /// let mut heap = QuaternaryHeap::from([3, 7, 1, 7, 5, 7]);
+    ///
+    /// assert_eq!(heap.pop_all_max(), [7, 7, 7]);
+    /// assert_eq!(heap.pop_all_max(), [5]);
+    /// assert_eq!(heap.len(), 2);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The worst case cost of `pop_all_max` is *O*(*k* log(*n*)) on a heap
+    /// containing *n* elements, where *k* is the number of removed items.
+    #[must_use = "use `.pop()` or `.retain()` if you don't need the removed items"]
+    pub fn pop_all_max(&mut self) -> Vec<T> {
+        let mut ties: Vec<T> = self.pop().into_iter().collect();
+        while let Some(item) = self.pop_if(|item| Some(item) == ties.first()) {
+            ties.push(item);
+        }
+        ties
+    }
+
     /// Pushes an item onto the *d*-ary heap.
     ///
     /// # Examples
@@ -748,348 +1528,255 @@ impl<T: Ord, const D: usize> DaryHeap<T, D> {
     /// The worst case cost of a *single* call to `push` is *O*(*n*). The worst case
     /// occurs when capacity is exhausted and needs a resize. The resize cost
     /// has been amortized in the previous figures.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new length exceeds [`MAX_LEN`].
+    ///
+    /// [`MAX_LEN`]: DaryHeap::MAX_LEN
     pub fn push(&mut self, item: T) {
         let old_len = self.len();
+        Self::check_len(old_len + 1);
         self.data.push(item);
         // SAFETY: Since we pushed a new item it means that
         //  old_len = self.len() - 1 < self.len()
         unsafe { self.sift_up(0, old_len) };
+        self.paranoid_check();
     }
 
-    /// Consumes the `DaryHeap` and returns a vector in sorted
-    /// (ascending) order.
+    /// Pushes an item onto the *d*-ary heap if it contains fewer than
+    /// [`MAX_LEN`] items, otherwise the item is returned.
+    ///
+    /// [`MAX_LEN`]: DaryHeap::MAX_LEN
     ///
     /// # Examples
     ///
     /// Basic usage:
     ///
     /// ```
-    /// use dary_heap::OctonaryHeap;
-    ///
-    /// let mut heap = OctonaryHeap::from([1, 2, 4, 5, 7]);
-    /// heap.push(6);
-    /// heap.push(3);
+    /// use dary_heap::BinaryHeap;
+    /// let mut heap = BinaryHeap::new();
     ///
-    /// let vec = heap.into_sorted_vec();
-    /// assert_eq!(vec, [1, 2, 3, 4, 5, 6, 7]);
+    /// assert_eq!(heap.try_push(3), Ok(()));
+    /// assert_eq!(heap.peek(), Some(&3));
     /// ```
-    #[must_use = "`self` will be dropped if the result is not used"]
-    pub fn into_sorted_vec(mut self) -> Vec<T> {
-        let mut end = self.len();
-        while end > 1 {
-            end -= 1;
-            // SAFETY: `end` goes from `self.len() - 1` to 1 (both included),
-            //  so it's always a valid index to access.
-            //  It is safe to access index 0 (i.e. `ptr`), because
-            //  1 <= end < self.len(), which means self.len() >= 2.
-            unsafe {
-                let ptr = self.data.as_mut_ptr();
-                ptr::swap(ptr, ptr.add(end));
-            }
-            // SAFETY: `end` goes from `self.len() - 1` to 1 (both included) so:
-            //  0 < 1 <= end <= self.len() - 1 < self.len()
-            //  Which means 0 < end and end < self.len().
-            unsafe { self.sift_down_range(0, end) };
-        }
-        self.into_vec()
-    }
-
-    // The implementations of sift_up and sift_down use unsafe blocks in
-    // order to move an element out of the vector (leaving behind a
-    // hole), shift along the others and move the removed element back into the
-    // vector at the final location of the hole.
-    // The `Hole` type is used to represent this, and make sure
-    // the hole is filled back at the end of its scope, even on panic.
-    // Using a hole reduces the constant factor compared to using swaps,
-    // which involves twice as many moves.
-
-    /// # Safety
     ///
-    /// The caller must guarantee that `pos < self.len()`.
-    unsafe fn sift_up(&mut self, start: usize, pos: usize) -> usize {
-        assert_ne!(D, 0, "Arity should be greater than zero");
-        // Take out the value at `pos` and create a hole.
-        // SAFETY: The caller guarantees that pos < self.len()
-        let mut hole = Hole::new(&mut self.data, pos);
-
-        while hole.pos() > start {
-            let parent = (hole.pos() - 1) / D;
-
-            // SAFETY: hole.pos() > start >= 0, which means hole.pos() > 0
-            //  and so hole.pos() - 1 can't underflow.
-            //  This guarantees that parent < hole.pos() so
-            //  it's a valid index and also != hole.pos().
-            if hole.element() <= hole.get(parent) {
-                break;
-            }
-
-            // SAFETY: Same as above
-            hole.move_to(parent);
-        }
-
-        hole.pos()
-    }
-
-    /// Take an element at `pos` and move it down the heap,
-    /// while its children are larger.
+    /// # Time complexity
     ///
-    /// # Safety
+    /// The cost of `try_push` is the same as that of [`push`].
     ///
-    /// The caller must guarantee that `pos < end <= self.len()`.
-    unsafe fn sift_down_range(&mut self, pos: usize, end: usize) {
-        assert_ne!(D, 0, "Arity should be greater than zero");
-        // SAFETY: The caller guarantees that pos < end <= self.len().
-        let mut hole = Hole::new(&mut self.data, pos);
-        let mut child = D * hole.pos() + 1;
-
-        // Loop invariant: child == d * hole.pos() + 1.
-        while child <= end.saturating_sub(D) {
-            // compare with the greatest of the d children
-            // SAFETY: child < end - d + 1 < self.len() and
-            //  child + d - 1 < end <= self.len(), so they're valid indexes.
-            //  child + i == d * hole.pos() + 1 + i != hole.pos() for i >= 0
-            child = hole.max_sibling::<D>(child);
-
-            // if we are already in order, stop.
-            // SAFETY: child is now either the old child or valid sibling
-            //  We already proven that all are < self.len() and != hole.pos()
-            if hole.element() >= hole.get(child) {
-                return;
-            }
-
-            // SAFETY: same as above.
-            hole.move_to(child);
-            child = D * hole.pos() + 1;
-        }
-
-        child = hole.max_sibling_to::<D>(child, end);
-        // SAFETY: && short circuit, which means that in the
-        //  second condition it's already true that child < end <= self.len().
-        if child < end && hole.element() < hole.get(child) {
-            // SAFETY: child is already proven to be a valid index and
-            //  child == d * hole.pos() + 1 != hole.pos().
-            hole.move_to(child);
+    /// [`push`]: DaryHeap::push
+    pub fn try_push(&mut self, item: T) -> Result<(), T> {
+        if self.len() >= Self::MAX_LEN {
+            return Err(item);
         }
+        self.push(item);
+        Ok(())
     }
 
-    /// # Safety
+    /// Pushes an item onto the *d*-ary heap if there is sufficient spare
+    /// capacity, otherwise the item is returned.
     ///
-    /// The caller must guarantee that `pos < self.len()`.
-    unsafe fn sift_down(&mut self, pos: usize) {
-        let len = self.len();
-        // SAFETY: pos < len is guaranteed by the caller and
-        //  obviously len = self.len() <= self.len().
-        self.sift_down_range(pos, len);
-    }
-
-    /// Take an element at `pos` and move it all the way down the heap,
-    /// then sift it up to its position.
+    /// Unlike [`push`], this method never reallocates. Use [`reserve`] or
+    /// [`with_capacity`] beforehand to ensure there is enough capacity.
     ///
-    /// Note: This is faster when the element is known to be large / should
-    /// be closer to the bottom.
+    /// [`push`]: DaryHeap::push
+    /// [`reserve`]: DaryHeap::reserve
+    /// [`with_capacity`]: DaryHeap::with_capacity
     ///
-    /// # Safety
+    /// # Examples
     ///
-    /// The caller must guarantee that `pos < self.len()`.
-    unsafe fn sift_down_to_bottom(&mut self, mut pos: usize) {
-        assert_ne!(D, 0, "Arity should be greater than zero");
-        let end = self.len();
-        let start = pos;
-
-        // SAFETY: The caller guarantees that pos < self.len().
-        let mut hole = Hole::new(&mut self.data, pos);
-        let mut child = D * hole.pos() + 1;
-
-        // Loop invariant: child == d * hole.pos() + 1.
-        while child <= end.saturating_sub(D) {
-            // SAFETY: child < end - d + 1 < self.len() and
-            //  child + d - 1 < end <= self.len(), so they're valid indexes.
-            //  child + i == d * hole.pos() + 1 + i != hole.pos() for i >= 0
-            child = hole.max_sibling::<D>(child);
-
-            // SAFETY: Same as above
-            hole.move_to(child);
-            child = D * hole.pos() + 1;
-        }
-
-        child = hole.max_sibling_to::<D>(child, end);
-        if child < end {
-            // SAFETY: child < end <= self.len(), so it's a valid index
-            //  and child == d * hole.pos() + i != hole.pos() for i >= 1
-            hole.move_to(child);
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::TernaryHeap;
+    /// let mut heap = TernaryHeap::with_capacity(2);
+    /// let capacity = heap.capacity();
+    ///
+    /// for i in 0..capacity {
+    ///     assert_eq!(heap.push_within_capacity(i), Ok(()));
+    /// }
+    /// assert_eq!(heap.push_within_capacity(capacity), Err(capacity));
+    /// assert_eq!(heap.capacity(), capacity);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The worst case cost of `push_within_capacity` on a heap containing *n*
+    /// elements is *O*(log(*n*)). Unlike [`push`], it never needs to resize.
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "unstable")))]
+    pub fn push_within_capacity(&mut self, item: T) -> Result<(), T> {
+        if self.len() == self.capacity() || self.len() >= Self::MAX_LEN {
+            return Err(item);
         }
-        pos = hole.pos();
-        drop(hole);
-
-        // SAFETY: pos is the position in the hole and was already proven
-        //  to be a valid index.
-        self.sift_up(start, pos);
+        self.push(item);
+        Ok(())
     }
 
-    /// Rebuild assuming data[0..start] is still a proper heap.
-    fn rebuild_tail(&mut self, start: usize) {
-        assert_ne!(D, 0, "Arity should be greater than zero");
-
-        if start == self.len() {
-            return;
-        }
-
-        let tail_len = self.len() - start;
-
-        // The fix for this lint (usize::BITS) requires Rust 1.53.0, but the
-        // MSRV is currently 1.51.0.
-        #[allow(clippy::manual_bits)]
-        #[inline(always)]
-        fn log2_fast(x: usize) -> usize {
-            8 * size_of::<usize>() - (x.leading_zeros() as usize) - 1
-        }
-
-        // `rebuild` takes O(self.len()) operations
-        // and about n * self.len() comparisons in the worst case
-        // with n = d / (d - 1)
-        // while repeating `sift_up` takes O(tail_len * log(start)) operations
-        // and about 1 * tail_len * log(start) comparisons in the worst case,
-        // assuming start >= tail_len. For larger heaps, the crossover point
-        // no longer follows this reasoning and was determined empirically.
-        let better_to_rebuild = if start < tail_len {
-            true
-        } else if self.len() <= 4096 / D {
-            D * self.len() < (D - 1) * tail_len * log2_fast(start)
-        } else {
-            D * self.len() < (D - 1) * tail_len * 13usize.saturating_sub(D)
-        };
-
-        if better_to_rebuild {
-            self.rebuild();
-        } else {
-            for i in start..self.len() {
-                // SAFETY: The index `i` is always less than self.len().
-                unsafe { self.sift_up(0, i) };
+    /// Pushes an item onto the *d*-ary heap, then removes the greatest item and
+    /// returns it.
+    ///
+    /// This is equivalent to calling [`push`] followed by [`pop`], but faster.
+    /// If `item` is at least as great as every item in the heap, it is
+    /// returned immediately without modifying the heap.
+    ///
+    /// [`push`]: DaryHeap::push
+    /// [`pop`]: DaryHeap::pop
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::TernaryHeap;
+    /// let mut heap = TernaryHeap::from([1, 5, 2]);
+    ///
+    /// assert_eq!(heap.push_pop(7), 7);
+    /// assert_eq!(heap.push_pop(3), 5);
+    /// assert_eq!(heap.into_sorted_vec(), [1, 2, 3]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The worst case cost of `push_pop` on a heap containing *n* elements is
+    /// *O*(log(*n*)). If `item` is not less than the greatest item, the cost is
+    /// *O*(1).
+    pub fn push_pop(&mut self, mut item: T) -> T {
+        if let Some(top) = self.data.get_mut(0) {
+            if item < *top {
+                swap(&mut item, top);
+                // SAFETY: The heap is not empty, so 0 < self.len().
+                unsafe { self.sift_down(0) };
+                self.paranoid_check();
             }
         }
+        item
     }
 
-    fn rebuild(&mut self) {
-        assert_ne!(D, 0, "Arity should be greater than zero");
-        if self.len() < 2 {
-            return;
-        }
-        let mut n = (self.len() - 1) / D + 1;
-        while n > 0 {
-            n -= 1;
-            // SAFETY: n starts from (self.len() - 1) / d + 1 and goes down to 0.
-            //  The only case when !(n < self.len()) is if
-            //  self.len() == 0, but it's ruled out by the loop condition.
-            unsafe { self.sift_down(n) };
-        }
-    }
-
-    /// Moves all the elements of `other` into `self`, leaving `other` empty.
+    /// Removes the greatest item from the *d*-ary heap and replaces it with
+    /// `item`, returning the removed item, or `None` if the heap was empty.
+    ///
+    /// This is equivalent to calling [`pop`] followed by [`push`], but faster.
+    /// Unlike [`push_pop`], the returned item is always an item that was in the
+    /// heap before the call, even if `item` is greater.
+    ///
+    /// [`pop`]: DaryHeap::pop
+    /// [`push`]: DaryHeap::push
+    /// [`push_pop`]: DaryHeap::push_pop
     ///
     /// # Examples
     ///
     /// Basic usage:
     ///
     /// ```
-    /// use dary_heap::OctonaryHeap;
+    /// use dary_heap::QuaternaryHeap;
+    /// let mut heap = QuaternaryHeap::new();
     ///
-    /// let mut a = OctonaryHeap::from([-10, 1, 2, 3, 3]);
-    /// let mut b = OctonaryHeap::from([-20, 5, 43]);
+    /// assert_eq!(heap.replace_root(3), None);
+    /// assert_eq!(heap.replace_root(7), Some(3));
+    /// heap.push(5);
+    /// assert_eq!(heap.replace_root(1), Some(7));
+    /// assert_eq!(heap.into_sorted_vec(), [1, 5]);
+    /// ```
     ///
-    /// a.append(&mut b);
+    /// # Time complexity
     ///
-    /// assert_eq!(a.into_sorted_vec(), [-20, -10, 1, 2, 3, 3, 5, 43]);
-    /// assert!(b.is_empty());
-    /// ```
-    pub fn append(&mut self, other: &mut Self) {
-        if self.len() < other.len() {
-            swap(self, other);
+    /// The worst case cost of `replace_root` on a heap containing *n* elements
+    /// is *O*(log(*n*)).
+    pub fn replace_root(&mut self, mut item: T) -> Option<T> {
+        match self.data.get_mut(0) {
+            Some(top) => {
+                swap(&mut item, top);
+                // SAFETY: The heap is not empty, so 0 < self.len().
+                unsafe { self.sift_down(0) };
+                self.paranoid_check();
+                Some(item)
+            }
+            None => {
+                self.data.push(item);
+                None
+            }
         }
-
-        let start = self.data.len();
-
-        self.data.append(&mut other.data);
-
-        self.rebuild_tail(start);
     }
 
-    /// Clears the *d*-ary heap, returning an iterator over the removed elements
-    /// in heap order. If the iterator is dropped before being fully consumed,
-    /// it drops the remaining elements in heap order.
+    /// Calls `f` with a mutable reference to the greatest item of the *d*-ary
+    /// heap, then moves the item to its new place, and returns the result of
+    /// `f`, or `None` if the heap is empty.
     ///
-    /// The returned iterator keeps a mutable borrow on the heap to optimize
-    /// its implementation.
+    /// This is like changing the item through [`peek_mut`], but the heap is
+    /// restored as soon as `f` returns, also if `f` panics.
     ///
-    /// Note:
-    /// * `.drain_sorted()` is *O*(*n* \* log(*n*)); much slower than `.drain()`.
-    ///   You should use the latter for most cases.
+    /// [`peek_mut`]: DaryHeap::peek_mut
     ///
     /// # Examples
     ///
     /// Basic usage:
     ///
     /// ```
-    /// use dary_heap::TernaryHeap;
+    /// use dary_heap::QuaternaryHeap;
+    /// use std::cmp::Reverse;
+    /// // Tasks ordered by the earliest time they should run next.
+    /// let mut tasks = QuaternaryHeap::from([(Reverse(10), "poll"), (Reverse(25), "flush")]);
+    ///
+    /// // Run the next task and reschedule it.
+    /// let name = tasks.replace_root_with(|(Reverse(time), name)| {
+    ///     *time += 20;
+    ///     *name
+    /// });
+    /// assert_eq!(name, Some("poll"));
+    /// assert_eq!(tasks.peek(), Some(&(Reverse(25), "flush")));
+    /// ```
     ///
-    /// let mut heap = TernaryHeap::from([1, 2, 3, 4, 5]);
-    /// assert_eq!(heap.len(), 5);
+    /// # Time complexity
     ///
-    /// drop(heap.drain_sorted()); // removes all elements in heap order
-    /// assert_eq!(heap.len(), 0);
-    /// ```
-    #[inline]
-    #[cfg(feature = "unstable")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "unstable")))]
-    pub fn drain_sorted(&mut self) -> DrainSorted<'_, T, D> {
-        DrainSorted { inner: self }
+    /// Apart from the cost of `f`, the worst case cost of `replace_root_with`
+    /// on a heap containing *n* elements is *O*(log(*n*)).
+    pub fn replace_root_with<F, R>(&mut self, f: F) -> Option<R>
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        let mut top = self.peek_mut()?;
+        Some(f(&mut top))
     }
 
-    /// Retains only the elements specified by the predicate.
+    /// Removes one item equal to `item` from the *d*-ary heap and returns it, or
+    /// `None` if there is no such item.
     ///
-    /// In other words, remove all elements `e` for which `f(&e)` returns
-    /// `false`. The elements are visited in unsorted (and unspecified) order.
+    /// If there are multiple equal items, it is unspecified which one is
+    /// removed.
     ///
     /// # Examples
     ///
     /// Basic usage:
     ///
     /// ```
-    /// use dary_heap::OctonaryHeap;
+    /// use dary_heap::TernaryHeap;
+    /// let mut heap = TernaryHeap::from([1, 5, 2, 4]);
     ///
-    /// let mut heap = OctonaryHeap::from([-10, -5, 1, 2, 4, 13]);
+    /// assert_eq!(heap.remove(&2), Some(2));
+    /// assert_eq!(heap.remove(&3), None);
+    /// assert_eq!(heap.into_sorted_vec(), [1, 4, 5]);
+    /// ```
     ///
-    /// heap.retain(|x| x % 2 == 0); // only keep even numbers
+    /// # Time complexity
     ///
-    /// assert_eq!(heap.into_sorted_vec(), [-10, 2, 4])
-    /// ```
-    pub fn retain<F>(&mut self, mut f: F)
-    where
-        F: FnMut(&T) -> bool,
-    {
-        // rebuild_start will be updated to the first touched element below, and the rebuild will
-        // only be done for the tail.
-        let mut guard = RebuildOnDrop {
-            rebuild_from: self.len(),
-            heap: self,
-        };
-        // Split the borrow outside of the closure to appease the borrow checker
-        let rebuild_from = &mut guard.rebuild_from;
-        let mut i = 0;
-
-        guard.heap.data.retain(|e| {
-            let keep = f(e);
-            if !keep && i < *rebuild_from {
-                *rebuild_from = i;
-            }
-            i += 1;
-            keep
-        });
+    /// Finding the item takes *O*(*n*) time on a heap containing *n* elements,
+    /// after which removing it takes *O*(log(*n*)) time in the worst case.
+    pub fn remove(&mut self, item: &T) -> Option<T> {
+        let pos = self.data.iter().position(|x| x == item)?;
+        Some(self.remove_pos(pos))
     }
-}
 
-impl<T, const D: usize> DaryHeap<T, D> {
-    /// Returns an iterator visiting all values in the underlying vector, in
-    /// arbitrary order.
+    /// Removes the item at position `index` from the *d*-ary heap and returns
+    /// it, or `None` if `index` is out of bounds.
+    ///
+    /// Positions are those of the items in [`as_slice`] and [`iter`], e.g. as
+    /// found by [`Iterator::position`]. The last item takes the place of the
+    /// removed one, after which only its path is sifted.
+    ///
+    /// [`as_slice`]: DaryHeap::as_slice
+    /// [`iter`]: DaryHeap::iter
     ///
     /// # Examples
     ///
@@ -1097,22 +1784,32 @@ impl<T, const D: usize> DaryHeap<T, D> {
     ///
     /// ```
     /// use dary_heap::TernaryHeap;
-    /// let heap = TernaryHeap::from([1, 2, 3, 4]);
+    /// let mut heap = TernaryHeap::from([(3, 'a'), (1, 'b'), (2, 'c')]);
     ///
-    /// // Print 1, 2, 3, 4 in arbitrary order
-    /// for x in heap.iter() {
-    ///     println!("{x}");
-    /// }
+    /// let index = heap.iter().position(|&(_, name)| name == 'c').unwrap();
+    /// assert_eq!(heap.remove_at(index), Some((2, 'c')));
+    /// assert_eq!(heap.remove_at(2), None);
+    /// assert_eq!(heap.into_sorted_vec(), [(1, 'b'), (3, 'a')]);
     /// ```
-    pub fn iter(&self) -> Iter<'_, T> {
-        Iter {
-            iter: self.data.iter(),
+    ///
+    /// # Time complexity
+    ///
+    /// The worst case cost of `remove_at` on a heap containing *n* elements is
+    /// *O*(log(*n*)).
+    pub fn remove_at(&mut self, index: usize) -> Option<T> {
+        if index >= self.len() {
+            return None;
         }
+        Some(self.remove_pos(index))
     }
 
-    /// Returns an iterator which retrieves elements in heap order.
+    /// Replaces one item equal to `old` with `new`, and returns the replaced
+    /// item. If there is no such item, `new` is returned as an error.
     ///
-    /// This method consumes the original heap.
+    /// Only the path of the replaced item is sifted, so this is a cheap way to
+    /// update the priority of an item whose equality does not depend on its
+    /// priority. If there are multiple equal items, it is unspecified which
+    /// one is replaced.
     ///
     /// # Examples
     ///
@@ -1120,100 +1817,167 @@ impl<T, const D: usize> DaryHeap<T, D> {
     ///
     /// ```
     /// use dary_heap::QuaternaryHeap;
-    /// let heap = QuaternaryHeap::from([1, 2, 3, 4, 5]);
+    /// let mut heap = QuaternaryHeap::from([1, 5, 2, 4]);
     ///
-    /// assert_eq!(heap.into_iter_sorted().take(2).collect::<Vec<_>>(), [5, 4]);
+    /// assert_eq!(heap.replace_item(&2, 7), Ok(2));
+    /// assert_eq!(heap.replace_item(&3, 8), Err(8));
+    /// assert_eq!(heap.into_sorted_vec(), [1, 4, 5, 7]);
     /// ```
-    #[cfg(feature = "unstable")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "unstable")))]
-    pub fn into_iter_sorted(self) -> IntoIterSorted<T, D> {
-        IntoIterSorted { inner: self }
+    ///
+    /// # Time complexity
+    ///
+    /// Finding the item takes *O*(*n*) time on a heap containing *n* elements,
+    /// after which replacing it takes *O*(log(*n*)) time in the worst case.
+    pub fn replace_item(&mut self, old: &T, mut new: T) -> Result<T, T> {
+        match self.data.iter().position(|x| x == old) {
+            Some(pos) => {
+                swap(&mut new, &mut self.data[pos]);
+                // SAFETY: position returned a valid index.
+                unsafe { self.sift_up_or_down(pos) };
+                Ok(new)
+            }
+            None => Err(new),
+        }
     }
 
-    /// Returns the greatest item in the *d*-ary heap, or `None` if it is empty.
+    /// Calls `f` with a mutable reference to the item at position `index`,
+    /// then moves the item to its new place, and returns the result of `f`.
+    ///
+    /// Positions are those of the items in [`as_slice`] and [`iter`], e.g. as
+    /// found by [`Iterator::position`]. Only the path of the changed item is
+    /// sifted, also if `f` panics.
+    ///
+    /// [`as_slice`]: DaryHeap::as_slice
+    /// [`iter`]: DaryHeap::iter
     ///
     /// # Examples
     ///
     /// Basic usage:
     ///
     /// ```
-    /// use dary_heap::BinaryHeap;
-    /// let mut heap = BinaryHeap::new();
-    /// assert_eq!(heap.peek(), None);
+    /// use dary_heap::TernaryHeap;
+    /// let mut heap = TernaryHeap::from([(3, 'a'), (1, 'b'), (2, 'c')]);
     ///
-    /// heap.push(1);
-    /// heap.push(5);
-    /// heap.push(2);
-    /// assert_eq!(heap.peek(), Some(&5));
+    /// let index = heap.iter().position(|&(_, name)| name == 'b').unwrap();
+    /// heap.modify_at(index, |(priority, _)| *priority = 5);
     ///
+    /// assert_eq!(heap.peek(), Some(&(5, 'b')));
     /// ```
     ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    ///
     /// # Time complexity
     ///
-    /// Cost is *O*(1) in the worst case.
-    #[must_use]
-    pub fn peek(&self) -> Option<&T> {
-        // Ignore this lint to keep it identical with upstream
-        #[allow(clippy::get_first)]
-        self.data.get(0)
+    /// Apart from the cost of `f`, the worst case cost of `modify_at` on a heap
+    /// containing *n* elements is *O*(log(*n*)).
+    pub fn modify_at<F, R>(&mut self, index: usize, f: F) -> R
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        let len = self.len();
+        assert!(
+            index < len,
+            "index (is {}) should be < len (is {})",
+            index,
+            len
+        );
+        let guard = SiftOnDrop {
+            heap: self,
+            pos: index,
+        };
+        f(&mut guard.heap.data[index])
     }
 
-    /// Returns the number of elements the *d*-ary heap can hold without reallocating.
+    /// Returns the `k`-th greatest item in the *d*-ary heap, counting from
+    /// zero, or `None` if the heap contains `k` items or fewer.
+    ///
+    /// The heap is not changed: the item is the last one returned by
+    /// [`peek_top_k`]`(k + 1)`, so `kth_largest(0)` is equivalent to
+    /// [`peek`]. If there are equal items, they are counted separately.
+    ///
+    /// [`peek`]: DaryHeap::peek
+    /// [`peek_top_k`]: DaryHeap::peek_top_k
     ///
     /// # Examples
     ///
     /// Basic usage:
     ///
     /// ```
-    /// use dary_heap::OctonaryHeap;
-    /// let mut heap = OctonaryHeap::with_capacity(100);
-    /// assert!(heap.capacity() >= 100);
-    /// heap.push(4);
+    /// use dary_heap::QuaternaryHeap;
+    /// let heap: QuaternaryHeap<_> = (1..=100).collect();
+    ///
+    /// assert_eq!(heap.kth_largest(0), Some(&100));
+    /// assert_eq!(heap.kth_largest(9), Some(&91));
+    /// assert_eq!(heap.kth_largest(100), None);
+    /// assert_eq!(heap.len(), 100);
     /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The cost of `kth_largest` is the same as that of
+    /// [`peek_top_k`]`(k + 1)`: *O*(*k* log(*k*)), independent of the size of
+    /// the heap.
     #[must_use]
-    pub fn capacity(&self) -> usize {
-        self.data.capacity()
+    pub fn kth_largest(&self, k: usize) -> Option<&T> {
+        if k >= self.len() {
+            return None;
+        }
+        if k == 0 {
+            return self.peek();
+        }
+        self.peek_top_k(k + 1).pop()
     }
 
-    /// Reserves the minimum capacity for at least `additional` elements more than
-    /// the current length. Unlike [`reserve`], this will not
-    /// deliberately over-allocate to speculatively avoid frequent allocations.
-    /// After calling `reserve_exact`, capacity will be greater than or equal to
-    /// `self.len() + additional`. Does nothing if the capacity is already
-    /// sufficient.
-    ///
-    /// [`reserve`]: DaryHeap::reserve
-    ///
-    /// # Panics
+    /// Returns `true` if the *d*-ary heap contains an item equal to `item`.
     ///
-    /// Panics if the new capacity overflows [`usize`].
+    /// Subtrees whose root is smaller than `item` are skipped, since none of
+    /// their items can be equal to `item`.
     ///
     /// # Examples
     ///
     /// Basic usage:
     ///
     /// ```
-    /// use dary_heap::OctonaryHeap;
-    /// let mut heap = OctonaryHeap::new();
-    /// heap.reserve_exact(100);
-    /// assert!(heap.capacity() >= 100);
-    /// heap.push(4);
+    /// use dary_heap::BinaryHeap;
+    /// let heap = BinaryHeap::from([1, 5, 2, 4]);
+    ///
+    /// assert!(heap.contains(&2));
+    /// assert!(!heap.contains(&3));
     /// ```
     ///
-    /// [`reserve`]: DaryHeap::reserve
-    pub fn reserve_exact(&mut self, additional: usize) {
-        self.data.reserve_exact(additional);
+    /// # Time complexity
+    ///
+    /// The worst case cost of `contains` on a heap containing *n* elements is
+    /// *O*(*n*), but items greater than most of the heap are found quickly.
+    #[must_use]
+    pub fn contains(&self, item: &T) -> bool {
+        self.find_index_bounded(|current| item <= current, |current| current == item)
+            .is_some()
     }
 
-    /// Reserves capacity for at least `additional` elements more than the
-    /// current length. The allocator may reserve more space to speculatively
-    /// avoid frequent allocations. After calling `reserve`,
-    /// capacity will be greater than or equal to `self.len() + additional`.
-    /// Does nothing if capacity is already sufficient.
+    /// Moves the item at position `index` up towards the root while it is
+    /// greater than its parent, and returns its new position.
     ///
-    /// # Panics
+    /// This restores the heap property after the item at `index` was
+    /// increased through [`as_mut_slice_unchecked`], as long as the rest of
+    /// the heap is valid. It is meant for
+    /// code that keeps track of the positions of items itself: the items that
+    /// moved are exactly the ancestors of the old position up to and including
+    /// the new position, which each moved one level down along that path.
+    /// Unlike [`modify_at`], no other checks are performed.
     ///
-    /// Panics if the new capacity overflows [`usize`].
+    /// [`as_mut_slice_unchecked`]: DaryHeap::as_mut_slice_unchecked
+    /// [`modify_at`]: DaryHeap::modify_at
+    ///
+    /// # Safety
+    ///
+    /// The caller must guarantee that `index < self.len()`.
+    ///
+    /// Violating the heap property elsewhere does not cause undefined
+    /// behavior, but the results of the methods of `DaryHeap` are then
+    /// unspecified.
     ///
     /// # Examples
     ///
@@ -1221,927 +1985,4016 @@ impl<T, const D: usize> DaryHeap<T, D> {
     ///
     /// ```
     /// use dary_heap::BinaryHeap;
-    /// let mut heap = BinaryHeap::new();
-    /// heap.reserve(100);
-    /// assert!(heap.capacity() >= 100);
-    /// heap.push(4);
+    /// let mut heap = BinaryHeap::from([5, 3, 4, 1]);
+    /// let index = heap.as_slice().iter().position(|&x| x == 1).unwrap();
+    ///
+    /// // Increase the item without restoring the heap property...
+    /// unsafe { heap.as_mut_slice_unchecked()[index] = 6 };
+    /// // SAFETY: index was found in the heap, so it is in bounds, and sifting
+    /// // the increased item up restores the heap property.
+    /// let index = unsafe { heap.sift_up_at(index) };
+    ///
+    /// assert_eq!(index, 0);
+    /// assert_eq!(heap.peek(), Some(&6));
     /// ```
-    pub fn reserve(&mut self, additional: usize) {
-        self.data.reserve(additional);
+    ///
+    /// # Time complexity
+    ///
+    /// The worst case cost of `sift_up_at` on a heap containing *n* elements is
+    /// *O*(log(*n*)).
+    pub unsafe fn sift_up_at(&mut self, index: usize) -> usize {
+        // SAFETY: The caller guarantees that index < self.len().
+        self.sift_up(0, index)
     }
 
-    /// Tries to reserve the minimum capacity for at least `additional` elements
-    /// more than the current length. Unlike [`try_reserve`], this will not
-    /// deliberately over-allocate to speculatively avoid frequent allocations.
-    /// After calling `try_reserve_exact`, capacity will be greater than or
-    /// equal to `self.len() + additional` if it returns `Ok(())`.
-    /// Does nothing if the capacity is already sufficient.
+    /// Moves the item at position `index` down while it is smaller than its
+    /// greatest child, and returns its new position.
     ///
-    /// Note that the allocator may give the collection more space than it
-    /// requests. Therefore, capacity can not be relied upon to be precisely
-    /// minimal. Prefer [`try_reserve`] if future insertions are expected.
+    /// This restores the heap property after the item at `index` was
+    /// decreased, as long as the rest of the heap is valid. The items that
+    /// moved are exactly those on the path from the old position down to, but
+    /// excluding, the new position, which each moved one level up along that
+    /// path. See [`sift_up_at`] for the opposite direction.
     ///
-    /// [`try_reserve`]: DaryHeap::try_reserve
+    /// [`sift_up_at`]: DaryHeap::sift_up_at
     ///
-    /// # Errors
+    /// # Safety
     ///
-    /// If the capacity overflows, or the allocator reports a failure, then an error
-    /// is returned.
+    /// The caller must guarantee that `index < self.len()`.
+    ///
+    /// Violating the heap property elsewhere does not cause undefined
+    /// behavior, but the results of the methods of `DaryHeap` are then
+    /// unspecified.
     ///
     /// # Examples
     ///
+    /// Basic usage:
+    ///
     /// ```
     /// use dary_heap::BinaryHeap;
-    /// use std::collections::TryReserveError;
+    /// let mut heap = BinaryHeap::from([5, 3, 4, 1]);
     ///
-    /// fn find_max_slow(data: &[u32]) -> Result<Option<u32>, TryReserveError> {
-    ///     let mut heap = BinaryHeap::new();
+    /// // Decrease the greatest item without restoring the heap property...
+    /// unsafe { heap.as_mut_slice_unchecked()[0] = 0 };
+    /// // SAFETY: The heap is not empty, so 0 is in bounds, and sifting the
+    /// // decreased item down restores the heap property.
+    /// let index = unsafe { heap.sift_down_at(0) };
     ///
-    ///     // Pre-reserve the memory, exiting if we can't
-    ///     heap.try_reserve_exact(data.len())?;
+    /// assert_ne!(index, 0);
+    /// assert_eq!(heap.peek(), Some(&4));
+    /// ```
     ///
-    ///     // Now we know this can't OOM in the middle of our complex work
-    ///     heap.extend(data.iter());
+    /// # Time complexity
     ///
-    ///     Ok(heap.pop())
-    /// }
-    /// # find_max_slow(&[1, 2, 3]).expect("why is the test harness OOMing on 12 bytes?");
-    /// ```
-    #[cfg(feature = "extra")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "extra")))]
-    pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), TryReserveError> {
-        self.data.try_reserve_exact(additional)
+    /// The worst case cost of `sift_down_at` on a heap containing *n* elements
+    /// is *O*(*d* log(*n*)).
+    pub unsafe fn sift_down_at(&mut self, index: usize) -> usize {
+        // SAFETY: The caller guarantees that index < self.len().
+        self.sift_down(index)
     }
 
-    /// Tries to reserve capacity for at least `additional` elements more than the
-    /// current length. The allocator may reserve more space to speculatively
-    /// avoid frequent allocations. After calling `try_reserve`, capacity will be
-    /// greater than or equal to `self.len() + additional` if it returns
-    /// `Ok(())`. Does nothing if capacity is already sufficient. This method
-    /// preserves the contents even if an error occurs.
+    /// Returns the position of the first item for which `predicate` returns
+    /// `true`, or `None` if there is no such item.
     ///
-    /// # Errors
+    /// Positions are those of the items in [`as_slice`] and [`iter`], so the
+    /// result can be passed to [`modify_at`] or [`remove_at`]. See
+    /// [`find_index_bounded`] to skip subtrees that cannot contain a match.
     ///
-    /// If the capacity overflows, or the allocator reports a failure, then an error
-    /// is returned.
+    /// [`as_slice`]: DaryHeap::as_slice
+    /// [`iter`]: DaryHeap::iter
+    /// [`modify_at`]: DaryHeap::modify_at
+    /// [`remove_at`]: DaryHeap::remove_at
+    /// [`find_index_bounded`]: DaryHeap::find_index_bounded
     ///
     /// # Examples
     ///
-    /// ```
-    /// use dary_heap::QuaternaryHeap;
-    /// use std::collections::TryReserveError;
+    /// Basic usage:
     ///
-    /// fn find_max_slow(data: &[u32]) -> Result<Option<u32>, TryReserveError> {
-    ///     let mut heap = QuaternaryHeap::new();
+    /// ```
+    /// use dary_heap::TernaryHeap;
+    /// let mut heap = TernaryHeap::from([(3, 'a'), (1, 'b'), (2, 'c')]);
     ///
-    ///     // Pre-reserve the memory, exiting if we can't
-    ///     heap.try_reserve(data.len())?;
+    /// let index = heap.find_index(|&(_, name)| name == 'b').unwrap();
+    /// assert_eq!(heap.remove_at(index), Some((1, 'b')));
+    /// assert_eq!(heap.find_index(|&(_, name)| name == 'b'), None);
+    /// ```
     ///
-    ///     // Now we know this can't OOM in the middle of our complex work
-    ///     heap.extend(data.iter());
+    /// # Time complexity
     ///
-    ///     Ok(heap.pop())
-    /// }
-    /// # find_max_slow(&[1, 2, 3]).expect("why is the test harness OOMing on 12 bytes?");
-    /// ```
-    #[cfg(feature = "extra")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "extra")))]
-    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
-        self.data.try_reserve(additional)
+    /// Apart from the cost of `predicate`, the worst case cost of `find_index`
+    /// on a heap containing *n* elements is *O*(*n*).
+    pub fn find_index<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.data.iter().position(predicate)
     }
 
-    /// Discards as much additional capacity as possible.
+    /// Returns the position of an item for which `predicate` returns `true`,
+    /// skipping the subtrees of items for which `bound` returns `false`, or
+    /// `None` if no such item is found.
+    ///
+    /// `bound` must be monotone: if it returns `false` for an item, it should
+    /// also return `false` for all smaller items, and `predicate` should not
+    /// match any of them. Since no item is greater than its parent, the whole
+    /// subtree of such an item is then skipped. A bound that does not uphold
+    /// this may cause matching items to be missed, but is not unsafe.
+    ///
+    /// The heap is searched depth-first, so the returned position is not
+    /// necessarily the lowest position of a matching item.
     ///
     /// # Examples
     ///
     /// Basic usage:
     ///
     /// ```
-    /// use dary_heap::TernaryHeap;
-    /// let mut heap: TernaryHeap<i32> = TernaryHeap::with_capacity(100);
-    ///
-    /// assert!(heap.capacity() >= 100);
-    /// heap.shrink_to_fit();
-    /// assert!(heap.capacity() == 0);
+    /// use dary_heap::QuaternaryHeap;
+    /// let heap = QuaternaryHeap::from([(3, 'a'), (1, 'b'), (2, 'c'), (5, 'd')]);
+    ///
+    /// // Only items with a priority of at least 2 are visited.
+    /// let index = heap
+    ///     .find_index_bounded(|&(priority, _)| priority >= 2, |&(_, name)| name == 'c')
+    ///     .unwrap();
+    /// assert_eq!(heap.as_slice()[index], (2, 'c'));
+    /// assert_eq!(
+    ///     heap.find_index_bounded(|&(priority, _)| priority >= 2, |&(_, name)| name == 'b'),
+    ///     None
+    /// );
     /// ```
-    pub fn shrink_to_fit(&mut self) {
-        self.data.shrink_to_fit();
+    ///
+    /// # Time complexity
+    ///
+    /// Apart from the cost of the closures, the worst case cost of
+    /// `find_index_bounded` on a heap containing *n* elements is *O*(*n*), but
+    /// only items for which `bound` returns `true` and their children are
+    /// visited.
+    pub fn find_index_bounded<B, P>(&self, mut bound: B, mut predicate: P) -> Option<usize>
+    where
+        B: FnMut(&T) -> bool,
+        P: FnMut(&T) -> bool,
+    {
+        let len = self.len();
+        if len == 0 {
+            return None;
+        }
+        // Visit the tree in pre-order without a stack, by computing the next
+        // position from the current one.
+        let mut pos = 0;
+        loop {
+            let current = &self.data[pos];
+            if bound(current) {
+                if predicate(current) {
+                    return Some(pos);
+                }
+                let first_child = D * pos + 1;
+                if first_child < len {
+                    pos = first_child;
+                    continue;
+                }
+            }
+            // Move to the next sibling, or to the next sibling of the nearest
+            // ancestor that has one.
+            loop {
+                if pos == 0 {
+                    return None;
+                }
+                let is_last_child = pos % D == 0;
+                if !is_last_child && pos + 1 < len {
+                    pos += 1;
+                    break;
+                }
+                pos = (pos - 1) / D;
+            }
+        }
     }
 
-    /// Discards capacity with a lower bound.
+    /// Checks whether the *d*-ary heap satisfies the heap property, i.e. no
+    /// item is greater than its parent.
     ///
-    /// The capacity will remain at least as large as both the length
-    /// and the supplied value.
+    /// This can only fail if the order of items was changed while they were
+    /// in the heap, which is a logic error; see the [`DaryHeap`]
+    /// documentation. After such a change, the order of the heap can be
+    /// restored with [`as_vec_mut`], which rebuilds the heap when the returned
+    /// guard is dropped.
     ///
-    /// If the current capacity is less than the lower limit, this is a no-op.
+    /// [`as_vec_mut`]: DaryHeap::as_vec_mut
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidHeapError`] with the position of the first item
+    /// that is greater than its parent.
     ///
     /// # Examples
     ///
+    /// Basic usage:
+    ///
     /// ```
-    /// use dary_heap::TernaryHeap;
-    /// let mut heap: TernaryHeap<i32> = TernaryHeap::with_capacity(100);
+    /// use dary_heap::BinaryHeap;
+    /// use std::cell::Cell;
     ///
-    /// assert!(heap.capacity() >= 100);
-    /// heap.shrink_to(10);
-    /// assert!(heap.capacity() >= 10);
+    /// let heap = BinaryHeap::from([Cell::new(3), Cell::new(2), Cell::new(1)]);
+    /// assert!(heap.validate().is_ok());
+    ///
+    /// // Change the order of the items through interior mutability
+    /// heap.iter().find(|x| x.get() == 1).unwrap().set(4);
+    /// let err = heap.validate().unwrap_err();
+    /// assert_eq!(err.parent(), 0);
     /// ```
-    #[inline]
-    #[cfg(feature = "extra")]
-    #[cfg_attr(docsrs, doc(cfg(feature = "extra")))]
-    pub fn shrink_to(&mut self, min_capacity: usize) {
-        self.data.shrink_to(min_capacity)
+    ///
+    /// # Time complexity
+    ///
+    /// The cost of `validate` is *O*(*n*).
+    pub fn validate(&self) -> Result<(), InvalidHeapError> {
+        let index = slice::is_heap_until::<T, D>(&self.data);
+        if index == self.len() {
+            Ok(())
+        } else {
+            Err(InvalidHeapError {
+                index,
+                parent: (index - 1) / D,
+            })
+        }
     }
 
-    /// Returns a slice of all values in the underlying vector, in arbitrary
-    /// order.
+    /// Returns `true` if the *d*-ary heap satisfies the heap property. See
+    /// [`validate`] for more information.
+    ///
+    /// [`validate`]: DaryHeap::validate
     ///
     /// # Examples
     ///
     /// Basic usage:
     ///
     /// ```
-    /// use dary_heap::OctonaryHeap;
-    /// use std::io::{self, Write};
-    ///
-    /// let heap = OctonaryHeap::from([1, 2, 3, 4, 5, 6, 7]);
+    /// use dary_heap::QuaternaryHeap;
+    /// let heap = QuaternaryHeap::from([1, 5, 2]);
     ///
-    /// io::sink().write(heap.as_slice()).unwrap();
+    /// assert!(heap.is_valid());
     /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The cost of `is_valid` is *O*(*n*).
     #[must_use]
-    pub fn as_slice(&self) -> &[T] {
-        self.data.as_slice()
+    pub fn is_valid(&self) -> bool {
+        self.validate().is_ok()
     }
 
-    /// Consumes the `DaryHeap` and returns the underlying vector
-    /// in arbitrary order.
+    /// Returns the smallest item in the *d*-ary heap, or `None` if it is
+    /// empty.
+    ///
+    /// Only the leaves of the heap are scanned, since every other item is
+    /// greater than or equal to its children. If several items are equal to
+    /// the smallest item, it is unspecified which one is returned.
     ///
     /// # Examples
     ///
     /// Basic usage:
     ///
     /// ```
-    /// use dary_heap::QuaternaryHeap;
-    /// let heap = QuaternaryHeap::from([1, 2, 3, 4, 5, 6, 7]);
-    /// let vec = heap.into_vec();
+    /// use dary_heap::TernaryHeap;
+    /// let mut heap = TernaryHeap::new();
+    /// assert_eq!(heap.min(), None);
     ///
-    /// // Will print in some order
-    /// for x in vec {
-    ///     println!("{x}");
-    /// }
+    /// heap.extend(vec![4, 9, 2, 7, 5]);
+    /// assert_eq!(heap.min(), Some(&2));
     /// ```
-    #[must_use = "`self` will be dropped if the result is not used"]
-    pub fn into_vec(self) -> Vec<T> {
-        self.into()
+    ///
+    /// # Time complexity
+    ///
+    /// The cost of `min` is *O*(*n*), but only about (*d* - 1) / *d* of the
+    /// items are compared.
+    #[must_use]
+    pub fn min(&self) -> Option<&T> {
+        assert_ne!(D, 0, "Arity should be greater than zero");
+        let first_leaf = match self.len() {
+            0 | 1 => 0,
+            len => (len - 2) / D + 1,
+        };
+        self.data[first_leaf..].iter().min()
     }
 
-    /// Returns the length of the *d*-ary heap.
+    /// Removes the item at `pos` and restores the heap property.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pos` is out of bounds.
+    fn remove_pos(&mut self, pos: usize) -> T {
+        let item = self.data.swap_remove(pos);
+        if pos < self.len() {
+            // SAFETY: pos < self.len() was just checked.
+            unsafe { self.sift_up_or_down(pos) };
+        } else {
+            self.paranoid_check();
+        }
+        item
+    }
+
+    /// Moves the item at `pos` up or down to restore the heap property,
+    /// assuming the rest of the heap is valid.
+    ///
+    /// # Safety
+    ///
+    /// The caller must guarantee that `pos < self.len()`.
+    unsafe fn sift_up_or_down(&mut self, pos: usize) {
+        // SAFETY: The caller guarantees that pos < self.len().
+        let new_pos = self.sift_up(0, pos);
+        if new_pos == pos {
+            // SAFETY: Same as above.
+            self.sift_down(pos);
+        }
+        self.paranoid_check();
+    }
+
+    /// Consumes the `DaryHeap` and returns a vector in sorted
+    /// (ascending) order.
     ///
     /// # Examples
     ///
     /// Basic usage:
     ///
     /// ```
-    /// use dary_heap::BinaryHeap;
-    /// let heap = BinaryHeap::from([1, 3]);
+    /// use dary_heap::OctonaryHeap;
     ///
-    /// assert_eq!(heap.len(), 2);
+    /// let mut heap = OctonaryHeap::from([1, 2, 4, 5, 7]);
+    /// heap.push(6);
+    /// heap.push(3);
+    ///
+    /// let vec = heap.into_sorted_vec();
+    /// assert_eq!(vec, [1, 2, 3, 4, 5, 6, 7]);
     /// ```
-    #[must_use]
-    pub fn len(&self) -> usize {
-        self.data.len()
+    #[must_use = "`self` will be dropped if the result is not used"]
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        sift::sort_heap(&mut self.data, sift::Const::<D>, &Natural);
+        self.into_vec()
     }
 
-    /// Checks if the *d*-ary heap is empty.
+    /// Consumes the `DaryHeap` and returns a vector in sorted
+    /// (descending) order.
+    ///
+    /// This is equivalent to reversing the result of [`into_sorted_vec`].
+    ///
+    /// [`into_sorted_vec`]: DaryHeap::into_sorted_vec
     ///
     /// # Examples
     ///
     /// Basic usage:
     ///
     /// ```
-    /// use dary_heap::BinaryHeap;
-    /// let mut heap = BinaryHeap::new();
-    ///
-    /// assert!(heap.is_empty());
+    /// use dary_heap::QuaternaryHeap;
     ///
+    /// let mut heap = QuaternaryHeap::from([1, 2, 4, 5, 7]);
+    /// heap.push(6);
     /// heap.push(3);
-    /// heap.push(5);
-    /// heap.push(1);
     ///
-    /// assert!(!heap.is_empty());
+    /// let vec = heap.into_sorted_vec_desc();
+    /// assert_eq!(vec, [7, 6, 5, 4, 3, 2, 1]);
     /// ```
-    #[must_use]
-    pub fn is_empty(&self) -> bool {
-        self.len() == 0
+    ///
+    /// # Time complexity
+    ///
+    /// The cost of `into_sorted_vec_desc` is *O*(*n* log(*n*)).
+    #[must_use = "`self` will be dropped if the result is not used"]
+    pub fn into_sorted_vec_desc(self) -> Vec<T> {
+        let mut vec = self.into_sorted_vec();
+        vec.reverse();
+        vec
     }
 
-    /// Clears the *d*-ary heap, returning an iterator over the removed elements
-    /// in arbitrary order. If the iterator is dropped before being fully
-    /// consumed, it drops the remaining elements in arbitrary order.
+    /// Moves all items of the *d*-ary heap into `vec` in sorted (ascending)
+    /// order, after the items already in `vec`.
     ///
-    /// The returned iterator keeps a mutable borrow on the heap to optimize
-    /// its implementation.
+    /// This is like [`into_sorted_vec`], but the heap keeps its allocation,
+    /// and the capacity of `vec` is reused. This avoids allocating in loops
+    /// that repeatedly fill a heap and extract its items in order.
+    ///
+    /// [`into_sorted_vec`]: DaryHeap::into_sorted_vec
     ///
     /// # Examples
     ///
     /// Basic usage:
     ///
     /// ```
-    /// use dary_heap::QuaternaryHeap;
-    /// let mut heap = QuaternaryHeap::from([1, 3]);
-    ///
-    /// assert!(!heap.is_empty());
-    ///
-    /// for x in heap.drain() {
-    ///     println!("{x}");
+    /// use dary_heap::TernaryHeap;
+    /// let mut heap = TernaryHeap::with_capacity(8);
+    /// let mut sorted = Vec::with_capacity(8);
+    ///
+    /// for round in 0..3 {
+    ///     heap.extend([5, 1, 4, 2, 3].iter().map(|i| i * round));
+    ///     sorted.clear();
+    ///     heap.sort_into(&mut sorted);
+    ///     assert_eq!(sorted, [round, 2 * round, 3 * round, 4 * round, 5 * round]);
     /// }
-    ///
     /// assert!(heap.is_empty());
+    /// assert!(heap.capacity() >= 8);
     /// ```
-    #[inline]
-    pub fn drain(&mut self) -> Drain<'_, T> {
-        Drain {
-            iter: self.data.drain(..),
-        }
+    ///
+    /// # Time complexity
+    ///
+    /// The cost of `sort_into` is *O*(*n* log(*n*)), plus the cost of
+    /// growing `vec` if it does not have enough spare capacity.
+    pub fn sort_into(&mut self, vec: &mut Vec<T>) {
+        sift::sort_heap(&mut self.data, sift::Const::<D>, &Natural);
+        vec.append(&mut self.data);
     }
 
-    /// Drops all items from the *d*-ary heap.
+    /// Consumes the `DaryHeap` and returns a vector of its `k` greatest items
+    /// in sorted (descending) order.
+    ///
+    /// If the heap contains fewer than `k` items, all of them are returned.
+    /// This is like Python's `heapq.nlargest`.
     ///
     /// # Examples
     ///
     /// Basic usage:
     ///
     /// ```
-    /// use dary_heap::TernaryHeap;
-    /// let mut heap = TernaryHeap::from([1, 3]);
+    /// use dary_heap::QuaternaryHeap;
+    /// let heap = QuaternaryHeap::from([5, 1, 8, 3, 9, 2]);
     ///
-    /// assert!(!heap.is_empty());
+    /// assert_eq!(heap.clone().into_n_largest(3), [9, 8, 5]);
+    /// assert_eq!(heap.into_n_largest(10), [9, 8, 5, 3, 2, 1]);
+    /// ```
     ///
-    /// heap.clear();
+    /// # Time complexity
     ///
-    /// assert!(heap.is_empty());
-    /// ```
-    pub fn clear(&mut self) {
-        self.drain();
+    /// Only the `k` greatest items are sorted by moving them out of the heap
+    /// one by one, so the cost of `into_n_largest` on a heap containing *n*
+    /// elements is *O*(*k* log(*n*)), plus *O*(*k*) for copying them to a new
+    /// vector if *k* < *n*.
+    #[must_use = "`self` will be dropped if the result is not used"]
+    pub fn into_n_largest(mut self, k: usize) -> Vec<T> {
+        let len = self.len();
+        if k >= len {
+            return self.into_sorted_vec_desc();
+        }
+        sift::sort_heap_tail(&mut self.data, sift::Const::<D>, &Natural, k);
+        let mut largest = self.data.split_off(len - k);
+        largest.reverse();
+        largest
     }
-}
 
-/// Hole represents a hole in a slice i.e., an index without valid value
-/// (because it was moved from or duplicated).
-/// In drop, `Hole` will restore the slice by filling the hole
-/// position with the value that was originally removed.
-struct Hole<'a, T: 'a> {
-    data: &'a mut [T],
-    elt: ManuallyDrop<T>,
-    pos: usize,
-}
-
-impl<'a, T> Hole<'a, T> {
-    /// Creates a new `Hole` at index `pos`.
+    /// Consumes the `DaryHeap` and returns a vector of its `k` smallest items
+    /// in sorted (ascending) order.
     ///
-    /// Unsafe because pos must be within the data slice.
-    #[inline]
-    unsafe fn new(data: &'a mut [T], pos: usize) -> Self {
-        debug_assert!(pos < data.len());
-        // SAFE: pos should be inside the slice
-        let elt = ptr::read(data.get_unchecked(pos));
-        Hole {
-            data,
-            elt: ManuallyDrop::new(elt),
-            pos,
+    /// If the heap contains fewer than `k` items, all of them are returned.
+    /// This is like Python's `heapq.nsmallest`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::QuaternaryHeap;
+    /// let heap = QuaternaryHeap::from([5, 1, 8, 3, 9, 2]);
+    ///
+    /// assert_eq!(heap.clone().into_n_smallest(3), [1, 2, 3]);
+    /// assert_eq!(heap.into_n_smallest(10), [1, 2, 3, 5, 8, 9]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The smallest items are not near the top of the heap, so if *k* < *n*
+    /// they are selected with [`slice::partial_sort`], which costs
+    /// *O*(*n* log(*k*)) on a heap containing *n* elements. Otherwise all
+    /// items are sorted in *O*(*n* log(*n*)) time, like [`into_sorted_vec`].
+    ///
+    /// [`into_sorted_vec`]: DaryHeap::into_sorted_vec
+    #[must_use = "`self` will be dropped if the result is not used"]
+    pub fn into_n_smallest(self, k: usize) -> Vec<T> {
+        if k >= self.len() {
+            return self.into_sorted_vec();
         }
+        let mut vec = self.into_vec();
+        slice::partial_sort::<T, D>(&mut vec, k);
+        vec.truncate(k);
+        vec
     }
 
-    #[inline]
-    fn pos(&self) -> usize {
-        self.pos
+    /// # Safety
+    ///
+    /// The caller must guarantee that `pos < self.len()`.
+    unsafe fn sift_up(&mut self, start: usize, pos: usize) -> usize {
+        let (data, arity, cmp) = self.sift_parts();
+        // SAFETY: The caller guarantees that pos < self.len().
+        sift::sift_up(data, arity, &cmp, start, pos)
     }
 
-    /// Returns a reference to the element removed.
-    #[inline]
-    fn element(&self) -> &T {
-        &self.elt
+    /// # Safety
+    ///
+    /// The caller must guarantee that `pos < self.len()`.
+    unsafe fn sift_down(&mut self, pos: usize) -> usize {
+        let (data, arity, cmp) = self.sift_parts();
+        // SAFETY: The caller guarantees that pos < self.len().
+        sift::sift_down(data, arity, &cmp, pos)
     }
 
-    /// Returns a reference to the element at `index`.
+    /// Take an element at `pos` and move it all the way down the heap,
+    /// then sift it up to its position.
     ///
-    /// Unsafe because index must be within the data slice and not equal to pos.
-    #[inline]
-    unsafe fn get(&self, index: usize) -> &T {
-        debug_assert!(index != self.pos);
-        debug_assert!(index < self.data.len());
-        self.data.get_unchecked(index)
+    /// # Safety
+    ///
+    /// The caller must guarantee that `pos < self.len()`.
+    unsafe fn sift_down_to_bottom(&mut self, pos: usize) {
+        let (data, arity, cmp) = self.sift_parts();
+        // SAFETY: The caller guarantees that pos < self.len().
+        sift::sift_down_to_bottom(data, arity, &cmp, pos);
     }
 
-    /// Move hole to new location
-    ///
-    /// Unsafe because index must be within the data slice and not equal to pos.
-    #[inline]
-    unsafe fn move_to(&mut self, index: usize) {
-        debug_assert!(index != self.pos);
-        debug_assert!(index < self.data.len());
-        let ptr = self.data.as_mut_ptr();
-        let index_ptr: *const _ = ptr.add(index);
-        let hole_ptr = ptr.add(self.pos);
-        ptr::copy_nonoverlapping(index_ptr, hole_ptr, 1);
-        self.pos = index;
+    /// Rebuild assuming data[0..start] is still a proper heap.
+    fn rebuild_tail(&mut self, start: usize) {
+        let (data, arity, cmp) = self.sift_parts();
+        sift::rebuild_tail(data, arity, &cmp, start);
     }
-}
 
-impl<'a, T: Ord> Hole<'a, T> {
-    /// Get largest element
-    ///
-    /// Unsafe because both elements must be within the data slice and not equal
-    /// to pos.
-    #[inline]
-    unsafe fn max(&self, elem1: usize, elem2: usize) -> usize {
-        if self.get(elem1) <= self.get(elem2) {
-            elem2
-        } else {
-            elem1
-        }
+    fn rebuild(&mut self) {
+        Self::check_len(self.len());
+        let (data, arity, cmp) = self.sift_parts();
+        sift::rebuild(data, arity, &cmp);
     }
 
-    /// Get index of greatest sibling
+    /// Panics if a heap of length `len` would exceed [`MAX_LEN`], such that
+    /// the positions of children could overflow.
     ///
-    /// Unsafe because all siblings must be within the data slice and not equal
-    /// to pos.
+    /// [`MAX_LEN`]: DaryHeap::MAX_LEN
     #[inline]
-    unsafe fn max_sibling<const D: usize>(&self, first_sibling: usize) -> usize {
-        let mut sibling = first_sibling;
-        match D {
-            2 => {
-                sibling += (self.get(sibling) <= self.get(sibling + 1)) as usize;
-            }
-            3 => {
-                let sibling_a = self.max_sibling::<2>(sibling);
-                let sibling_b = sibling + 2;
-                sibling = self.max(sibling_a, sibling_b);
-            }
-            4 => {
-                let sibling_a = self.max_sibling::<2>(sibling);
-                let sibling_b = self.max_sibling::<2>(sibling + 2);
-                sibling = self.max(sibling_a, sibling_b);
-            }
-            _ => {
-                for other_sibling in sibling + 1..sibling + D {
-                    if self.get(sibling) <= self.get(other_sibling) {
-                        sibling = other_sibling;
-                    }
-                }
-            }
-        }
-        sibling
+    #[track_caller]
+    fn check_len(len: usize) {
+        assert!(
+            len <= Self::MAX_LEN,
+            "length {} exceeds the maximum length of a {}-ary heap",
+            len,
+            D
+        );
     }
 
-    /// Get index of greatest sibling within range
-    ///
-    /// Unsafe because end must be the length of the data slice, last sibling
-    /// must be outside of the data slice and no sibling may be equal to pos.
-    /// It is allowed for first_sibling to be outside of the data slice.
+    /// Panics if the heap property does not hold, if the `paranoid` feature
+    /// is enabled in a build with debug assertions.
     #[inline]
-    unsafe fn max_sibling_to<const D: usize>(&self, first_sibling: usize, end: usize) -> usize {
-        let mut sibling = first_sibling;
-        match D {
-            2 => {}
-            3 => {
-                if sibling + 1 < end {
-                    sibling = self.max_sibling::<2>(sibling);
-                }
-            }
-            _ => {
-                for other_sibling in sibling + 1..end {
-                    if self.get(sibling) <= self.get(other_sibling) {
-                        sibling = other_sibling;
-                    }
-                }
-            }
+    #[track_caller]
+    fn paranoid_check(&self) {
+        #[cfg(all(feature = "paranoid", debug_assertions))]
+        if let Err(err) = self.validate() {
+            panic!("heap property violated: {}", err);
         }
-        sibling
     }
-}
 
-impl<T> Drop for Hole<'_, T> {
-    #[inline]
-    fn drop(&mut self) {
-        // fill the hole again
-        unsafe {
-            let pos = self.pos;
-            ptr::copy_nonoverlapping(&*self.elt, self.data.get_unchecked_mut(pos), 1);
-        }
+    /// Returns the data of the heap together with the arity and comparator to
+    /// sift it with, which record their work if the `stats` feature is
+    /// enabled.
+    #[cfg(feature = "stats")]
+    fn sift_parts(&mut self) -> (&mut Vec<T>, stats::Recorder<'_, D>, stats::Recorder<'_, D>) {
+        let recorder = stats::Recorder::new(&mut self.stats);
+        (&mut self.data, recorder, recorder)
     }
-}
 
-/// An iterator over the elements of a `DaryHeap`.
-///
-/// This `struct` is created by [`DaryHeap::iter()`]. See its
-/// documentation for more.
-///
-/// [`iter`]: DaryHeap::iter
-#[must_use = "iterators are lazy and do nothing unless consumed"]
-pub struct Iter<'a, T: 'a> {
-    iter: slice::Iter<'a, T>,
-}
+    /// Returns the data of the heap together with the arity and comparator to
+    /// sift it with, which record their work if the `stats` feature is
+    /// enabled.
+    #[cfg(not(feature = "stats"))]
+    fn sift_parts(&mut self) -> (&mut Vec<T>, sift::Const<D>, Natural) {
+        (&mut self.data, sift::Const, Natural)
+    }
 
-impl<T> Default for Iter<'_, T> {
-    /// Creates an empty `dary_heap::Iter`.
+    /// Moves all the elements of `other` into `self`, leaving `other` empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
     ///
     /// ```
-    /// let iter: dary_heap::Iter<'_, u8> = Default::default();
-    /// assert_eq!(iter.len(), 0);
+    /// use dary_heap::OctonaryHeap;
+    ///
+    /// let mut a = OctonaryHeap::from([-10, 1, 2, 3, 3]);
+    /// let mut b = OctonaryHeap::from([-20, 5, 43]);
+    ///
+    /// a.append(&mut b);
+    ///
+    /// assert_eq!(a.into_sorted_vec(), [-20, -10, 1, 2, 3, 3, 5, 43]);
+    /// assert!(b.is_empty());
     /// ```
-    fn default() -> Self {
-        // `Default::default()` requires Rust 1.70.0 or later
-        Iter { iter: [].iter() }
-    }
-}
-
-impl<T: fmt::Debug> fmt::Debug for Iter<'_, T> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_tuple("Iter").field(&self.iter.as_slice()).finish()
-    }
-}
+    pub fn append(&mut self, other: &mut Self) {
+        Self::check_len(self.len().saturating_add(other.len()));
 
-// FIXME(#26925) Remove in favor of `#[derive(Clone)]`
-impl<T> Clone for Iter<'_, T> {
-    fn clone(&self) -> Self {
-        Iter {
-            iter: self.iter.clone(),
+        if self.len() < other.len() {
+            swap(self, other);
         }
-    }
-}
 
-impl<'a, T> Iterator for Iter<'a, T> {
-    type Item = &'a T;
+        let start = self.data.len();
 
-    #[inline]
-    fn next(&mut self) -> Option<&'a T> {
-        self.iter.next()
-    }
+        self.data.append(&mut other.data);
 
-    #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        self.iter.size_hint()
+        self.rebuild_tail(start);
+        self.paranoid_check();
     }
 
-    #[inline]
-    fn last(self) -> Option<&'a T> {
-        self.iter.last()
+    /// Moves all the elements of `other` into `self`, leaving `other` empty.
+    ///
+    /// Unlike converting `other` into a heap and using [`append`], the items
+    /// are not arranged as a heap first. Depending on the number of items,
+    /// either the whole heap is rebuilt or the new items are sifted up,
+    /// whichever is expected to be cheaper.
+    ///
+    /// [`append`]: DaryHeap::append
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::OctonaryHeap;
+    ///
+    /// let mut heap = OctonaryHeap::from([-10, 1, 2, 3, 3]);
+    /// let mut vec = vec![-20, 5, 43];
+    ///
+    /// heap.append_vec(&mut vec);
+    ///
+    /// assert_eq!(heap.into_sorted_vec(), [-20, -10, 1, 2, 3, 3, 5, 43]);
+    /// assert!(vec.is_empty());
+    /// ```
+    pub fn append_vec(&mut self, other: &mut Vec<T>) {
+        Self::check_len(self.len().saturating_add(other.len()));
+
+        let start = self.data.len();
+
+        self.data.append(other);
+
+        self.rebuild_tail(start);
+        self.paranoid_check();
     }
-}
 
-impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    /// Converts the *d*-ary heap into a heap with a different arity *e*,
+    /// reusing its allocation.
+    ///
+    /// `From` cannot be used for this conversion, since it would conflict
+    /// with the reflexive `From` implementation when *d* = *e*.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::{BinaryHeap, OctonaryHeap};
+    ///
+    /// let heap = BinaryHeap::from([3, 1, 4, 1, 5]);
+    /// let mut heap: OctonaryHeap<_> = heap.into_arity();
+    ///
+    /// assert_eq!(heap.pop(), Some(5));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The cost of `into_arity` is *O*(*n*) for rebuilding the heap, or *O*(1)
+    /// if *d* = *e*.
+    #[must_use = "`self` will be dropped if the result is not used"]
+    pub fn into_arity<const E: usize>(self) -> DaryHeap<T, E> {
+        let mut heap = DaryHeap {
+            data: self.data,
+            #[cfg(feature = "stats")]
+            stats: HeapStats::new(),
+        };
+        if D != E {
+            heap.rebuild();
+        }
+        heap.paranoid_check();
+        heap
+    }
+
+    /// Melds *d*-ary heaps into a single heap.
+    ///
+    /// The allocation of the largest heap is reused, and the items of the
+    /// other heaps are moved into it, after which the heap is restored once
+    /// like in [`append`]. The [`Sum`] implementation of `DaryHeap` does the
+    /// same.
+    ///
+    /// [`append`]: DaryHeap::append
+    /// [`Sum`]: core::iter::Sum
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::TernaryHeap;
+    ///
+    /// let shards = vec![
+    ///     TernaryHeap::from(vec![1, 7]),
+    ///     TernaryHeap::from(vec![4, 2, 9]),
+    ///     TernaryHeap::new(),
+    /// ];
+    /// let heap = TernaryHeap::meld(shards);
+    /// assert_eq!(heap.into_sorted_vec(), [1, 2, 4, 7, 9]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The cost of `meld` is the same as that of appending all heaps but the
+    /// largest one to the largest one at once.
+    pub fn meld<I: IntoIterator<Item = DaryHeap<T, D>>>(heaps: I) -> DaryHeap<T, D> {
+        let mut heaps: Vec<_> = heaps.into_iter().collect();
+        let largest = match (0..heaps.len()).max_by_key(|&i| heaps[i].len()) {
+            Some(largest) => largest,
+            None => return DaryHeap::new(),
+        };
+        let len = heaps
+            .iter()
+            .fold(0usize, |len, heap| len.saturating_add(heap.len()));
+        Self::check_len(len);
+        let mut heap = heaps.swap_remove(largest);
+        let start = heap.len();
+        heap.reserve(len - start);
+        for other in heaps {
+            heap.data.extend(other.data);
+        }
+        heap.rebuild_tail(start);
+        heap.paranoid_check();
+        heap
+    }
+
+    /// Splits the *d*-ary heap into two at the given index of the underlying
+    /// vector.
+    ///
+    /// Returns a newly allocated heap containing the items at positions
+    /// `[at, len)` of the underlying vector. After the call, the original
+    /// heap is left containing the items at positions `[0, at)`. The items
+    /// that end up in either heap depend on the internal layout of the heap,
+    /// so this is mostly useful to divide the items in two parts of a chosen
+    /// size. The original heap does not need to be rebuilt, since the start of
+    /// a heap is a heap itself.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::TernaryHeap;
+    ///
+    /// let mut a = TernaryHeap::from([1, 2, 3, 4, 5, 6, 7, 8]);
+    /// let b = a.split_off(5);
+    ///
+    /// assert_eq!(a.len(), 5);
+    /// assert_eq!(b.len(), 3);
+    /// assert_eq!(a.peek(), Some(&8));
+    ///
+    /// let mut all = a.into_vec();
+    /// all.extend(b.into_sorted_vec());
+    /// all.sort_unstable();
+    /// assert_eq!(all, [1, 2, 3, 4, 5, 6, 7, 8]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The cost of `split_off` is *O*(*k*), where *k* = `len - at` is the
+    /// number of items moved to the new heap.
+    #[must_use = "`self` keeps only the first half; the other half is returned"]
+    pub fn split_off(&mut self, at: usize) -> Self {
+        let other = DaryHeap::from(self.data.split_off(at));
+        self.paranoid_check();
+        other.paranoid_check();
+        other
+    }
+
+    /// Discards all but the `k` greatest items of the *d*-ary heap.
+    ///
+    /// If `k` is greater than or equal to the length of the heap, this has no
+    /// effect.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::QuaternaryHeap;
+    ///
+    /// let mut heap = QuaternaryHeap::from([3, 9, 1, 7, 5, 8]);
+    /// heap.truncate_top(3);
+    ///
+    /// assert_eq!(heap.into_sorted_vec(), [7, 8, 9]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The cost of `truncate_top` is *O*(*n* + *k* log(*n*)).
+    pub fn truncate_top(&mut self, k: usize) {
+        let len = self.len();
+        if k >= len {
+            return;
+        }
+        self.sort_top(k);
+        self.data.drain(..len - k);
+        // The remaining items are in ascending order, so reversing them
+        // yields a valid heap.
+        self.data.reverse();
+        self.paranoid_check();
+    }
+
+    /// Removes the `n` greatest items from the *d*-ary heap and returns them
+    /// in descending order.
+    ///
+    /// If `n` is greater than the length of the heap, all items are removed.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::QuaternaryHeap;
+    ///
+    /// let mut heap = QuaternaryHeap::from([3, 9, 1, 7, 5, 8]);
+    ///
+    /// assert_eq!(heap.pop_n(4), [9, 8, 7, 5]);
+    /// assert_eq!(heap.into_sorted_vec(), [1, 3]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The cost of `pop_n` is *O*(*n* log(*m*)) on a heap containing *m*
+    /// elements.
+    #[must_use = "use `.truncate_top()` if you don't need the removed items"]
+    pub fn pop_n(&mut self, n: usize) -> Vec<T> {
+        let n = n.min(self.len());
+        self.sort_top(n);
+        let mut top = self.data.split_off(self.len() - n);
+        top.reverse();
+        self.paranoid_check();
+        top
+    }
+
+    /// Returns references to the `k` greatest items in the *d*-ary heap in
+    /// descending order, without removing them.
+    ///
+    /// If `k` is greater than the length of the heap, references to all items
+    /// are returned.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::TernaryHeap;
+    ///
+    /// let heap = TernaryHeap::from([3, 9, 1, 7, 5, 8]);
+    ///
+    /// assert_eq!(heap.peek_top_k(3), [&9, &8, &7]);
+    /// assert_eq!(heap.len(), 6);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The cost of `peek_top_k` is *O*(*k* log(*k*)), independent of the size
+    /// of the heap. It uses an auxiliary heap of candidate positions, which
+    /// starts at the root and receives the children of every item that is
+    /// taken from it.
+    #[must_use]
+    pub fn peek_top_k(&self, k: usize) -> Vec<&T> {
+        let k = k.min(self.len());
+        let mut top = Vec::with_capacity(k);
+        if k == 0 {
+            return top;
+        }
+        let mut candidates = DaryHeap::<(&T, usize), D>::with_capacity(k);
+        candidates.push((&self.data[0], 0));
+        while top.len() < k {
+            // The candidates always contain the greatest remaining item, as
+            // every item not yet taken descends from a candidate.
+            let (item, pos) = candidates.pop().unwrap();
+            top.push(item);
+            let first_child = D * pos + 1;
+            let children = first_child.min(self.len())..(first_child + D).min(self.len());
+            candidates.extend(children.map(|child| (&self.data[child], child)));
+        }
+        top
+    }
+
+    /// Moves the `k` greatest items to the end of the underlying vector in
+    /// ascending order, leaving the other items as a heap at the start.
+    ///
+    /// The caller must guarantee that `k <= self.len()`.
+    fn sort_top(&mut self, k: usize) {
+        let len = self.len();
+        for end in (len - k..len).rev() {
+            self.data.swap(0, end);
+            if end > 0 {
+                // SAFETY: 0 < end <= self.len()
+                unsafe {
+                    sift::sift_down_range(&mut self.data, sift::Const::<D>, &Natural, 0, end)
+                };
+            }
+        }
+    }
+
+    /// Clears the *d*-ary heap, returning an iterator over the removed elements
+    /// in heap order. If the iterator is dropped before being fully consumed,
+    /// it drops the remaining elements in heap order.
+    ///
+    /// The returned iterator keeps a mutable borrow on the heap to optimize
+    /// its implementation.
+    ///
+    /// Note:
+    /// * `.drain_sorted()` is *O*(*n* \* log(*n*)); much slower than `.drain()`.
+    ///   You should use the latter for most cases.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::TernaryHeap;
+    ///
+    /// let mut heap = TernaryHeap::from([1, 2, 3, 4, 5]);
+    /// assert_eq!(heap.len(), 5);
+    ///
+    /// drop(heap.drain_sorted()); // removes all elements in heap order
+    /// assert_eq!(heap.len(), 0);
+    /// ```
+    #[inline]
+    pub fn drain_sorted(&mut self) -> DrainSorted<'_, T, D> {
+        DrainSorted { inner: self }
+    }
+
+    /// Returns an iterator which removes the greatest item from the *d*-ary
+    /// heap and yields it, for as long as the predicate returns `true` for the
+    /// greatest item.
+    ///
+    /// The iterator stops at the first item for which the predicate returns
+    /// `false`, which remains in the heap. Items that are not yielded because
+    /// the iterator was dropped early also remain in the heap.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::TernaryHeap;
+    /// use std::cmp::Reverse;
+    ///
+    /// let now = 10;
+    /// let mut deadlines = TernaryHeap::from([Reverse(12), Reverse(3), Reverse(9)]);
+    ///
+    /// let due: Vec<_> = deadlines.pop_while(|&Reverse(t)| t <= now).collect();
+    /// assert_eq!(due, [Reverse(3), Reverse(9)]);
+    /// assert_eq!(deadlines.peek(), Some(&Reverse(12)));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Yielding an item costs *O*(log(*n*)), and checking whether the iterator
+    /// is done costs *O*(1).
+    #[inline]
+    pub fn pop_while<F>(&mut self, pred: F) -> PopWhile<'_, T, F, D>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        PopWhile {
+            inner: self,
+            pred: Some(pred),
+        }
+    }
+
+    /// Returns an iterator which removes the items greater than or equal to
+    /// `threshold` from the *d*-ary heap and yields them in heap order.
+    ///
+    /// The iterator stops as soon as the greatest remaining item is less than
+    /// `threshold`. If the iterator is dropped before being fully consumed,
+    /// it removes the remaining matching items in heap order.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::QuaternaryHeap;
+    ///
+    /// let mut heap = QuaternaryHeap::from([1, 8, 3, 6, 5]);
+    ///
+    /// let ready: Vec<_> = heap.drain_above(5).collect();
+    /// assert_eq!(ready, [8, 6, 5]);
+    /// assert_eq!(heap.into_sorted_vec(), [1, 3]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Yielding an item costs *O*(log(*n*)), and checking whether the iterator
+    /// is done costs *O*(1).
+    #[inline]
+    pub fn drain_above(&mut self, threshold: T) -> DrainAbove<'_, T, D> {
+        DrainAbove {
+            inner: self,
+            threshold,
+        }
+    }
+
+    /// Splits the *d*-ary heap in two: the items greater than or equal to
+    /// `threshold` are kept, and the items less than `threshold` are returned
+    /// as a new heap.
+    ///
+    /// The items greater than or equal to `threshold` are at the top of the
+    /// heap, so they are found without comparing the items of subtrees whose
+    /// root is less than `threshold`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::QuaternaryHeap;
+    ///
+    /// let mut heap = QuaternaryHeap::from([1, 8, 3, 6, 5, 2]);
+    /// let low = heap.split_by(&5);
+    ///
+    /// assert_eq!(heap.into_sorted_vec(), [5, 6, 8]);
+    /// assert_eq!(low.into_sorted_vec(), [1, 2, 3]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// On a heap containing *n* elements of which *m* are kept, the cost of
+    /// `split_by` is *O*(*m* log(*n*)) if *m* is small, as the kept items are
+    /// then removed one by one. Otherwise, both heaps are rebuilt at a cost of
+    /// *O*(*n*).
+    #[must_use = "`self` keeps only the greater items; the smaller ones are returned"]
+    pub fn split_by(&mut self, threshold: &T) -> DaryHeap<T, D> {
+        let len = self.len();
+        // Find the positions of the kept items in ascending order, by visiting
+        // the tree level by level and skipping the children of smaller items.
+        let mut kept = Vec::new();
+        if matches!(self.peek(), Some(top) if top >= threshold) {
+            kept.push(0);
+        }
+        let mut i = 0;
+        while let Some(&pos) = kept.get(i) {
+            let first_child = D * pos + 1;
+            for child in first_child..len.min(first_child.saturating_add(D)) {
+                if self.data[child] >= *threshold {
+                    kept.push(child);
+                }
+            }
+            i += 1;
+        }
+
+        // The fix for this lint (usize::BITS) requires Rust 1.53.0, but the
+        // MSRV is currently 1.51.0.
+        #[allow(clippy::manual_bits)]
+        let log_len = 8 * core::mem::size_of::<usize>() - len.leading_zeros() as usize;
+        let lower = if kept.len().saturating_mul(log_len) < len {
+            // Removing a kept item only moves smaller items in its subtree,
+            // which contains no kept items at smaller positions, so the
+            // positions of the remaining kept items are unaffected.
+            let mut upper: Vec<_> = kept.iter().rev().map(|&pos| self.remove_pos(pos)).collect();
+            upper.reverse();
+            let lower = core::mem::replace(&mut self.data, upper);
+            DaryHeap {
+                data: lower,
+                #[cfg(feature = "stats")]
+                stats: HeapStats::new(),
+            }
+        } else {
+            // Gather the kept items at the front. The item at kept[k] has not
+            // been moved before step k, as all earlier swaps involve smaller
+            // positions.
+            for (k, &pos) in kept.iter().enumerate() {
+                self.data.swap(k, pos);
+            }
+            let lower = self.data.split_off(kept.len());
+            DaryHeap::from(lower)
+        };
+        self.rebuild();
+        self.paranoid_check();
+        lower
+    }
+
+    /// Retains only the elements specified by the predicate.
+    ///
+    /// In other words, remove all elements `e` for which `f(&e)` returns
+    /// `false`. The elements are visited in unsorted (and unspecified) order.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::OctonaryHeap;
+    ///
+    /// let mut heap = OctonaryHeap::from([-10, -5, 1, 2, 4, 13]);
+    ///
+    /// heap.retain(|x| x % 2 == 0); // only keep even numbers
+    ///
+    /// assert_eq!(heap.into_sorted_vec(), [-10, 2, 4])
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        // rebuild_start will be updated to the first touched element below, and the rebuild will
+        // only be done for the tail.
+        let mut guard = RebuildOnDrop {
+            rebuild_from: self.len(),
+            heap: self,
+        };
+        // Split the borrow outside of the closure to appease the borrow checker
+        let rebuild_from = &mut guard.rebuild_from;
+        let mut i = 0;
+
+        guard.heap.data.retain(|e| {
+            let keep = f(e);
+            if !keep && i < *rebuild_from {
+                *rebuild_from = i;
+            }
+            i += 1;
+            keep
+        });
+    }
+
+    /// Retains only the elements specified by the predicate, passing a
+    /// mutable reference to it.
+    ///
+    /// In other words, remove all elements `e` for which `f(&mut e)` returns
+    /// `false`. The elements are visited in unsorted (and unspecified) order.
+    /// Unlike [`retain`], the predicate can change the retained elements,
+    /// e.g. to decay their priorities.
+    ///
+    /// [`retain`]: DaryHeap::retain
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::QuaternaryHeap;
+    ///
+    /// let mut heap = QuaternaryHeap::from([10, 3, 7, 1, 5]);
+    ///
+    /// // Halve all priorities, and drop the items that reach zero.
+    /// heap.retain_mut(|x| {
+    ///     *x /= 2;
+    ///     *x > 0
+    /// });
+    ///
+    /// assert_eq!(heap.into_sorted_vec(), [1, 2, 3, 5])
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// As any retained element may have changed, the heap is always rebuilt
+    /// afterwards, which costs *O*(*n*) on a heap containing *n* elements.
+    #[cfg(feature = "extra")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "extra")))]
+    pub fn retain_mut<F>(&mut self, f: F)
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        let guard = RebuildOnDrop {
+            rebuild_from: 0,
+            heap: self,
+        };
+        guard.heap.data.retain_mut(f);
+    }
+
+    /// Returns an iterator which removes the items for which the predicate
+    /// returns `true` and yields them.
+    ///
+    /// The items are visited in unsorted (and unspecified) order. Unlike
+    /// [`retain`], the removed items are yielded instead of dropped. If the
+    /// iterator is dropped before being fully consumed, the remaining items
+    /// are kept, and the *d*-ary heap is restored when the iterator is
+    /// dropped.
+    ///
+    /// Note: If the `ExtractIf` value is leaked, the heap may be left in an
+    /// invalid state; its behavior is then unspecified, but safe.
+    ///
+    /// [`retain`]: DaryHeap::retain
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::OctonaryHeap;
+    ///
+    /// let mut heap = OctonaryHeap::from([-10, -5, 1, 2, 4, 13]);
+    ///
+    /// let mut evens: Vec<_> = heap.extract_if(|x| x % 2 == 0).collect();
+    /// evens.sort_unstable();
+    ///
+    /// assert_eq!(evens, [-10, 2, 4]);
+    /// assert_eq!(heap.into_sorted_vec(), [-5, 1, 13]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Iterating over all items costs *O*(*n*) plus the cost of the predicate
+    /// calls, and restoring the heap costs *O*(*n*) in the worst case.
+    pub fn extract_if<F>(&mut self, pred: F) -> ExtractIf<'_, T, F, D>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let len = self.len();
+        ExtractIf {
+            guard: RebuildOnDrop {
+                heap: self,
+                rebuild_from: len,
+            },
+            pos: 0,
+            pred,
+        }
+    }
+
+    /// Calls a closure with a mutable reference to every item, and rebuilds
+    /// the *d*-ary heap afterwards.
+    ///
+    /// The items are visited in unsorted (and unspecified) order. The heap is
+    /// rebuilt even if the closure panics.
+    ///
+    /// This is a closure-based alternative to a mutable iterator: an iterator
+    /// yielding `&mut T` cannot restore the heap when it is dropped, because
+    /// the references it yielded may outlive it. For iterator-style access, use
+    /// `as_vec_mut().iter_mut()` instead, which rebuilds when the guard
+    /// returned by [`as_vec_mut`] is dropped.
+    ///
+    /// [`as_vec_mut`]: DaryHeap::as_vec_mut
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::QuaternaryHeap;
+    ///
+    /// let mut heap = QuaternaryHeap::from([(3, 'a'), (1, 'b'), (2, 'c')]);
+    ///
+    /// heap.for_each_mut(|(score, name)| {
+    ///     if *name != 'a' {
+    ///         *score *= 10;
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(heap.pop(), Some((20, 'c')));
+    /// assert_eq!(heap.pop(), Some((10, 'b')));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The cost of `for_each_mut` is *O*(*n*) plus the cost of the closure
+    /// calls.
+    pub fn for_each_mut<F>(&mut self, f: F)
+    where
+        F: FnMut(&mut T),
+    {
+        let guard = RebuildOnDrop {
+            rebuild_from: 0,
+            heap: self,
+        };
+        guard.heap.data.iter_mut().for_each(f);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, const D: usize> DaryHeap<T, D> {
+    /// Returns an iterator visiting all values in the underlying vector, in
+    /// arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::TernaryHeap;
+    /// let heap = TernaryHeap::from([1, 2, 3, 4]);
+    ///
+    /// // Print 1, 2, 3, 4 in arbitrary order
+    /// for x in heap.iter() {
+    ///     println!("{x}");
+    /// }
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            iter: self.data.iter(),
+        }
+    }
+
+    /// Returns an iterator visiting all values in the *d*-ary heap level by
+    /// level, starting at the root.
+    ///
+    /// Unlike [`iter`], the order is guaranteed: every element is yielded
+    /// before any of its children. Pushing the yielded elements one by one
+    /// onto an empty heap of the same arity therefore never moves an element
+    /// upwards, and the elements form a valid heap for the same arity when
+    /// collected into a vector in the same order. This does not hold for a
+    /// heap of a different arity, where elements have different parents.
+    ///
+    /// [`iter`]: DaryHeap::iter
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::TernaryHeap;
+    /// let heap = TernaryHeap::from([1, 2, 3, 4, 5, 6]);
+    ///
+    /// let levels: Vec<_> = heap.iter_top_down().collect();
+    /// assert_eq!(levels[0], &6);
+    /// for (i, x) in levels.iter().enumerate().skip(1) {
+    ///     assert!(levels[(i - 1) / 3] >= *x);
+    /// }
+    /// ```
+    pub fn iter_top_down(&self) -> Iter<'_, T> {
+        // The heap is stored in level order, so the underlying vector already
+        // yields parents before their children.
+        self.iter()
+    }
+
+    /// Returns an iterator which retrieves elements in heap order.
+    ///
+    /// This method consumes the original heap.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::QuaternaryHeap;
+    /// let heap = QuaternaryHeap::from([1, 2, 3, 4, 5]);
+    ///
+    /// assert_eq!(heap.into_iter_sorted().take(2).collect::<Vec<_>>(), [5, 4]);
+    /// ```
+    pub fn into_iter_sorted(self) -> IntoIterSorted<T, D> {
+        IntoIterSorted { inner: self }
+    }
+
+    /// Returns the greatest item in the *d*-ary heap, or `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::BinaryHeap;
+    /// let mut heap = BinaryHeap::new();
+    /// assert_eq!(heap.peek(), None);
+    ///
+    /// heap.push(1);
+    /// heap.push(5);
+    /// heap.push(2);
+    /// assert_eq!(heap.peek(), Some(&5));
+    ///
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Cost is *O*(1) in the worst case.
+    #[must_use]
+    pub fn peek(&self) -> Option<&T> {
+        // Ignore this lint to keep it identical with upstream
+        #[allow(clippy::get_first)]
+        self.data.get(0)
+    }
+
+    /// Returns the number of elements the *d*-ary heap can hold without reallocating.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::OctonaryHeap;
+    /// let mut heap = OctonaryHeap::with_capacity(100);
+    /// assert!(heap.capacity() >= 100);
+    /// heap.push(4);
+    /// ```
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.data.capacity()
+    }
+
+    /// Reserves the minimum capacity for at least `additional` elements more than
+    /// the current length. Unlike [`reserve`], this will not
+    /// deliberately over-allocate to speculatively avoid frequent allocations.
+    /// After calling `reserve_exact`, capacity will be greater than or equal to
+    /// `self.len() + additional`. Does nothing if the capacity is already
+    /// sufficient.
+    ///
+    /// [`reserve`]: DaryHeap::reserve
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity overflows [`usize`].
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::OctonaryHeap;
+    /// let mut heap = OctonaryHeap::new();
+    /// heap.reserve_exact(100);
+    /// assert!(heap.capacity() >= 100);
+    /// heap.push(4);
+    /// ```
+    ///
+    /// [`reserve`]: DaryHeap::reserve
+    pub fn reserve_exact(&mut self, additional: usize) {
+        self.data.reserve_exact(additional);
+    }
+
+    /// Reserves capacity for at least `additional` elements more than the
+    /// current length. The allocator may reserve more space to speculatively
+    /// avoid frequent allocations. After calling `reserve`,
+    /// capacity will be greater than or equal to `self.len() + additional`.
+    /// Does nothing if capacity is already sufficient.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity overflows [`usize`].
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::BinaryHeap;
+    /// let mut heap = BinaryHeap::new();
+    /// heap.reserve(100);
+    /// assert!(heap.capacity() >= 100);
+    /// heap.push(4);
+    /// ```
+    pub fn reserve(&mut self, additional: usize) {
+        self.data.reserve(additional);
+    }
+
+    /// Tries to reserve the minimum capacity for at least `additional` elements
+    /// more than the current length. Unlike [`try_reserve`], this will not
+    /// deliberately over-allocate to speculatively avoid frequent allocations.
+    /// After calling `try_reserve_exact`, capacity will be greater than or
+    /// equal to `self.len() + additional` if it returns `Ok(())`.
+    /// Does nothing if the capacity is already sufficient.
+    ///
+    /// Note that the allocator may give the collection more space than it
+    /// requests. Therefore, capacity can not be relied upon to be precisely
+    /// minimal. Prefer [`try_reserve`] if future insertions are expected.
+    ///
+    /// [`try_reserve`]: DaryHeap::try_reserve
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dary_heap::BinaryHeap;
+    /// use std::collections::TryReserveError;
+    ///
+    /// fn find_max_slow(data: &[u32]) -> Result<Option<u32>, TryReserveError> {
+    ///     let mut heap = BinaryHeap::new();
+    ///
+    ///     // Pre-reserve the memory, exiting if we can't
+    ///     heap.try_reserve_exact(data.len())?;
+    ///
+    ///     // Now we know this can't OOM in the middle of our complex work
+    ///     heap.extend(data.iter());
+    ///
+    ///     Ok(heap.pop())
+    /// }
+    /// # find_max_slow(&[1, 2, 3]).expect("why is the test harness OOMing on 12 bytes?");
+    /// ```
+    #[cfg(feature = "extra")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "extra")))]
+    pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.data.try_reserve_exact(additional)
+    }
+
+    /// Tries to reserve capacity for at least `additional` elements more than the
+    /// current length. The allocator may reserve more space to speculatively
+    /// avoid frequent allocations. After calling `try_reserve`, capacity will be
+    /// greater than or equal to `self.len() + additional` if it returns
+    /// `Ok(())`. Does nothing if capacity is already sufficient. This method
+    /// preserves the contents even if an error occurs.
+    ///
+    /// # Errors
+    ///
+    /// If the capacity overflows, or the allocator reports a failure, then an error
+    /// is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dary_heap::QuaternaryHeap;
+    /// use std::collections::TryReserveError;
+    ///
+    /// fn find_max_slow(data: &[u32]) -> Result<Option<u32>, TryReserveError> {
+    ///     let mut heap = QuaternaryHeap::new();
+    ///
+    ///     // Pre-reserve the memory, exiting if we can't
+    ///     heap.try_reserve(data.len())?;
+    ///
+    ///     // Now we know this can't OOM in the middle of our complex work
+    ///     heap.extend(data.iter());
+    ///
+    ///     Ok(heap.pop())
+    /// }
+    /// # find_max_slow(&[1, 2, 3]).expect("why is the test harness OOMing on 12 bytes?");
+    /// ```
+    #[cfg(feature = "extra")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "extra")))]
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.data.try_reserve(additional)
+    }
+
+    /// Discards as much additional capacity as possible.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::TernaryHeap;
+    /// let mut heap: TernaryHeap<i32> = TernaryHeap::with_capacity(100);
+    ///
+    /// assert!(heap.capacity() >= 100);
+    /// heap.shrink_to_fit();
+    /// assert!(heap.capacity() == 0);
+    /// ```
+    pub fn shrink_to_fit(&mut self) {
+        self.data.shrink_to_fit();
+    }
+
+    /// Discards capacity with a lower bound.
+    ///
+    /// The capacity will remain at least as large as both the length
+    /// and the supplied value.
+    ///
+    /// If the current capacity is less than the lower limit, this is a no-op.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dary_heap::TernaryHeap;
+    /// let mut heap: TernaryHeap<i32> = TernaryHeap::with_capacity(100);
+    ///
+    /// assert!(heap.capacity() >= 100);
+    /// heap.shrink_to(10);
+    /// assert!(heap.capacity() >= 10);
+    /// ```
+    #[inline]
+    #[cfg(feature = "extra")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "extra")))]
+    pub fn shrink_to(&mut self, min_capacity: usize) {
+        self.data.shrink_to(min_capacity)
+    }
+
+    /// Returns a slice of all values in the underlying vector, in arbitrary
+    /// order.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::OctonaryHeap;
+    /// use std::io::{self, Write};
+    ///
+    /// let heap = OctonaryHeap::from([1, 2, 3, 4, 5, 6, 7]);
+    ///
+    /// io::sink().write(heap.as_slice()).unwrap();
+    /// ```
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        self.data.as_slice()
+    }
+
+    /// Returns a mutable slice of all values in the underlying vector, without
+    /// restoring the heap property afterwards.
+    ///
+    /// Unlike [`as_vec_mut`], which rebuilds the whole heap, this leaves it to
+    /// the caller to restore the heap property, e.g. with [`sift_up_at`] and
+    /// [`sift_down_at`] for the changed positions.
+    ///
+    /// [`as_vec_mut`]: DaryHeap::as_vec_mut
+    /// [`sift_up_at`]: DaryHeap::sift_up_at
+    /// [`sift_down_at`]: DaryHeap::sift_down_at
+    ///
+    /// # Safety
+    ///
+    /// The caller must restore the heap property before the *d*-ary heap is
+    /// used again, as described for [`from_vec_unchecked`]. Violating it does
+    /// not cause undefined behavior, but the results of the methods of
+    /// `DaryHeap` are then unspecified.
+    ///
+    /// [`from_vec_unchecked`]: DaryHeap::from_vec_unchecked
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::BinaryHeap;
+    /// let mut heap = BinaryHeap::from([(5, 'a'), (3, 'b'), (4, 'c')]);
+    ///
+    /// // SAFETY: Changing the names does not affect the order.
+    /// for (_, name) in unsafe { heap.as_mut_slice_unchecked() } {
+    ///     name.make_ascii_uppercase();
+    /// }
+    /// assert_eq!(heap.peek(), Some(&(5, 'A')));
+    /// ```
+    pub unsafe fn as_mut_slice_unchecked(&mut self) -> &mut [T] {
+        self.data.as_mut_slice()
+    }
+
+    /// Consumes the `DaryHeap` and returns the underlying vector
+    /// in arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::QuaternaryHeap;
+    /// let heap = QuaternaryHeap::from([1, 2, 3, 4, 5, 6, 7]);
+    /// let vec = heap.into_vec();
+    ///
+    /// // Will print in some order
+    /// for x in vec {
+    ///     println!("{x}");
+    /// }
+    /// ```
+    #[must_use = "`self` will be dropped if the result is not used"]
+    pub fn into_vec(self) -> Vec<T> {
+        self.into()
+    }
+
+    /// Decomposes the `DaryHeap` into the raw components of its underlying
+    /// vector: a pointer to its buffer, its length and its capacity.
+    ///
+    /// The items are in arbitrary order, as with [`into_vec`]. After calling
+    /// this function, the caller is responsible for the memory previously
+    /// managed by the heap. The only way to do this is to convert the parts
+    /// back with [`Vec::from_raw_parts`] or [`from_raw_parts`].
+    ///
+    /// [`into_vec`]: DaryHeap::into_vec
+    /// [`from_raw_parts`]: DaryHeap::from_raw_parts
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::TernaryHeap;
+    /// let heap = TernaryHeap::from([1, 2, 3]);
+    /// let (ptr, len, capacity) = heap.into_raw_parts();
+    ///
+    /// // SAFETY: The parts were produced by `into_raw_parts`.
+    /// let mut vec = unsafe { Vec::from_raw_parts(ptr, len, capacity) };
+    /// vec.sort_unstable();
+    /// assert_eq!(vec, [1, 2, 3]);
+    /// ```
+    #[must_use = "losing the pointer will leak memory"]
+    pub fn into_raw_parts(self) -> (*mut T, usize, usize) {
+        let mut vec = core::mem::ManuallyDrop::new(self.data);
+        (vec.as_mut_ptr(), vec.len(), vec.capacity())
+    }
+
+    /// Returns the length of the *d*-ary heap.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::BinaryHeap;
+    /// let heap = BinaryHeap::from([1, 3]);
+    ///
+    /// assert_eq!(heap.len(), 2);
+    /// ```
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Checks if the *d*-ary heap is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::BinaryHeap;
+    /// let mut heap = BinaryHeap::new();
+    ///
+    /// assert!(heap.is_empty());
+    ///
+    /// heap.push(3);
+    /// heap.push(5);
+    /// heap.push(1);
+    ///
+    /// assert!(!heap.is_empty());
+    /// ```
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of levels of the *d*-ary heap, which is
+    /// ⌈log<sub>*d*</sub>(*n*(*d* − 1) + 1)⌉ for a heap with *n* elements.
+    ///
+    /// An item that is sifted up or down passes through fewer levels than
+    /// this, which bounds the number of moves of a single push or pop.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::TernaryHeap;
+    /// let mut heap = TernaryHeap::new();
+    /// assert_eq!(heap.depth(), 0);
+    ///
+    /// heap.push(1);
+    /// assert_eq!(heap.depth(), 1);
+    ///
+    /// heap.extend(2..=4);
+    /// assert_eq!(heap.depth(), 2);
+    ///
+    /// heap.push(5);
+    /// assert_eq!(heap.depth(), 3);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The cost of `depth` is *O*(log(*n*)).
+    #[must_use]
+    pub fn depth(&self) -> usize {
+        if D < 2 {
+            // Every level of a unary heap contains a single item.
+            return self.len();
+        }
+        let mut remaining = self.len();
+        let mut width = 1usize;
+        let mut depth = 0;
+        while remaining > 0 {
+            remaining = remaining.saturating_sub(width);
+            width = width.saturating_mul(D);
+            depth += 1;
+        }
+        depth
+    }
+
+    /// Returns an iterator over the levels of the *d*-ary heap, from the root
+    /// down. Every item is a slice of the underlying vector containing the
+    /// items of one level, of which the children are on the next level.
+    ///
+    /// All levels contain *d* times as many items as the previous level,
+    /// except for the last one, which can contain fewer.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::BinaryHeap;
+    /// let heap = BinaryHeap::from_sorted_desc(vec![7, 6, 5, 4, 3, 2]);
+    ///
+    /// let mut layers = heap.layers();
+    /// assert_eq!(layers.next(), Some(&[7][..]));
+    /// assert_eq!(layers.next(), Some(&[6, 5][..]));
+    /// assert_eq!(layers.next(), Some(&[4, 3, 2][..]));
+    /// assert_eq!(layers.next(), None);
+    /// ```
+    pub fn layers(&self) -> Layers<'_, T, D> {
+        Layers {
+            rest: &self.data,
+            width: 1,
+        }
+    }
+
+    /// Clears the *d*-ary heap, returning an iterator over the removed elements
+    /// in arbitrary order. If the iterator is dropped before being fully
+    /// consumed, it drops the remaining elements in arbitrary order.
+    ///
+    /// The returned iterator keeps a mutable borrow on the heap to optimize
+    /// its implementation.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::QuaternaryHeap;
+    /// let mut heap = QuaternaryHeap::from([1, 3]);
+    ///
+    /// assert!(!heap.is_empty());
+    ///
+    /// for x in heap.drain() {
+    ///     println!("{x}");
+    /// }
+    ///
+    /// assert!(heap.is_empty());
+    /// ```
+    #[inline]
+    pub fn drain(&mut self) -> Drain<'_, T> {
+        Drain {
+            iter: self.data.drain(..),
+        }
+    }
+
+    /// Drops all items from the *d*-ary heap.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::TernaryHeap;
+    /// let mut heap = TernaryHeap::from([1, 3]);
+    ///
+    /// assert!(!heap.is_empty());
+    ///
+    /// heap.clear();
+    ///
+    /// assert!(heap.is_empty());
+    /// ```
+    pub fn clear(&mut self) {
+        self.drain();
+    }
+}
+
+/// An iterator over the elements of a `DaryHeap`.
+///
+/// This `struct` is created by [`DaryHeap::iter()`]. See its
+/// documentation for more.
+///
+/// [`iter`]: DaryHeap::iter
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Iter<'a, T: 'a> {
+    iter: core::slice::Iter<'a, T>,
+}
+
+impl<T> Default for Iter<'_, T> {
+    /// Creates an empty `dary_heap::Iter`.
+    ///
+    /// ```
+    /// let iter: dary_heap::Iter<'_, u8> = Default::default();
+    /// assert_eq!(iter.len(), 0);
+    /// ```
+    fn default() -> Self {
+        // `Default::default()` requires Rust 1.70.0 or later
+        Iter { iter: [].iter() }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Iter<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Iter").field(&self.iter.as_slice()).finish()
+    }
+}
+
+// FIXME(#26925) Remove in favor of `#[derive(Clone)]`
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Iter {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        self.iter.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    #[inline]
+    fn last(self) -> Option<&'a T> {
+        self.iter.last()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a T> {
+        self.iter.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {
+    #[cfg(feature = "unstable_nightly")]
+    fn is_empty(&self) -> bool {
+        self.iter.is_empty()
+    }
+}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+/// An iterator over the levels of a `DaryHeap`.
+///
+/// This `struct` is created by [`DaryHeap::layers()`]. See its
+/// documentation for more.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Layers<'a, T: 'a, const D: usize> {
+    rest: &'a [T],
+    width: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl<T: fmt::Debug, const D: usize> fmt::Debug for Layers<'_, T, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+// FIXME(#26925) Remove in favor of `#[derive(Clone)]`
+#[cfg(feature = "alloc")]
+impl<T, const D: usize> Clone for Layers<'_, T, D> {
+    fn clone(&self) -> Self {
+        Layers {
+            rest: self.rest,
+            width: self.width,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T, const D: usize> Iterator for Layers<'a, T, D> {
+    type Item = &'a [T];
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a [T]> {
+        if self.rest.is_empty() {
+            return None;
+        }
+        let (layer, rest) = self.rest.split_at(self.width.min(self.rest.len()));
+        self.rest = rest;
+        self.width = self.width.saturating_mul(D).max(1);
+        Some(layer)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let mut rest = self.rest.len();
+        let mut width = self.width;
+        let mut exact = 0;
+        while rest > 0 {
+            rest = rest.saturating_sub(width);
+            width = width.saturating_mul(D).max(1);
+            exact += 1;
+        }
+        (exact, Some(exact))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, const D: usize> ExactSizeIterator for Layers<'_, T, D> {}
+
+#[cfg(feature = "alloc")]
+impl<T, const D: usize> FusedIterator for Layers<'_, T, D> {}
+
+/// An owning iterator over the elements of a `DaryHeap`.
+///
+/// This `struct` is created by [`DaryHeap::into_iter()`]
+/// (provided by the [`IntoIterator`] trait). See its documentation for more.
+///
+/// [`into_iter`]: DaryHeap::into_iter
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Clone)]
+pub struct IntoIter<T> {
+    iter: vec::IntoIter<T>,
+}
+
+#[cfg(feature = "alloc")]
+impl<T: fmt::Debug> fmt::Debug for IntoIter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IntoIter")
+            .field(&self.iter.as_slice())
+            .finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.iter.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        self.iter.next_back()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> ExactSizeIterator for IntoIter<T> {
+    #[cfg(feature = "unstable_nightly")]
+    fn is_empty(&self) -> bool {
+        self.iter.is_empty()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> FusedIterator for IntoIter<T> {}
+
+#[cfg(all(feature = "alloc", feature = "unstable_nightly"))]
+#[doc(hidden)]
+unsafe impl<T> core::iter::TrustedFused for IntoIter<T> {}
+
+#[cfg(feature = "alloc")]
+impl<T> Default for IntoIter<T> {
+    /// Creates an empty `dary_heap::IntoIter`.
+    ///
+    /// ```
+    /// let iter: dary_heap::IntoIter<u8> = Default::default();
+    /// assert_eq!(iter.len(), 0);
+    /// ```
+    fn default() -> Self {
+        IntoIter {
+            iter: Vec::new().into_iter(),
+        }
+    }
+}
+
+// In addition to the SAFETY invariants of the following two unsafe traits
+// also refer to the vec::in_place_collect module documentation to get an overview
+#[cfg(all(feature = "alloc", feature = "unstable_nightly"))]
+#[doc(hidden)]
+unsafe impl<T> core::iter::SourceIter for IntoIter<T> {
+    type Source = IntoIter<T>;
+
+    #[inline]
+    unsafe fn as_inner(&mut self) -> &mut Self::Source {
+        self
+    }
+}
+
+#[cfg(all(feature = "alloc", feature = "unstable_nightly"))]
+#[doc(hidden)]
+unsafe impl<I> core::iter::InPlaceIterable for IntoIter<I> {
+    const EXPAND_BY: Option<NonZeroUsize> = NonZeroUsize::new(1);
+    const MERGE_BY: Option<NonZeroUsize> = NonZeroUsize::new(1);
+}
+
+/// An owning iterator over the elements of a `DaryHeap` in heap order.
+///
+/// This `struct` is created by [`DaryHeap::into_iter_sorted()`]. See its
+/// documentation for more.
+///
+/// [`into_iter_sorted`]: DaryHeap::into_iter_sorted
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[derive(Clone, Debug)]
+pub struct IntoIterSorted<T, const D: usize> {
+    inner: DaryHeap<T, D>,
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord, const D: usize> Iterator for IntoIterSorted<T, D> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.inner.pop()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let exact = self.inner.len();
+        (exact, Some(exact))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord, const D: usize> ExactSizeIterator for IntoIterSorted<T, D> {}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord, const D: usize> FusedIterator for IntoIterSorted<T, D> {}
+
+#[cfg(all(feature = "alloc", feature = "unstable_nightly"))]
+unsafe impl<T: Ord, const D: usize> core::iter::TrustedLen for IntoIterSorted<T, D> {}
+
+/// A draining iterator over the elements of a `DaryHeap`.
+///
+/// This `struct` is created by [`DaryHeap::drain()`]. See its
+/// documentation for more.
+///
+/// [`drain`]: DaryHeap::drain
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Debug)]
+pub struct Drain<'a, T: 'a> {
+    iter: vec::Drain<'a, T>,
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Iterator for Drain<'_, T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.iter.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> DoubleEndedIterator for Drain<'_, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        self.iter.next_back()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> ExactSizeIterator for Drain<'_, T> {
+    #[cfg(feature = "unstable_nightly")]
+    fn is_empty(&self) -> bool {
+        self.iter.is_empty()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> FusedIterator for Drain<'_, T> {}
+
+/// A draining iterator over the elements of a `DaryHeap`.
+///
+/// This `struct` is created by [`DaryHeap::drain_sorted()`]. See its
+/// documentation for more.
+///
+/// [`drain_sorted`]: DaryHeap::drain_sorted
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Debug)]
+pub struct DrainSorted<'a, T: Ord, const D: usize> {
+    inner: &'a mut DaryHeap<T, D>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T: Ord, const D: usize> Drop for DrainSorted<'a, T, D> {
+    /// Removes heap elements in heap order.
+    fn drop(&mut self) {
+        use core::mem::forget;
+
+        struct DropGuard<'r, 'a, T: Ord, const D: usize>(&'r mut DrainSorted<'a, T, D>);
+
+        impl<'r, 'a, T: Ord, const D: usize> Drop for DropGuard<'r, 'a, T, D> {
+            fn drop(&mut self) {
+                while self.0.inner.pop().is_some() {}
+            }
+        }
+
+        while let Some(item) = self.inner.pop() {
+            let guard = DropGuard(self);
+            drop(item);
+            forget(guard);
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord, const D: usize> Iterator for DrainSorted<'_, T, D> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.inner.pop()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let exact = self.inner.len();
+        (exact, Some(exact))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord, const D: usize> ExactSizeIterator for DrainSorted<'_, T, D> {}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord, const D: usize> FusedIterator for DrainSorted<'_, T, D> {}
+
+#[cfg(all(feature = "alloc", feature = "unstable_nightly"))]
+unsafe impl<T: Ord, const D: usize> core::iter::TrustedLen for DrainSorted<'_, T, D> {}
+
+/// An iterator which removes the elements of a `DaryHeap` that match a
+/// predicate.
+///
+/// This `struct` is created by [`DaryHeap::extract_if()`]. See its
+/// documentation for more.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub struct ExtractIf<'a, T: Ord, F, const D: usize> {
+    guard: RebuildOnDrop<'a, T, D>,
+    pos: usize,
+    pred: F,
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord + fmt::Debug, F, const D: usize> fmt::Debug for ExtractIf<'_, T, F, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ExtractIf")
+            .field(&&self.guard.heap.data[self.pos..])
+            .finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord, F: FnMut(&T) -> bool, const D: usize> Iterator for ExtractIf<'_, T, F, D> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let data = &mut self.guard.heap.data;
+        while self.pos < data.len() {
+            if (self.pred)(&data[self.pos]) {
+                // Only the items from `pos` onwards are moved, so everything
+                // before it remains a valid heap.
+                self.guard.rebuild_from = self.guard.rebuild_from.min(self.pos);
+                return Some(data.swap_remove(self.pos));
+            }
+            self.pos += 1;
+        }
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.guard.heap.len() - self.pos))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord, F: FnMut(&T) -> bool, const D: usize> FusedIterator for ExtractIf<'_, T, F, D> {}
+
+/// An iterator which removes the greatest elements of a `DaryHeap` while they
+/// match a predicate.
+///
+/// This `struct` is created by [`DaryHeap::pop_while()`]. See its
+/// documentation for more.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+pub struct PopWhile<'a, T: Ord, F, const D: usize> {
+    inner: &'a mut DaryHeap<T, D>,
+    // Set to None once the predicate returns false, to fuse the iterator.
+    pred: Option<F>,
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord + fmt::Debug, F, const D: usize> fmt::Debug for PopWhile<'_, T, F, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PopWhile").field(&self.inner).finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord, F: FnMut(&T) -> bool, const D: usize> Iterator for PopWhile<'_, T, F, D> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        let pred = self.pred.as_mut()?;
+        let item = self.inner.pop_if(pred);
+        if item.is_none() {
+            self.pred = None;
+        }
+        item
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.pred {
+            Some(_) => (0, Some(self.inner.len())),
+            None => (0, Some(0)),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord, F: FnMut(&T) -> bool, const D: usize> FusedIterator for PopWhile<'_, T, F, D> {}
+
+/// A draining iterator over the elements of a `DaryHeap` that are greater
+/// than or equal to a threshold.
+///
+/// This `struct` is created by [`DaryHeap::drain_above()`]. See its
+/// documentation for more.
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
+#[derive(Debug)]
+pub struct DrainAbove<'a, T: Ord, const D: usize> {
+    inner: &'a mut DaryHeap<T, D>,
+    threshold: T,
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord, const D: usize> Drop for DrainAbove<'_, T, D> {
+    /// Removes the remaining matching elements in heap order.
+    fn drop(&mut self) {
+        self.for_each(drop);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord, const D: usize> Iterator for DrainAbove<'_, T, D> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        let threshold = &self.threshold;
+        self.inner.pop_if(|item| item >= threshold)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.inner.len()))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord, const D: usize> FusedIterator for DrainAbove<'_, T, D> {}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord, const D: usize> From<Vec<T>> for DaryHeap<T, D> {
+    /// Converts a `Vec<T>` into a `DaryHeap<T, D>`.
+    ///
+    /// This conversion happens in-place, and has *O*(*n*) time complexity.
+    fn from(vec: Vec<T>) -> DaryHeap<T, D> {
+        let mut heap = DaryHeap {
+            data: vec,
+            #[cfg(feature = "stats")]
+            stats: HeapStats::new(),
+        };
+        heap.rebuild();
+        heap
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord, const D: usize, const N: usize> From<[T; N]> for DaryHeap<T, D> {
+    /// ```
+    /// use dary_heap::TernaryHeap;
+    ///
+    /// let mut h1 = TernaryHeap::from([1, 4, 2, 3]);
+    /// let mut h2: TernaryHeap<_> = [1, 4, 2, 3].into();
+    /// while let Some((a, b)) = h1.pop().zip(h2.pop()) {
+    ///     assert_eq!(a, b);
+    /// }
+    /// ```
+    fn from(arr: [T; N]) -> Self {
+        // With newer Rust versions `Self::from_iter(arr)` should be used, as
+        // using `IntoIter::new` is deprecated from 1.59.0. However, this would
+        // require a MSRV of 1.53.0, and both are equivalent behind the scenes.
+        #[allow(deprecated)]
+        core::array::IntoIter::new(arr).collect()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord, const D: usize> From<alloc::collections::BinaryHeap<T>> for DaryHeap<T, D> {
+    /// Converts a [`BinaryHeap<T>`] of the standard library into a
+    /// `DaryHeap<T, D>`.
+    ///
+    /// This conversion reuses the allocation of the standard library heap.
+    /// For *d* = 2 the heap layout is identical, so the conversion has
+    /// constant time complexity; other arities require a rebuild with *O*(*n*)
+    /// time complexity.
+    ///
+    /// [`BinaryHeap<T>`]: alloc::collections::BinaryHeap
+    ///
+    /// ```
+    /// use dary_heap::{BinaryHeap, QuaternaryHeap};
+    ///
+    /// let std_heap = std::collections::BinaryHeap::from(vec![1, 5, 2]);
+    /// let mut heap = BinaryHeap::from(std_heap.clone());
+    /// assert_eq!(heap.pop(), Some(5));
+    ///
+    /// let mut heap = QuaternaryHeap::from(std_heap);
+    /// assert_eq!(heap.pop(), Some(5));
+    /// ```
+    fn from(heap: alloc::collections::BinaryHeap<T>) -> DaryHeap<T, D> {
+        let data = heap.into_vec();
+        if D == 2 {
+            DaryHeap {
+                data,
+                #[cfg(feature = "stats")]
+                stats: HeapStats::new(),
+            }
+        } else {
+            DaryHeap::from(data)
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord, const D: usize> From<DaryHeap<T, D>> for alloc::collections::BinaryHeap<T> {
+    /// Converts a `DaryHeap<T, D>` into a [`BinaryHeap<T>`] of the standard
+    /// library.
+    ///
+    /// This conversion reuses the allocation of the *d*-ary heap. The standard
+    /// library always rebuilds the heap, which has *O*(*n*) time complexity;
+    /// for *d* = 2 the layout is already valid, so no items are moved.
+    ///
+    /// [`BinaryHeap<T>`]: alloc::collections::BinaryHeap
+    ///
+    /// ```
+    /// use dary_heap::TernaryHeap;
+    ///
+    /// let heap = TernaryHeap::from(vec![1, 5, 2]);
+    /// let mut std_heap = std::collections::BinaryHeap::from(heap);
+    /// assert_eq!(std_heap.pop(), Some(5));
+    /// ```
+    fn from(heap: DaryHeap<T, D>) -> alloc::collections::BinaryHeap<T> {
+        alloc::collections::BinaryHeap::from(heap.into_vec())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord, const D: usize> From<alloc::collections::BTreeSet<T>> for DaryHeap<T, D> {
+    /// Converts a [`BTreeSet<T>`] into a `DaryHeap<T, D>`.
+    ///
+    /// The items of the set are collected in descending order, which is
+    /// already a valid heap, so no rebuild is needed. This conversion has
+    /// *O*(*n*) time complexity.
+    ///
+    /// [`BTreeSet<T>`]: alloc::collections::BTreeSet
+    ///
+    /// ```
+    /// use dary_heap::TernaryHeap;
+    /// use std::collections::BTreeSet;
+    ///
+    /// let set: BTreeSet<_> = [3, 1, 4, 5].iter().copied().collect();
+    /// let mut heap = TernaryHeap::from(set);
+    /// assert_eq!(heap.pop(), Some(5));
+    /// ```
+    fn from(set: alloc::collections::BTreeSet<T>) -> DaryHeap<T, D> {
+        DaryHeap::from_sorted_desc(set.into_iter().rev().collect())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K: Ord, V: Ord, const D: usize> From<alloc::collections::BTreeMap<K, V>>
+    for DaryHeap<(K, V), D>
+{
+    /// Converts a [`BTreeMap<K, V>`] into a `DaryHeap<(K, V), D>` of its
+    /// entries.
+    ///
+    /// The keys of the map are unique, so its entries are collected in
+    /// descending order, which is already a valid heap, and no rebuild is
+    /// needed. This conversion has *O*(*n*) time complexity.
+    ///
+    /// [`BTreeMap<K, V>`]: alloc::collections::BTreeMap
+    ///
+    /// ```
+    /// use dary_heap::TernaryHeap;
+    /// use std::collections::BTreeMap;
+    ///
+    /// let map: BTreeMap<_, _> = [(3, 'c'), (1, 'a'), (5, 'e')].iter().copied().collect();
+    /// let mut heap = TernaryHeap::from(map);
+    /// assert_eq!(heap.pop(), Some((5, 'e')));
+    /// ```
+    fn from(map: alloc::collections::BTreeMap<K, V>) -> DaryHeap<(K, V), D> {
+        DaryHeap::from_sorted_desc(map.into_iter().rev().collect())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord, const D: usize> From<alloc::collections::VecDeque<T>> for DaryHeap<T, D> {
+    /// Converts a [`VecDeque<T>`] into a `DaryHeap<T, D>`.
+    ///
+    /// This conversion reuses the allocation of the deque, moving its items
+    /// to the start of the buffer if needed, and has *O*(*n*) time complexity.
+    ///
+    /// [`VecDeque<T>`]: alloc::collections::VecDeque
+    ///
+    /// ```
+    /// use dary_heap::TernaryHeap;
+    /// use std::collections::VecDeque;
+    ///
+    /// let deque: VecDeque<_> = [3, 1, 4, 5].iter().copied().collect();
+    /// let mut heap = TernaryHeap::from(deque);
+    /// assert_eq!(heap.pop(), Some(5));
+    /// ```
+    fn from(deque: alloc::collections::VecDeque<T>) -> DaryHeap<T, D> {
+        DaryHeap::from(Vec::from(deque))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, const D: usize> From<DaryHeap<T, D>> for Vec<T> {
+    /// Converts a `DaryHeap<T, D>` into a `Vec<T>`.
+    ///
+    /// This conversion requires no data movement or allocation, and has
+    /// constant time complexity.
+    fn from(heap: DaryHeap<T, D>) -> Vec<T> {
+        heap.data
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord, const D: usize> FromIterator<T> for DaryHeap<T, D> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> DaryHeap<T, D> {
+        DaryHeap::from(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord, const D: usize> core::iter::Sum for DaryHeap<T, D> {
+    /// Melds *d*-ary heaps into a single heap; see [`meld`] for details.
+    ///
+    /// [`meld`]: DaryHeap::meld
+    ///
+    /// ```
+    /// use dary_heap::QuaternaryHeap;
+    ///
+    /// let shards = vec![QuaternaryHeap::from(vec![1, 7]), QuaternaryHeap::from(vec![4])];
+    /// let heap: QuaternaryHeap<_> = shards.into_iter().sum();
+    /// assert_eq!(heap.into_sorted_vec(), [1, 4, 7]);
+    /// ```
+    fn sum<I: Iterator<Item = DaryHeap<T, D>>>(iter: I) -> DaryHeap<T, D> {
+        DaryHeap::meld(iter)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, const D: usize> IntoIterator for DaryHeap<T, D> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    /// Creates a consuming iterator, that is, one that moves each value out of
+    /// the *d*-ary heap in arbitrary order. The *d*-ary heap cannot be used
+    /// after calling this.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::BinaryHeap;
+    /// let heap = BinaryHeap::from([1, 2, 3, 4]);
+    ///
+    /// // Print 1, 2, 3, 4 in arbitrary order
+    /// for x in heap.into_iter() {
+    ///     // x has type i32, not &i32
+    ///     println!("{x}");
+    /// }
+    /// ```
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter {
+            iter: self.data.into_iter(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T, const D: usize> IntoIterator for &'a DaryHeap<T, D> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord, const D: usize> Extend<T> for DaryHeap<T, D> {
+    /// Extends the *d*-ary heap with the contents of an iterator.
+    ///
+    /// The items are appended to the underlying vector first, after which the
+    /// heap is either rebuilt or the new items are sifted up, whichever is
+    /// expected to be cheaper; this is the same strategy as [`append`]. When
+    /// extending from another `DaryHeap`, its items are moved into the buffer
+    /// in bulk.
+    ///
+    /// [`append`]: DaryHeap::append
     #[inline]
-    fn next_back(&mut self) -> Option<&'a T> {
-        self.iter.next_back()
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let guard = RebuildOnDrop {
+            rebuild_from: self.len(),
+            heap: self,
+        };
+        guard.heap.data.extend(iter);
+        let len = guard.heap.len();
+        if len > Self::MAX_LEN {
+            // Drop the new items, so that the guard leaves a valid heap behind.
+            guard.heap.data.truncate(guard.rebuild_from);
+            Self::check_len(len);
+        }
+    }
+
+    #[inline]
+    #[cfg(feature = "unstable_nightly")]
+    fn extend_one(&mut self, item: T) {
+        self.push(item);
+    }
+
+    #[inline]
+    #[cfg(feature = "unstable_nightly")]
+    fn extend_reserve(&mut self, additional: usize) {
+        self.reserve(additional);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T: 'a + Ord + Copy, const D: usize> Extend<&'a T> for DaryHeap<T, D> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().cloned());
+    }
+
+    #[inline]
+    #[cfg(feature = "unstable_nightly")]
+    fn extend_one(&mut self, &item: &'a T) {
+        self.push(item);
+    }
+
+    #[inline]
+    #[cfg(feature = "unstable_nightly")]
+    fn extend_reserve(&mut self, additional: usize) {
+        self.reserve(additional);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord, const D: usize> BitOr for DaryHeap<T, D> {
+    type Output = DaryHeap<T, D>;
+
+    /// Merges two *d*-ary heaps, reusing the allocation of the larger one.
+    ///
+    /// This is equivalent to [`append`].
+    ///
+    /// [`append`]: DaryHeap::append
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::QuaternaryHeap;
+    /// let a = QuaternaryHeap::from([1, 4]);
+    /// let b = QuaternaryHeap::from([2, 3, 5]);
+    ///
+    /// let merged = a | b;
+    /// assert_eq!(merged.into_sorted_vec(), [1, 2, 3, 4, 5]);
+    /// ```
+    fn bitor(mut self, mut rhs: DaryHeap<T, D>) -> DaryHeap<T, D> {
+        self.append(&mut rhs);
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord + Clone, const D: usize> BitOr<&DaryHeap<T, D>> for &DaryHeap<T, D> {
+    type Output = DaryHeap<T, D>;
+
+    /// Returns a new *d*-ary heap with clones of the items of both heaps.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::QuaternaryHeap;
+    /// let a = QuaternaryHeap::from([1, 4]);
+    /// let b = QuaternaryHeap::from([2, 3, 5]);
+    ///
+    /// let merged = &a | &b;
+    /// assert_eq!(merged.into_sorted_vec(), [1, 2, 3, 4, 5]);
+    /// assert_eq!(a.len(), 2);
+    /// ```
+    fn bitor(self, rhs: &DaryHeap<T, D>) -> DaryHeap<T, D> {
+        let (larger, smaller) = if self.len() < rhs.len() {
+            (rhs, self)
+        } else {
+            (self, rhs)
+        };
+        let mut merged = DaryHeap::with_capacity(larger.len() + smaller.len());
+        merged.data.extend_from_slice(&larger.data);
+        merged.extend(smaller.iter().cloned());
+        merged
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord, const D: usize> BitOrAssign for DaryHeap<T, D> {
+    /// Moves all items of `rhs` into the *d*-ary heap.
+    ///
+    /// This is equivalent to [`append`].
+    ///
+    /// [`append`]: DaryHeap::append
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::QuaternaryHeap;
+    /// let mut heap = QuaternaryHeap::from([1, 4]);
+    /// heap |= QuaternaryHeap::from([2, 3, 5]);
+    ///
+    /// assert_eq!(heap.into_sorted_vec(), [1, 2, 3, 4, 5]);
+    /// ```
+    fn bitor_assign(&mut self, mut rhs: DaryHeap<T, D>) {
+        self.append(&mut rhs);
+    }
+}
+
+/// Strategy to restore the heap property after removing the greatest item,
+/// used by [`DaryHeap::pop_with_strategy`].
+///
+/// Both strategies move the last item of the heap to the root, and then move
+/// it down until it is in order with its children.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PopStrategy {
+    /// Move the item down to the bottom along the path of the greatest
+    /// children, then move it up to its place. This needs fewer comparisons
+    /// when the item ends up near the bottom, which is usually the case as
+    /// the last item of a heap tends to be small. This is the strategy used
+    /// by [`DaryHeap::pop`].
+    ToBottom,
+    /// Move the item down while it is smaller than the greatest of its
+    /// children. This needs fewer comparisons when the item ends up near the
+    /// root, e.g. if many items of the heap are equal.
+    SiftDown,
+}
+
+// Deriving this (#[default]) requires Rust 1.62.0, but the MSRV is currently
+// 1.51.0.
+#[allow(clippy::derivable_impls)]
+impl Default for PopStrategy {
+    fn default() -> PopStrategy {
+        PopStrategy::ToBottom
+    }
+}
+
+/// The error type returned by [`DaryHeap::validate`] if an item is greater
+/// than its parent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct InvalidHeapError {
+    index: usize,
+    parent: usize,
+}
+
+impl InvalidHeapError {
+    /// Returns the position in the underlying vector of the item that is
+    /// greater than its parent.
+    #[must_use]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the position in the underlying vector of the parent of the
+    /// offending item.
+    #[must_use]
+    pub fn parent(&self) -> usize {
+        self.parent
+    }
+}
+
+impl fmt::Display for InvalidHeapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "item at index {} is greater than its parent at index {}",
+            self.index, self.parent
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for InvalidHeapError {}
+
+#[cfg(all(feature = "alloc", any(test, fuzzing)))]
+impl<T: Ord + fmt::Debug, const D: usize> DaryHeap<T, D> {
+    /// Panics if the heap is in an inconsistent state
+    #[track_caller]
+    pub fn assert_valid_state(&self) {
+        if let Err(err) = self.validate() {
+            panic!("{}: {:?}", err, self);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use rand::{seq::SliceRandom, thread_rng, Rng};
+
+    fn pop<const D: usize>() {
+        let mut rng = thread_rng();
+        let ntest = if cfg!(miri) { 1 } else { 10 };
+        let nelem = if cfg!(miri) { 100 } else { 1000 };
+        for _ in 0..ntest {
+            let mut data: Vec<_> = (0..nelem).collect();
+            data.shuffle(&mut rng);
+            let mut heap = DaryHeap::<_, D>::from(data);
+            heap.assert_valid_state();
+            for i in (0..nelem).rev() {
+                assert_eq!(heap.pop(), Some(i));
+                heap.assert_valid_state();
+            }
+            assert_eq!(heap.pop(), None);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn push_d0() {
+        let mut heap = DaryHeap::<_, 0>::new();
+        heap.push(42);
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_vec_d0() {
+        let _heap = DaryHeap::<_, 0>::from(vec![42]);
+    }
+
+    #[test]
+    fn pop_d1() {
+        pop::<1>();
+    }
+
+    #[test]
+    fn pop_d2() {
+        pop::<2>();
+    }
+
+    #[test]
+    fn pop_d3() {
+        pop::<3>();
     }
-}
 
-impl<T> ExactSizeIterator for Iter<'_, T> {
-    #[cfg(feature = "unstable_nightly")]
-    fn is_empty(&self) -> bool {
-        self.iter.is_empty()
+    #[test]
+    fn pop_d4() {
+        pop::<4>();
     }
-}
 
-impl<T> FusedIterator for Iter<'_, T> {}
+    #[test]
+    fn pop_d5() {
+        pop::<5>();
+    }
 
-/// An owning iterator over the elements of a `DaryHeap`.
-///
-/// This `struct` is created by [`DaryHeap::into_iter()`]
-/// (provided by the [`IntoIterator`] trait). See its documentation for more.
-///
-/// [`into_iter`]: DaryHeap::into_iter
-#[derive(Clone)]
-pub struct IntoIter<T> {
-    iter: vec::IntoIter<T>,
-}
+    #[test]
+    fn pop_d6() {
+        pop::<6>();
+    }
 
-impl<T: fmt::Debug> fmt::Debug for IntoIter<T> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_tuple("IntoIter")
-            .field(&self.iter.as_slice())
-            .finish()
+    #[test]
+    fn pop_d7() {
+        pop::<7>();
     }
-}
 
-impl<T> Iterator for IntoIter<T> {
-    type Item = T;
+    #[test]
+    fn pop_d8() {
+        pop::<8>();
+    }
 
-    #[inline]
-    fn next(&mut self) -> Option<T> {
-        self.iter.next()
+    #[test]
+    fn push_pop() {
+        let mut rng = thread_rng();
+        let nelem = if cfg!(miri) { 100 } else { 1000 };
+        let mut data: Vec<_> = (0..nelem).collect();
+        data.shuffle(&mut rng);
+        let (initial, pushed) = data.split_at(nelem / 2);
+        let mut heap = DaryHeap::<_, 3>::from(initial.to_vec());
+        let mut reference = alloc::collections::BinaryHeap::from(initial.to_vec());
+        for &x in pushed {
+            reference.push(x);
+            assert_eq!(Some(heap.push_pop(x)), reference.pop());
+            heap.assert_valid_state();
+        }
+        assert_eq!(heap.into_sorted_vec(), reference.into_sorted_vec());
     }
 
-    #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        self.iter.size_hint()
+    #[test]
+    fn replace_root() {
+        let mut rng = thread_rng();
+        let nelem = if cfg!(miri) { 100 } else { 1000 };
+        let mut data: Vec<_> = (0..nelem).collect();
+        data.shuffle(&mut rng);
+        let (initial, pushed) = data.split_at(nelem / 2);
+        let mut heap = DaryHeap::<_, 4>::from(initial.to_vec());
+        let mut reference = alloc::collections::BinaryHeap::from(initial.to_vec());
+        for &x in pushed {
+            assert_eq!(heap.replace_root(x), reference.pop());
+            reference.push(x);
+            heap.assert_valid_state();
+        }
+        assert_eq!(heap.into_sorted_vec(), reference.into_sorted_vec());
     }
-}
 
-impl<T> DoubleEndedIterator for IntoIter<T> {
-    #[inline]
-    fn next_back(&mut self) -> Option<T> {
-        self.iter.next_back()
+    #[test]
+    fn remove() {
+        let mut rng = thread_rng();
+        let nelem = if cfg!(miri) { 100 } else { 1000 };
+        let mut data: Vec<_> = (0..nelem).map(|x| x / 2).collect();
+        data.shuffle(&mut rng);
+        let mut heap = DaryHeap::<_, 5>::from(data.clone());
+        data.shuffle(&mut rng);
+        let (removed, kept) = data.split_at(nelem / 2);
+        for x in removed {
+            assert_eq!(heap.remove(x).as_ref(), Some(x));
+            heap.assert_valid_state();
+        }
+        assert_eq!(heap.remove(&nelem), None);
+        let mut kept = kept.to_vec();
+        kept.sort_unstable();
+        assert_eq!(heap.into_sorted_vec(), kept);
     }
-}
 
-impl<T> ExactSizeIterator for IntoIter<T> {
-    #[cfg(feature = "unstable_nightly")]
-    fn is_empty(&self) -> bool {
-        self.iter.is_empty()
+    #[test]
+    fn replace_item() {
+        let mut rng = thread_rng();
+        let nelem = if cfg!(miri) { 100 } else { 1000 };
+        let mut data: Vec<_> = (0..nelem).collect();
+        data.shuffle(&mut rng);
+        let mut heap = DaryHeap::<_, 2>::from(data.clone());
+        for x in &mut data {
+            let new = rng.gen_range(0..nelem);
+            assert_eq!(heap.replace_item(x, new), Ok(*x));
+            heap.assert_valid_state();
+            *x = new;
+        }
+        assert_eq!(heap.replace_item(&nelem, 0), Err(0));
+        data.sort_unstable();
+        assert_eq!(heap.into_sorted_vec(), data);
     }
-}
 
-impl<T> FusedIterator for IntoIter<T> {}
+    #[test]
+    fn as_vec_mut() {
+        let mut rng = thread_rng();
+        let nelem = if cfg!(miri) { 100 } else { 1000 };
+        let mut heap: DaryHeap<u16, 4> = (0..nelem).map(|_| rng.gen()).collect();
+        {
+            let mut vec = heap.as_vec_mut();
+            vec.retain(|&x| x % 3 != 0);
+            for x in vec.iter_mut() {
+                *x = x.wrapping_mul(7);
+            }
+            vec.push(42);
+        }
+        heap.assert_valid_state();
+    }
 
-#[cfg(feature = "unstable_nightly")]
-#[doc(hidden)]
-unsafe impl<T> core::iter::TrustedFused for IntoIter<T> {}
+    #[test]
+    fn for_each_mut() {
+        let mut rng = thread_rng();
+        let nelem = if cfg!(miri) { 100 } else { 1000 };
+        let mut heap: DaryHeap<u16, 3> = (0..nelem).map(|_| rng.gen()).collect();
+        let offset: u16 = rng.gen();
+        heap.for_each_mut(|x| *x = x.wrapping_add(offset));
+        heap.assert_valid_state();
+    }
 
-impl<T> Default for IntoIter<T> {
-    /// Creates an empty `dary_heap::IntoIter`.
-    ///
-    /// ```
-    /// let iter: dary_heap::IntoIter<u8> = Default::default();
-    /// assert_eq!(iter.len(), 0);
-    /// ```
-    fn default() -> Self {
-        IntoIter {
-            iter: Vec::new().into_iter(),
+    #[test]
+    fn split_off() {
+        let mut data: Vec<_> = (0..100).collect();
+        data.shuffle(&mut thread_rng());
+        for &at in &[0, 1, 37, 99, 100] {
+            let mut a = DaryHeap::<_, 4>::from(data.clone());
+            let b = a.split_off(at);
+            assert_eq!((a.len(), b.len()), (at, 100 - at));
+            a.assert_valid_state();
+            b.assert_valid_state();
+            let mut all = a.into_vec();
+            all.extend(b.into_vec());
+            all.sort_unstable();
+            assert_eq!(all, (0..100).collect::<Vec<_>>());
         }
     }
-}
 
-// In addition to the SAFETY invariants of the following two unsafe traits
-// also refer to the vec::in_place_collect module documentation to get an overview
-#[cfg(feature = "unstable_nightly")]
-#[doc(hidden)]
-unsafe impl<T> core::iter::SourceIter for IntoIter<T> {
-    type Source = IntoIter<T>;
+    #[test]
+    fn truncate_top_and_pop_n() {
+        let mut data: Vec<_> = (0..100).collect();
+        data.shuffle(&mut thread_rng());
+        for &k in &[0, 1, 37, 99, 100, 200] {
+            let mut heap = DaryHeap::<_, 3>::from(data.clone());
+            heap.truncate_top(k);
+            heap.assert_valid_state();
+            let n = k.min(100);
+            assert_eq!(heap.into_sorted_vec(), (100 - n..100).collect::<Vec<_>>());
 
-    #[inline]
-    unsafe fn as_inner(&mut self) -> &mut Self::Source {
-        self
+            let mut heap = DaryHeap::<_, 3>::from(data.clone());
+            let top = heap.pop_n(k);
+            heap.assert_valid_state();
+            assert_eq!(top, (100 - n..100).rev().collect::<Vec<_>>());
+            assert_eq!(heap.into_sorted_vec(), (0..100 - n).collect::<Vec<_>>());
+        }
     }
-}
 
-#[cfg(feature = "unstable_nightly")]
-#[doc(hidden)]
-unsafe impl<I> core::iter::InPlaceIterable for IntoIter<I> {
-    const EXPAND_BY: Option<NonZeroUsize> = NonZeroUsize::new(1);
-    const MERGE_BY: Option<NonZeroUsize> = NonZeroUsize::new(1);
-}
+    #[test]
+    fn peek_top_k() {
+        fn check<const D: usize>() {
+            let mut rng = thread_rng();
+            let data: Vec<u8> = (0..200).map(|_| rng.gen()).collect();
+            let mut sorted = data.clone();
+            sorted.sort_unstable_by(|a, b| b.cmp(a));
+            let heap = DaryHeap::<_, D>::from(data);
+            for &k in &[0, 1, 37, 200, 300] {
+                let top: Vec<_> = heap.peek_top_k(k).into_iter().copied().collect();
+                assert_eq!(top, sorted[..k.min(200)]);
+            }
+        }
+        check::<1>();
+        check::<2>();
+        check::<3>();
+        check::<4>();
+        check::<7>();
+    }
 
-#[must_use = "iterators are lazy and do nothing unless consumed"]
-#[cfg(feature = "unstable")]
-#[derive(Clone, Debug)]
-pub struct IntoIterSorted<T, const D: usize> {
-    inner: DaryHeap<T, D>,
-}
+    #[test]
+    fn drain_above() {
+        let mut data: Vec<_> = (0..100).collect();
+        data.shuffle(&mut thread_rng());
+        let mut heap = DaryHeap::<_, 3>::from(data);
+
+        let mut iter = heap.drain_above(50);
+        assert_eq!(iter.next(), Some(99));
+        assert_eq!(iter.next(), Some(98));
+        drop(iter);
+        heap.assert_valid_state();
+        assert_eq!(heap.peek(), Some(&49));
+
+        assert_eq!(heap.drain_above(100).next(), None);
+        let drained: Vec<_> = heap.drain_above(0).collect();
+        assert_eq!(drained, (0..50).rev().collect::<Vec<_>>());
+        assert!(heap.is_empty());
+    }
 
-#[cfg(feature = "unstable")]
-impl<T: Ord, const D: usize> Iterator for IntoIterSorted<T, D> {
-    type Item = T;
+    #[test]
+    fn extract_if() {
+        let mut data: Vec<_> = (0..100).collect();
+        data.shuffle(&mut thread_rng());
+        let mut heap = DaryHeap::<_, 4>::from(data);
+
+        let mut extracted: Vec<_> = heap.extract_if(|x| x % 3 == 0).collect();
+        extracted.sort_unstable();
+        heap.assert_valid_state();
+        assert_eq!(extracted, (0..100).step_by(3).collect::<Vec<_>>());
+        assert_eq!(heap.len(), 66);
+
+        let mut iter = heap.extract_if(|&x| x > 50);
+        assert!(iter.next().unwrap() > 50);
+        drop(iter);
+        heap.assert_valid_state();
+        assert_eq!(heap.len(), 65);
+    }
 
-    #[inline]
-    fn next(&mut self) -> Option<T> {
-        self.inner.pop()
+    #[test]
+    fn pop_while() {
+        let mut data: Vec<_> = (0..100).collect();
+        data.shuffle(&mut thread_rng());
+        let mut heap = DaryHeap::<_, 2>::from(data);
+
+        let popped: Vec<_> = heap.pop_while(|&x| x >= 90).collect();
+        assert_eq!(popped, (90..100).rev().collect::<Vec<_>>());
+        assert_eq!(heap.peek(), Some(&89));
+
+        let mut calls = 0;
+        let mut iter = heap.pop_while(|_| {
+            calls += 1;
+            false
+        });
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next(), None);
+        drop(iter);
+        assert_eq!(calls, 1);
+        assert_eq!(heap.len(), 90);
     }
 
-    #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        let exact = self.inner.len();
-        (exact, Some(exact))
+    #[test]
+    fn pop_all_max() {
+        let mut rng = thread_rng();
+        let mut data: Vec<u8> = (0..200).map(|_| rng.gen_range(0..10)).collect();
+        let mut heap = DaryHeap::<_, 3>::from(data.clone());
+        data.sort_unstable();
+        while let Some(&max) = data.last() {
+            let count = data.iter().filter(|&&x| x == max).count();
+            assert_eq!(heap.pop_all_max(), vec![max; count]);
+            heap.assert_valid_state();
+            data.truncate(data.len() - count);
+        }
+        assert!(heap.pop_all_max().is_empty());
     }
-}
 
-#[cfg(feature = "unstable")]
-impl<T: Ord, const D: usize> ExactSizeIterator for IntoIterSorted<T, D> {}
+    #[test]
+    fn min() {
+        fn check<const D: usize>() {
+            let mut rng = thread_rng();
+            let mut heap = DaryHeap::<u16, D>::new();
+            assert_eq!(heap.min(), None);
+            for _ in 0..100 {
+                heap.push(rng.gen());
+                assert_eq!(heap.min(), heap.iter().min());
+            }
+        }
+        check::<1>();
+        check::<2>();
+        check::<3>();
+        check::<4>();
+        check::<7>();
+    }
 
-#[cfg(feature = "unstable")]
-impl<T: Ord, const D: usize> FusedIterator for IntoIterSorted<T, D> {}
+    #[test]
+    fn from_std() {
+        let mut rng = thread_rng();
+        let data: Vec<u16> = (0..100).map(|_| rng.gen()).collect();
+        let std_heap = alloc::collections::BinaryHeap::from(data);
 
-#[cfg(all(feature = "unstable", feature = "unstable_nightly"))]
-unsafe impl<T: Ord, const D: usize> core::iter::TrustedLen for IntoIterSorted<T, D> {}
+        let heap = DaryHeap::<_, 2>::from(std_heap.clone());
+        heap.assert_valid_state();
+        assert_eq!(heap.into_vec(), std_heap.clone().into_vec());
 
-/// A draining iterator over the elements of a `DaryHeap`.
-///
-/// This `struct` is created by [`DaryHeap::drain()`]. See its
-/// documentation for more.
-///
-/// [`drain`]: DaryHeap::drain
-#[derive(Debug)]
-pub struct Drain<'a, T: 'a> {
-    iter: vec::Drain<'a, T>,
-}
+        let heap = DaryHeap::<_, 5>::from(std_heap.clone());
+        heap.assert_valid_state();
+        assert_eq!(heap.into_sorted_vec(), std_heap.into_sorted_vec());
+    }
 
-impl<T> Iterator for Drain<'_, T> {
-    type Item = T;
+    #[test]
+    fn into_std() {
+        let mut rng = thread_rng();
+        let data: Vec<u16> = (0..100).map(|_| rng.gen()).collect();
 
-    #[inline]
-    fn next(&mut self) -> Option<T> {
-        self.iter.next()
-    }
+        let heap = DaryHeap::<_, 2>::from(data.clone());
+        let vec = heap.clone().into_vec();
+        let std_heap = alloc::collections::BinaryHeap::from(heap);
+        assert_eq!(std_heap.into_vec(), vec);
 
-    #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        self.iter.size_hint()
+        let heap = DaryHeap::<_, 3>::from(data);
+        let sorted = heap.clone().into_sorted_vec();
+        let std_heap = alloc::collections::BinaryHeap::from(heap);
+        assert_eq!(std_heap.into_sorted_vec(), sorted);
     }
-}
 
-impl<T> DoubleEndedIterator for Drain<'_, T> {
-    #[inline]
-    fn next_back(&mut self) -> Option<T> {
-        self.iter.next_back()
+    #[test]
+    fn into_arity() {
+        let mut data: Vec<_> = (0..100).collect();
+        data.shuffle(&mut thread_rng());
+        let heap = DaryHeap::<_, 2>::from(data);
+        let heap: DaryHeap<_, 2> = heap.into_arity();
+        heap.assert_valid_state();
+        let heap: DaryHeap<_, 7> = heap.into_arity();
+        heap.assert_valid_state();
+        let heap: DaryHeap<_, 3> = heap.into_arity();
+        heap.assert_valid_state();
+        assert_eq!(heap.into_sorted_vec(), (0..100).collect::<Vec<_>>());
     }
-}
 
-impl<T> ExactSizeIterator for Drain<'_, T> {
-    #[cfg(feature = "unstable_nightly")]
-    fn is_empty(&self) -> bool {
-        self.iter.is_empty()
+    #[test]
+    fn extend_heap() {
+        let mut data: Vec<_> = (0..100).collect();
+        data.shuffle(&mut thread_rng());
+        for &at in &[0, 10, 50, 90, 100] {
+            let mut a = DaryHeap::<_, 3>::from(data[..at].to_vec());
+            let b = DaryHeap::<_, 3>::from(data[at..].to_vec());
+            a.extend(b);
+            a.assert_valid_state();
+            assert_eq!(a.into_sorted_vec(), (0..100).collect::<Vec<_>>());
+        }
     }
-}
 
-impl<T> FusedIterator for Drain<'_, T> {}
+    #[test]
+    fn meld_heaps() {
+        let mut data: Vec<_> = (0..100).collect();
+        data.shuffle(&mut thread_rng());
+        let heaps: Vec<_> = data
+            .chunks(13)
+            .map(|chunk| DaryHeap::<_, 4>::from(chunk.to_vec()))
+            .collect();
+
+        let heap = DaryHeap::meld(heaps.clone());
+        heap.assert_valid_state();
+        assert_eq!(heap.into_sorted_vec(), (0..100).collect::<Vec<_>>());
+
+        let heap: DaryHeap<_, 4> = heaps.into_iter().sum();
+        heap.assert_valid_state();
+        assert_eq!(heap.len(), 100);
+
+        let heap: DaryHeap<i32, 4> = Vec::new().into_iter().sum();
+        assert!(heap.is_empty());
+    }
 
-/// A draining iterator over the elements of a `DaryHeap`.
-///
-/// This `struct` is created by [`DaryHeap::drain_sorted()`]. See its
-/// documentation for more.
-///
-/// [`drain_sorted`]: DaryHeap::drain_sorted
-#[cfg(feature = "unstable")]
-#[derive(Debug)]
-pub struct DrainSorted<'a, T: Ord, const D: usize> {
-    inner: &'a mut DaryHeap<T, D>,
-}
+    #[test]
+    fn relaxed_bounds() {
+        struct NotOrd;
+        let heap = DaryHeap::<NotOrd, 3>::new();
+        assert!(heap.is_empty());
+        let heap = DaryHeap::<NotOrd, 3>::with_capacity(4);
+        assert!(heap.capacity() >= 4);
+        let heap = crate::min::MinDaryHeap::<NotOrd, 3>::default();
+        assert!(heap.is_empty());
+    }
 
-#[cfg(feature = "unstable")]
-impl<'a, T: Ord, const D: usize> Drop for DrainSorted<'a, T, D> {
-    /// Removes heap elements in heap order.
-    fn drop(&mut self) {
-        use core::mem::forget;
+    #[test]
+    fn from_sorted_desc() {
+        let mut rng = thread_rng();
+        let nelem = if cfg!(miri) { 100 } else { 1000 };
+        let mut data: Vec<u16> = (0..nelem).map(|_| rng.gen()).collect();
+        data.sort_unstable_by(|a, b| b.cmp(a));
 
-        struct DropGuard<'r, 'a, T: Ord, const D: usize>(&'r mut DrainSorted<'a, T, D>);
+        let heap = DaryHeap::<_, 3>::from_sorted_desc(data.clone());
+        heap.assert_valid_state();
+        let heap = DaryHeap::<_, 5>::try_from_sorted_desc(data.clone()).unwrap();
+        heap.assert_valid_state();
 
-        impl<'r, 'a, T: Ord, const D: usize> Drop for DropGuard<'r, 'a, T, D> {
-            fn drop(&mut self) {
-                while self.0.inner.pop().is_some() {}
-            }
-        }
+        data.reverse();
+        let err = DaryHeap::<_, 5>::try_from_sorted_desc(data.clone()).unwrap_err();
+        assert_eq!(err, data);
+    }
 
-        while let Some(item) = self.inner.pop() {
-            let guard = DropGuard(self);
-            drop(item);
-            forget(guard);
-        }
+    #[test]
+    fn from_vec_unchecked() {
+        let mut data: Vec<_> = (0..100).collect();
+        data.shuffle(&mut thread_rng());
+        let vec = DaryHeap::<_, 3>::from(data).into_vec();
+        let heap = unsafe { DaryHeap::<_, 3>::from_vec_unchecked(vec) };
+        heap.assert_valid_state();
+        assert_eq!(heap.into_sorted_vec(), (0..100).collect::<Vec<_>>());
     }
-}
 
-#[cfg(feature = "unstable")]
-impl<T: Ord, const D: usize> Iterator for DrainSorted<'_, T, D> {
-    type Item = T;
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn from_vec_unchecked_invalid() {
+        let _heap = unsafe { DaryHeap::<_, 3>::from_vec_unchecked(vec![1, 2, 3]) };
+    }
 
-    #[inline]
-    fn next(&mut self) -> Option<T> {
-        self.inner.pop()
+    #[test]
+    fn into_sorted_vec_desc() {
+        let mut rng = thread_rng();
+        let nelem = if cfg!(miri) { 100 } else { 1000 };
+        let data: Vec<u16> = (0..nelem).map(|_| rng.gen()).collect();
+        let mut expected = data.clone();
+        expected.sort_unstable_by(|a, b| b.cmp(a));
+        assert_eq!(QuinaryHeap::from(data).into_sorted_vec_desc(), expected);
     }
 
-    #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        let exact = self.inner.len();
-        (exact, Some(exact))
+    #[test]
+    fn depth() {
+        fn check<const D: usize>() {
+            let mut heap = DaryHeap::<_, D>::new();
+            for n in 1..200 {
+                heap.push(n);
+                // The last item is on the deepest level.
+                let mut depth = 1;
+                let mut pos = n - 1;
+                while pos > 0 {
+                    pos = (pos - 1) / D;
+                    depth += 1;
+                }
+                assert_eq!(heap.depth(), depth);
+            }
+        }
+        check::<1>();
+        check::<2>();
+        check::<3>();
+        check::<4>();
+        check::<7>();
     }
-}
 
-#[cfg(feature = "unstable")]
-impl<T: Ord, const D: usize> ExactSizeIterator for DrainSorted<'_, T, D> {}
+    #[test]
+    fn layers() {
+        fn check<const D: usize>() {
+            let heap = DaryHeap::<_, D>::from((0..200).collect::<Vec<_>>());
+            let layers: Vec<_> = heap.layers().collect();
+            assert_eq!(layers.len(), heap.depth());
+            assert_eq!(heap.layers().len(), heap.depth());
+            assert_eq!(layers.concat(), heap.as_slice());
+
+            let mut start = 0;
+            for pair in layers.windows(2) {
+                let (layer, next) = (pair[0], pair[1]);
+                for (i, parent) in layer.iter().enumerate() {
+                    let first = D * (start + i) + 1 - (start + layer.len());
+                    for child in next.iter().skip(first).take(D) {
+                        assert!(child <= parent);
+                    }
+                }
+                start += layer.len();
+            }
+        }
+        check::<1>();
+        check::<2>();
+        check::<3>();
+        check::<4>();
+        check::<7>();
+    }
 
-#[cfg(feature = "unstable")]
-impl<T: Ord, const D: usize> FusedIterator for DrainSorted<'_, T, D> {}
+    #[test]
+    fn debug_tree() {
+        let heap = DaryHeap::<u32, 2>::new();
+        assert_eq!(alloc::format!("{:#?}", heap), "[\n]");
+
+        let heap = DaryHeap::<_, 1>::from(vec![1, 2, 3]);
+        assert_eq!(
+            alloc::format!("{:#?}", heap),
+            "[\n    3\n        2\n            1\n]"
+        );
+
+        let heap = DaryHeap::<_, 2>::from_sorted_desc(vec![(3, 'a'), (2, 'b'), (1, 'c')]);
+        assert_eq!(
+            alloc::format!("{:#?}", heap),
+            "[\n    (3, 'a')\n        (2, 'b')\n        (1, 'c')\n]"
+        );
+    }
 
-#[cfg(all(feature = "unstable", feature = "unstable_nightly"))]
-unsafe impl<T: Ord, const D: usize> core::iter::TrustedLen for DrainSorted<'_, T, D> {}
+    #[test]
+    fn validate() {
+        fn check<const D: usize>() {
+            let mut data: Vec<_> = (0..100).collect();
+            data.shuffle(&mut thread_rng());
+            let mut heap = DaryHeap::<_, D>::from(data);
+            assert_eq!(heap.validate(), Ok(()));
+
+            // Make the last child of some node greater than the root.
+            let last_child = D.min(heap.len() - 1);
+            heap.data[last_child] = 100;
+            assert!(!heap.is_valid());
+            let err = heap.validate().unwrap_err();
+            assert_eq!(err.index(), last_child);
+            assert_eq!(err.parent(), 0);
+        }
+        check::<1>();
+        check::<2>();
+        check::<3>();
+        check::<4>();
+        check::<7>();
+    }
 
-impl<T: Ord, const D: usize> From<Vec<T>> for DaryHeap<T, D> {
-    /// Converts a `Vec<T>` into a `DaryHeap<T, D>`.
-    ///
-    /// This conversion happens in-place, and has *O*(*n*) time complexity.
-    fn from(vec: Vec<T>) -> DaryHeap<T, D> {
-        let mut heap = DaryHeap { data: vec };
-        heap.rebuild();
-        heap
+    #[test]
+    #[cfg(all(feature = "paranoid", debug_assertions))]
+    #[should_panic(expected = "heap property violated")]
+    fn paranoid() {
+        use core::cell::Cell;
+        let mut heap = DaryHeap::<_, 2>::from(vec![Cell::new(3), Cell::new(2), Cell::new(1)]);
+        heap.data[2].set(4);
+        heap.push(Cell::new(0));
     }
-}
 
-impl<T: Ord, const D: usize, const N: usize> From<[T; N]> for DaryHeap<T, D> {
-    /// ```
-    /// use dary_heap::TernaryHeap;
-    ///
-    /// let mut h1 = TernaryHeap::from([1, 4, 2, 3]);
-    /// let mut h2: TernaryHeap<_> = [1, 4, 2, 3].into();
-    /// while let Some((a, b)) = h1.pop().zip(h2.pop()) {
-    ///     assert_eq!(a, b);
-    /// }
-    /// ```
-    fn from(arr: [T; N]) -> Self {
-        // With newer Rust versions `Self::from_iter(arr)` should be used, as
-        // using `IntoIter::new` is deprecated from 1.59.0. However, this would
-        // require a MSRV of 1.53.0, and both are equivalent behind the scenes.
-        #[allow(deprecated)]
-        core::array::IntoIter::new(arr).collect()
+    #[test]
+    #[cfg(all(feature = "paranoid", debug_assertions))]
+    #[should_panic(expected = "heap property violated")]
+    fn paranoid_remove() {
+        use core::cell::Cell;
+        let data = vec![Cell::new(3), Cell::new(2), Cell::new(1), Cell::new(0)];
+        let mut heap = DaryHeap::<_, 2>::from(data);
+        heap.data[2].set(4);
+        // Removing the last item does not sift anything, but is still checked.
+        heap.remove(&Cell::new(0));
     }
-}
 
-impl<T, const D: usize> From<DaryHeap<T, D>> for Vec<T> {
-    /// Converts a `DaryHeap<T, D>` into a `Vec<T>`.
-    ///
-    /// This conversion requires no data movement or allocation, and has
-    /// constant time complexity.
-    fn from(heap: DaryHeap<T, D>) -> Vec<T> {
-        heap.data
+    #[test]
+    fn replace_root_with() {
+        fn check<const D: usize>() {
+            let mut data: Vec<_> = (0..100).collect();
+            data.shuffle(&mut thread_rng());
+            let mut heap = DaryHeap::<_, D>::from(data);
+            for i in (0..100).rev() {
+                assert_eq!(
+                    heap.replace_root_with(|top| core::mem::replace(top, -i)),
+                    Some(i)
+                );
+                heap.assert_valid_state();
+            }
+            assert_eq!(heap.into_sorted_vec(), (-99..=0).collect::<Vec<_>>());
+            let mut empty = DaryHeap::<i32, D>::new();
+            assert_eq!(empty.replace_root_with(|_| ()), None);
+        }
+        check::<1>();
+        check::<2>();
+        check::<3>();
+        check::<4>();
+        check::<7>();
     }
-}
 
-impl<T: Ord, const D: usize> FromIterator<T> for DaryHeap<T, D> {
-    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> DaryHeap<T, D> {
-        DaryHeap::from(iter.into_iter().collect::<Vec<_>>())
+    #[test]
+    #[cfg(feature = "extra")]
+    fn retain_mut() {
+        fn check<const D: usize>() {
+            let mut data: Vec<_> = (0..100).collect();
+            data.shuffle(&mut thread_rng());
+            let mut heap = DaryHeap::<_, D>::from(data);
+            // Reverses the order of the retained items.
+            heap.retain_mut(|x| {
+                *x = 100 - *x;
+                *x % 3 != 0
+            });
+            heap.assert_valid_state();
+            let expected: Vec<_> = (1..=100).filter(|x| x % 3 != 0).collect();
+            assert_eq!(heap.into_sorted_vec(), expected);
+        }
+        check::<1>();
+        check::<2>();
+        check::<3>();
+        check::<4>();
+        check::<7>();
     }
-}
 
-impl<T, const D: usize> IntoIterator for DaryHeap<T, D> {
-    type Item = T;
-    type IntoIter = IntoIter<T>;
+    #[test]
+    fn modify_at() {
+        fn check<const D: usize>() {
+            let mut rng = thread_rng();
+            let mut data: Vec<_> = (0..100).collect();
+            data.shuffle(&mut rng);
+            let mut heap = DaryHeap::<_, D>::from(data);
+            let mut expected: Vec<_> = (0..100).collect();
+            for _ in 0..100 {
+                let index = rng.gen_range(0..heap.len());
+                let new = rng.gen_range(0..200);
+                let old = heap.modify_at(index, |x| core::mem::replace(x, new));
+                heap.assert_valid_state();
+                let pos = expected.iter().position(|&x| x == old).unwrap();
+                expected[pos] = new;
+            }
+            expected.sort_unstable();
+            assert_eq!(heap.into_sorted_vec(), expected);
+        }
+        check::<1>();
+        check::<2>();
+        check::<3>();
+        check::<4>();
+        check::<7>();
+    }
 
-    /// Creates a consuming iterator, that is, one that moves each value out of
-    /// the *d*-ary heap in arbitrary order. The *d*-ary heap cannot be used
-    /// after calling this.
-    ///
-    /// # Examples
-    ///
-    /// Basic usage:
-    ///
-    /// ```
-    /// use dary_heap::BinaryHeap;
-    /// let heap = BinaryHeap::from([1, 2, 3, 4]);
-    ///
-    /// // Print 1, 2, 3, 4 in arbitrary order
-    /// for x in heap.into_iter() {
-    ///     // x has type i32, not &i32
-    ///     println!("{x}");
-    /// }
-    /// ```
-    fn into_iter(self) -> IntoIter<T> {
-        IntoIter {
-            iter: self.data.into_iter(),
+    #[test]
+    fn remove_at() {
+        fn check<const D: usize>() {
+            let mut rng = thread_rng();
+            let mut data: Vec<_> = (0..100).collect();
+            data.shuffle(&mut rng);
+            let mut heap = DaryHeap::<_, D>::from(data);
+            let mut expected: Vec<_> = (0..100).collect();
+            assert_eq!(heap.remove_at(100), None);
+            while !heap.is_empty() {
+                let index = rng.gen_range(0..heap.len());
+                let item = heap.remove_at(index).unwrap();
+                heap.assert_valid_state();
+                let pos = expected.iter().position(|&x| x == item).unwrap();
+                expected.remove(pos);
+                let mut items: Vec<_> = heap.iter().copied().collect();
+                items.sort_unstable();
+                assert_eq!(items, expected);
+            }
+            assert_eq!(heap.remove_at(0), None);
         }
+        check::<1>();
+        check::<2>();
+        check::<3>();
+        check::<4>();
+        check::<7>();
     }
-}
 
-impl<'a, T, const D: usize> IntoIterator for &'a DaryHeap<T, D> {
-    type Item = &'a T;
-    type IntoIter = Iter<'a, T>;
+    #[test]
+    fn find_index() {
+        fn check<const D: usize>() {
+            let mut rng = thread_rng();
+            let data: Vec<u32> = (0..100).map(|_| rng.gen_range(0..50)).collect();
+            let heap = DaryHeap::<_, D>::from(data);
+            for target in 0..60 {
+                let first = heap.find_index(|&x| x == target);
+                assert_eq!(first, heap.iter().position(|&x| x == target));
+                let bounded = heap.find_index_bounded(|&x| x >= target, |&x| x == target);
+                assert_eq!(bounded.is_some(), first.is_some());
+                if let Some(index) = bounded {
+                    assert_eq!(heap.as_slice()[index], target);
+                }
+            }
+        }
+        check::<1>();
+        check::<2>();
+        check::<3>();
+        check::<4>();
+        check::<7>();
+    }
 
-    fn into_iter(self) -> Iter<'a, T> {
-        self.iter()
+    #[test]
+    fn raw_parts() {
+        fn check<const D: usize>() {
+            let mut rng = thread_rng();
+            let mut data: Vec<_> = (0..100).collect();
+            data.shuffle(&mut rng);
+            let mut heap = DaryHeap::<_, D>::with_capacity(200);
+            heap.extend(data);
+            let expected = heap.as_slice().to_vec();
+            let (ptr, len, capacity) = heap.into_raw_parts();
+            assert_eq!(len, 100);
+            assert!(capacity >= 200);
+            // SAFETY: The parts were just produced by into_raw_parts.
+            let heap = unsafe { DaryHeap::<_, D>::from_raw_parts(ptr, len, capacity) };
+            assert_eq!(heap.as_slice(), expected);
+            assert_eq!(heap.capacity(), capacity);
+            heap.assert_valid_state();
+        }
+        check::<1>();
+        check::<2>();
+        check::<3>();
+        check::<4>();
+        check::<7>();
     }
-}
 
-impl<T: Ord, const D: usize> Extend<T> for DaryHeap<T, D> {
-    #[inline]
-    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
-        let guard = RebuildOnDrop {
-            rebuild_from: self.len(),
-            heap: self,
-        };
-        guard.heap.data.extend(iter);
+    #[test]
+    fn sift_at() {
+        fn check<const D: usize>() {
+            let mut rng = thread_rng();
+            let mut data: Vec<_> = (0..100).collect();
+            data.shuffle(&mut rng);
+            let mut heap = DaryHeap::<_, D>::from(data);
+            for _ in 0..100 {
+                let index = rng.gen_range(0..heap.len());
+                let new = rng.gen_range(0..200);
+                // SAFETY: The heap property is restored below.
+                let item = unsafe { &mut heap.as_mut_slice_unchecked()[index] };
+                let increased = new > *item;
+                *item = new;
+                // SAFETY: index < heap.len().
+                let new_index = unsafe {
+                    if increased {
+                        heap.sift_up_at(index)
+                    } else {
+                        heap.sift_down_at(index)
+                    }
+                };
+                heap.assert_valid_state();
+                assert_eq!(heap.as_slice()[new_index], new);
+                assert!(if increased {
+                    new_index <= index
+                } else {
+                    new_index >= index
+                });
+            }
+        }
+        check::<1>();
+        check::<2>();
+        check::<3>();
+        check::<4>();
+        check::<7>();
     }
 
-    #[inline]
-    #[cfg(feature = "unstable_nightly")]
-    fn extend_one(&mut self, item: T) {
-        self.push(item);
+    #[test]
+    #[should_panic(expected = "should be < len")]
+    fn modify_at_out_of_bounds() {
+        let mut heap = DaryHeap::<_, 4>::from(vec![1, 2, 3]);
+        heap.modify_at(3, |x| *x = 0);
     }
 
-    #[inline]
-    #[cfg(feature = "unstable_nightly")]
-    fn extend_reserve(&mut self, additional: usize) {
-        self.reserve(additional);
+    #[test]
+    fn into_n_largest_smallest() {
+        fn check<const D: usize>() {
+            let mut rng = thread_rng();
+            let data: Vec<u32> = (0..100).map(|_| rng.gen_range(0..50)).collect();
+            let mut sorted = data.clone();
+            sorted.sort_unstable();
+            let heap = DaryHeap::<_, D>::from(data);
+            for &k in &[0, 1, 7, 50, 99, 100, 150] {
+                let n = k.min(sorted.len());
+                let smallest = heap.clone().into_n_smallest(k);
+                assert_eq!(smallest, sorted[..n]);
+                let largest = heap.clone().into_n_largest(k);
+                assert!(largest.iter().eq(sorted.iter().rev().take(n)));
+            }
+        }
+        check::<1>();
+        check::<2>();
+        check::<3>();
+        check::<4>();
+        check::<7>();
     }
-}
 
-impl<'a, T: 'a + Ord + Copy, const D: usize> Extend<&'a T> for DaryHeap<T, D> {
-    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
-        self.extend(iter.into_iter().cloned());
+    #[test]
+    fn kth_largest() {
+        fn check<const D: usize>() {
+            let mut rng = thread_rng();
+            let data: Vec<u32> = (0..100).map(|_| rng.gen_range(0..50)).collect();
+            let mut sorted = data.clone();
+            sorted.sort_unstable_by(|a, b| b.cmp(a));
+            let heap = DaryHeap::<_, D>::from(data);
+            for k in 0..110 {
+                assert_eq!(heap.kth_largest(k), sorted.get(k));
+            }
+        }
+        check::<1>();
+        check::<2>();
+        check::<3>();
+        check::<4>();
+        check::<7>();
     }
 
-    #[inline]
-    #[cfg(feature = "unstable_nightly")]
-    fn extend_one(&mut self, &item: &'a T) {
-        self.push(item);
+    #[test]
+    fn bitor() {
+        fn check<const D: usize>() {
+            let mut rng = thread_rng();
+            for &(m, n) in &[(0, 0), (0, 10), (10, 0), (3, 50), (50, 3), (40, 40)] {
+                let a: Vec<u32> = (0..m).map(|_| rng.gen_range(0..100)).collect();
+                let b: Vec<u32> = (0..n).map(|_| rng.gen_range(0..100)).collect();
+                let mut expected = [a.clone(), b.clone()].concat();
+                expected.sort_unstable();
+                let a = DaryHeap::<_, D>::from(a);
+                let b = DaryHeap::<_, D>::from(b);
+
+                let merged = &a | &b;
+                merged.assert_valid_state();
+                assert_eq!(merged.into_sorted_vec(), expected);
+
+                let mut assigned = a.clone();
+                assigned |= b.clone();
+                assigned.assert_valid_state();
+                assert_eq!(assigned.into_sorted_vec(), expected);
+
+                let merged = a | b;
+                merged.assert_valid_state();
+                assert_eq!(merged.into_sorted_vec(), expected);
+            }
+        }
+        check::<1>();
+        check::<2>();
+        check::<3>();
+        check::<4>();
+        check::<7>();
     }
 
-    #[inline]
-    #[cfg(feature = "unstable_nightly")]
-    fn extend_reserve(&mut self, additional: usize) {
-        self.reserve(additional);
+    #[test]
+    fn from_collections() {
+        use alloc::collections::{BTreeMap, BTreeSet, VecDeque};
+
+        fn check<const D: usize>() {
+            let mut rng = thread_rng();
+            let data: Vec<u32> = (0..100).map(|_| rng.gen_range(0..1000)).collect();
+
+            let set: BTreeSet<_> = data.iter().copied().collect();
+            let heap = DaryHeap::<_, D>::from(set.clone());
+            heap.assert_valid_state();
+            assert!(heap.into_sorted_vec().into_iter().eq(set));
+
+            let map: BTreeMap<_, _> = data.iter().map(|&x| (x, x % 7)).collect();
+            let heap = DaryHeap::<_, D>::from(map.clone());
+            heap.assert_valid_state();
+            assert!(heap.into_sorted_vec().into_iter().eq(map));
+
+            let mut deque: VecDeque<_> = data.iter().copied().collect();
+            deque.rotate_left(30);
+            let heap = DaryHeap::<_, D>::from(deque);
+            heap.assert_valid_state();
+            let mut sorted = data;
+            sorted.sort_unstable();
+            assert_eq!(heap.into_sorted_vec(), sorted);
+        }
+        check::<1>();
+        check::<2>();
+        check::<3>();
+        check::<4>();
+        check::<7>();
     }
-}
 
-#[cfg(any(test, fuzzing))]
-impl<T: Ord + fmt::Debug, const D: usize> DaryHeap<T, D> {
-    /// Panics if the heap is in an inconsistent state
-    #[track_caller]
-    pub fn assert_valid_state(&self) {
-        assert_ne!(D, 0, "Arity should be greater than zero");
-        for (i, v) in self.iter().enumerate() {
-            let children = D * i + 1..D * i + D;
-            if children.start > self.len() {
-                break;
+    #[test]
+    fn append_vec() {
+        fn check<const D: usize>() {
+            let mut rng = thread_rng();
+            for &(m, n) in &[(0, 0), (0, 10), (10, 0), (3, 50), (50, 3), (40, 40)] {
+                let a: Vec<u32> = (0..m).map(|_| rng.gen_range(0..100)).collect();
+                let mut b: Vec<u32> = (0..n).map(|_| rng.gen_range(0..100)).collect();
+                let mut expected = [a.clone(), b.clone()].concat();
+                expected.sort_unstable();
+                let mut heap = DaryHeap::<_, D>::from(a);
+                heap.append_vec(&mut b);
+                assert!(b.is_empty());
+                heap.assert_valid_state();
+                assert_eq!(heap.into_sorted_vec(), expected);
             }
-            for j in children {
-                if let Some(x) = self.data.get(j) {
-                    assert!(v >= x);
-                }
+        }
+        check::<1>();
+        check::<2>();
+        check::<3>();
+        check::<4>();
+        check::<7>();
+    }
+
+    #[test]
+    fn split_by() {
+        fn check<const D: usize>() {
+            let mut rng = thread_rng();
+            let data: Vec<u32> = (0..200).map(|_| rng.gen_range(0..100)).collect();
+            let mut sorted = data.clone();
+            sorted.sort_unstable();
+            for &threshold in &[0, 1, 50, 90, 99, 100] {
+                let mut heap = DaryHeap::<_, D>::from(data.clone());
+                let lower = heap.split_by(&threshold);
+                heap.assert_valid_state();
+                lower.assert_valid_state();
+                let split = sorted.iter().filter(|&&x| x < threshold).count();
+                assert_eq!(heap.into_sorted_vec(), sorted[split..]);
+                assert_eq!(lower.into_sorted_vec(), sorted[..split]);
             }
         }
+        check::<1>();
+        check::<2>();
+        check::<3>();
+        check::<4>();
+        check::<7>();
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use rand::{seq::SliceRandom, thread_rng};
+    // Integrity means that all elements are present after a comparison panics
+    // during a rebuild, and the heap can be rebuilt afterwards.
+    #[test]
+    #[cfg_attr(not(panic = "unwind"), ignore = "test requires unwinding support")]
+    fn rebuild_panic_safe() {
+        extern crate std;
+        use core::cell::Cell;
+        use core::cmp::Ordering;
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        std::thread_local! {
+            // Number of comparisons left before panicking.
+            // The fix for this lint (a const initializer) requires Rust 1.59.0,
+            // but the MSRV is currently 1.51.0.
+            #[allow(clippy::thread_local_initializer_can_be_made_const)]
+            static COMPARISONS_LEFT: Cell<usize> = Cell::new(usize::MAX);
+        }
 
-    fn pop<const D: usize>() {
-        let mut rng = thread_rng();
-        let ntest = if cfg!(miri) { 1 } else { 10 };
-        let nelem = if cfg!(miri) { 100 } else { 1000 };
-        for _ in 0..ntest {
-            let mut data: Vec<_> = (0..nelem).collect();
-            data.shuffle(&mut rng);
-            let mut heap = DaryHeap::<_, D>::from(data);
-            heap.assert_valid_state();
-            for i in (0..nelem).rev() {
-                assert_eq!(heap.pop(), Some(i));
-                heap.assert_valid_state();
+        #[derive(Eq, PartialEq, Debug)]
+        struct PanicOrd(usize);
+
+        impl PartialOrd for PanicOrd {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for PanicOrd {
+            fn cmp(&self, other: &Self) -> Ordering {
+                COMPARISONS_LEFT.with(|left| {
+                    if left.get() == 0 {
+                        left.set(usize::MAX);
+                        panic!("Panicking comparison");
+                    }
+                    left.set(left.get() - 1);
+                });
+                self.0.cmp(&other.0)
+            }
+        }
+
+        fn check<const D: usize>() {
+            const DATASZ: usize = 32;
+            for comparisons in 0.. {
+                let mut heap: DaryHeap<_, D> = (0..DATASZ).map(PanicOrd).collect();
+
+                let result = catch_unwind(AssertUnwindSafe(|| {
+                    let mut vec = heap.as_vec_mut();
+                    vec.reverse();
+                    COMPARISONS_LEFT.with(|left| left.set(comparisons));
+                }));
+                COMPARISONS_LEFT.with(|left| left.set(usize::MAX));
+
+                // No items were lost, and rebuilding restores the heap property.
+                drop(heap.as_vec_mut());
+                assert!(heap.is_valid());
+                let data: Vec<_> = heap.into_sorted_vec().into_iter().map(|p| p.0).collect();
+                assert_eq!(data, (0..DATASZ).collect::<Vec<_>>());
+
+                // Stop once the rebuild needs fewer comparisons than allowed.
+                if result.is_ok() {
+                    break;
+                }
             }
-            assert_eq!(heap.pop(), None);
         }
+        check::<1>();
+        check::<2>();
+        check::<3>();
+        check::<4>();
+        check::<7>();
     }
 
     #[test]
-    #[should_panic]
-    fn push_d0() {
-        let mut heap = DaryHeap::<_, 0>::new();
-        heap.push(42);
+    fn iter_top_down_parent_first() {
+        fn check<const D: usize>() {
+            let mut rng = thread_rng();
+            let data: Vec<u8> = (0..200).map(|_| rng.gen()).collect();
+            let heap = DaryHeap::<_, D>::from(data);
+            let items: Vec<_> = heap.iter_top_down().collect();
+            assert_eq!(items.len(), 200);
+            for i in 1..items.len() {
+                assert!(items[i] <= items[(i - 1) / D]);
+            }
+        }
+        check::<1>();
+        check::<2>();
+        check::<3>();
+        check::<4>();
+        check::<7>();
     }
 
     #[test]
-    #[should_panic]
-    fn from_vec_d0() {
-        let _heap = DaryHeap::<_, 0>::from(vec![42]);
+    fn sort_into() {
+        fn check<const D: usize>() {
+            let mut data: Vec<_> = (0..100).collect();
+            data.shuffle(&mut thread_rng());
+            let mut heap = DaryHeap::<_, D>::from(data);
+            let capacity = heap.capacity();
+            let mut vec = vec![-1];
+            heap.sort_into(&mut vec);
+            assert!(heap.is_empty());
+            assert_eq!(heap.capacity(), capacity);
+            assert_eq!(vec, (-1..100).collect::<Vec<_>>());
+        }
+        check::<1>();
+        check::<2>();
+        check::<3>();
+        check::<4>();
+        check::<7>();
     }
 
     #[test]
-    fn pop_d1() {
-        pop::<1>();
+    fn pop_with_strategy() {
+        fn check<const D: usize>() {
+            let mut rng = thread_rng();
+            let data: Vec<u32> = (0..200).map(|_| rng.gen_range(0..20)).collect();
+            let mut sorted = data.clone();
+            sorted.sort_unstable();
+            let mut heap = DaryHeap::<_, D>::from(data);
+            while let Some(expected) = sorted.pop() {
+                let strategy = if rng.gen() {
+                    PopStrategy::ToBottom
+                } else {
+                    PopStrategy::SiftDown
+                };
+                assert_eq!(heap.pop_with_strategy(strategy), Some(expected));
+                heap.assert_valid_state();
+            }
+            assert_eq!(heap.pop_with_strategy(PopStrategy::SiftDown), None);
+        }
+        check::<1>();
+        check::<2>();
+        check::<3>();
+        check::<4>();
+        check::<7>();
     }
 
     #[test]
-    fn pop_d2() {
-        pop::<2>();
+    fn integer_keys() {
+        fn check<T: Ord + Copy + fmt::Debug, const D: usize>(data: &[T]) {
+            let mut sorted = data.to_vec();
+            sorted.sort_unstable();
+            let heap = DaryHeap::<_, D>::from(data.to_vec());
+            heap.assert_valid_state();
+            assert_eq!(heap.into_sorted_vec(), sorted);
+        }
+        let mut rng = thread_rng();
+        // A small range of values, so that siblings are often equal.
+        let data: Vec<i64> = (0..1000).map(|_| rng.gen_range(-50..50)).collect();
+        check::<_, 4>(&data);
+        check::<_, 8>(&data);
+        check::<_, 16>(&data);
+        let data: Vec<u8> = (0..1000).map(|_| rng.gen()).collect();
+        check::<_, 4>(&data);
+        check::<_, 8>(&data);
+        check::<_, 16>(&data);
     }
 
+    /// An arity for which the maximum length is 100, to test the limit.
+    const HUGE_D: usize = usize::MAX / 100;
+
     #[test]
-    fn pop_d3() {
-        pop::<3>();
+    fn max_len() {
+        assert_eq!(DaryHeap::<u32, 1>::MAX_LEN, usize::MAX);
+        assert_eq!(DaryHeap::<u32, HUGE_D>::MAX_LEN, 100);
+
+        let mut data: Vec<u32> = (0..100).collect();
+        data.shuffle(&mut thread_rng());
+        let mut heap = DaryHeap::<_, HUGE_D>::new();
+        for item in data {
+            assert_eq!(heap.try_push(item), Ok(()));
+        }
+        heap.assert_valid_state();
+        assert_eq!(heap.try_push(100), Err(100));
+        assert_eq!(heap.len(), 100);
+
+        assert_eq!(heap.pop(), Some(99));
+        assert_eq!(heap.try_push(100), Ok(()));
+        assert_eq!(heap.try_push(101), Err(101));
+        heap.assert_valid_state();
+
+        let mut expected: Vec<_> = (0..99).collect();
+        expected.push(100);
+        assert_eq!(heap.into_sorted_vec(), expected);
     }
 
     #[test]
-    fn pop_d4() {
-        pop::<4>();
+    #[should_panic(expected = "exceeds the maximum length")]
+    fn push_beyond_max_len() {
+        let mut heap: DaryHeap<_, HUGE_D> = (0..100).collect();
+        heap.push(100);
     }
 
     #[test]
-    fn pop_d5() {
-        pop::<5>();
+    #[should_panic(expected = "exceeds the maximum length")]
+    fn from_vec_beyond_max_len() {
+        let _ = DaryHeap::<_, HUGE_D>::from((0..101).collect::<Vec<_>>());
     }
 
+    // Growing a full heap panics before any items are moved into it, so it is
+    // left untouched.
     #[test]
-    fn pop_d6() {
-        pop::<6>();
+    #[cfg_attr(not(panic = "unwind"), ignore = "test requires unwinding support")]
+    fn grow_beyond_max_len() {
+        extern crate std;
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let full = || (0..100).collect::<DaryHeap<_, HUGE_D>>();
+        let check = |heap: DaryHeap<_, HUGE_D>| {
+            heap.assert_valid_state();
+            assert_eq!(heap.into_sorted_vec(), (0..100).collect::<Vec<_>>());
+        };
+
+        let mut heap = full();
+        let mut other = DaryHeap::from(vec![100]);
+        assert!(catch_unwind(AssertUnwindSafe(|| heap.append(&mut other))).is_err());
+        check(heap);
+        assert_eq!(other.into_vec(), [100]);
+
+        let mut heap = full();
+        let mut other = full();
+        assert!(catch_unwind(AssertUnwindSafe(|| other.append(&mut heap))).is_err());
+        check(heap);
+        check(other);
+
+        let mut heap = full();
+        let mut vec = vec![100];
+        assert!(catch_unwind(AssertUnwindSafe(|| heap.append_vec(&mut vec))).is_err());
+        check(heap);
+        assert_eq!(vec, [100]);
+
+        let mut heap = full();
+        assert!(catch_unwind(AssertUnwindSafe(|| heap.extend(100..110))).is_err());
+        check(heap);
+
+        let heaps = vec![full(), DaryHeap::from(vec![100])];
+        assert!(catch_unwind(AssertUnwindSafe(|| DaryHeap::meld(heaps))).is_err());
     }
 
     #[test]
-    fn pop_d7() {
-        pop::<7>();
+    fn contains() {
+        fn check<const D: usize>() {
+            let mut rng = thread_rng();
+            let nelem = if cfg!(miri) { 100 } else { 1000 };
+            let mut data: Vec<_> = (0..nelem).map(|x| 2 * x).collect();
+            data.shuffle(&mut rng);
+            let heap = DaryHeap::<_, D>::from(data);
+            for x in 0..2 * nelem {
+                assert_eq!(heap.contains(&x), x % 2 == 0);
+            }
+        }
+        check::<1>();
+        check::<2>();
+        check::<3>();
+        check::<4>();
+        check::<7>();
     }
 
     #[test]
-    fn pop_d8() {
-        pop::<8>();
+    #[cfg(feature = "bincode")]
+    fn bincode() {
+        let config = bincode::config::standard();
+        let heap: DaryHeap<u32, 3> = vec![3, 1, 4, 1, 5, 9, 2, 6].into();
+        let bytes = bincode::encode_to_vec(&heap, config).unwrap();
+        assert_eq!(
+            bytes,
+            bincode::encode_to_vec(heap.as_slice(), config).unwrap()
+        );
+        let (decoded, len): (DaryHeap<u32, 3>, _) =
+            bincode::decode_from_slice(&bytes, config).unwrap();
+        assert_eq!(len, bytes.len());
+        assert_eq!(decoded.as_slice(), heap.as_slice());
+
+        // Decoding a sequence that is not a heap restores the heap property.
+        let bytes = bincode::encode_to_vec(vec![1u32, 2, 3, 4], config).unwrap();
+        let (decoded, _): (DaryHeap<u32, 3>, _) =
+            bincode::borrow_decode_from_slice(&bytes, config).unwrap();
+        decoded.assert_valid_state();
+        assert_eq!(decoded.into_sorted_vec(), [1, 2, 3, 4]);
     }
 
     #[test]
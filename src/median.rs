@@ -0,0 +1,254 @@
+//! Running median of a stream of items.
+//!
+//! The [`RunningMedian`] type in this module keeps track of the median of all
+//! items inserted into it so far. It stores the smaller half of the items in a
+//! *d*-ary max-heap and the larger half in a *d*-ary min-heap, so the median is
+//! always at the top of one or both heaps.
+
+use core::fmt;
+use core::iter::FromIterator;
+
+use alloc::vec::Vec;
+
+use crate::min::MinDaryHeap;
+use crate::DaryHeap;
+
+/// A running median implemented with two *d*-ary heaps.
+///
+/// For an odd number of items, the median is the middle item. For an even
+/// number of items, there are two middle items: [`median`] returns the lower
+/// one and [`median_pair`] returns both.
+///
+/// [`median`]: RunningMedian::median
+/// [`median_pair`]: RunningMedian::median_pair
+///
+/// # Examples
+///
+/// ```
+/// use dary_heap::median::RunningMedian;
+///
+/// let mut median = RunningMedian::<_, 4>::new();
+/// median.insert(5);
+/// assert_eq!(median.median(), Some(&5));
+///
+/// median.insert(1);
+/// assert_eq!(median.median(), Some(&1));
+/// assert_eq!(median.median_pair(), Some((&1, &5)));
+///
+/// median.insert(3);
+/// assert_eq!(median.median(), Some(&3));
+/// assert_eq!(median.median_pair(), Some((&3, &3)));
+/// ```
+pub struct RunningMedian<T, const D: usize> {
+    /// The lower half of the items; has the same length as `upper` or one
+    /// item more.
+    lower: DaryHeap<T, D>,
+    /// The upper half of the items.
+    upper: MinDaryHeap<T, D>,
+}
+
+impl<T: Clone, const D: usize> Clone for RunningMedian<T, D> {
+    fn clone(&self) -> Self {
+        RunningMedian {
+            lower: self.lower.clone(),
+            upper: self.upper.clone(),
+        }
+    }
+}
+
+impl<T: Ord, const D: usize> Default for RunningMedian<T, D> {
+    /// Creates an empty `RunningMedian<T, D>`.
+    #[inline]
+    fn default() -> RunningMedian<T, D> {
+        RunningMedian::new()
+    }
+}
+
+impl<T: fmt::Debug, const D: usize> fmt::Debug for RunningMedian<T, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RunningMedian")
+            .field("lower", &self.lower)
+            .field("upper", &self.upper)
+            .finish()
+    }
+}
+
+impl<T: Ord, const D: usize> RunningMedian<T, D> {
+    /// Creates an empty `RunningMedian<T, D>`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::median::RunningMedian;
+    /// let mut median = RunningMedian::<_, 2>::new();
+    /// median.insert(4);
+    /// ```
+    #[must_use]
+    pub fn new() -> RunningMedian<T, D> {
+        RunningMedian {
+            lower: DaryHeap::new(),
+            upper: MinDaryHeap::new(),
+        }
+    }
+
+    /// Inserts an item.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::median::RunningMedian;
+    /// let mut median = RunningMedian::<_, 3>::new();
+    /// median.insert(3);
+    /// median.insert(5);
+    /// median.insert(1);
+    ///
+    /// assert_eq!(median.len(), 3);
+    /// assert_eq!(median.median(), Some(&3));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The cost of `insert` is *O*(log(*n*)), as it pushes to one heap and may
+    /// move an item from one heap to the other.
+    pub fn insert(&mut self, item: T) {
+        match self.lower.peek() {
+            Some(top) if item > *top => self.upper.push(item),
+            _ => self.lower.push(item),
+        }
+        if self.lower.len() > self.upper.len() + 1 {
+            let item = self.lower.pop().unwrap();
+            self.upper.push(item);
+        } else if self.upper.len() > self.lower.len() {
+            let item = self.upper.pop().unwrap();
+            self.lower.push(item);
+        }
+    }
+
+    /// Returns the two middle items in ascending order, or `None` if empty.
+    ///
+    /// For an odd number of items both references point to the single middle
+    /// item.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::median::RunningMedian;
+    /// let median: RunningMedian<_, 2> = vec![1, 9, 2, 8].into_iter().collect();
+    ///
+    /// assert_eq!(median.median_pair(), Some((&2, &8)));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Cost is *O*(1) in the worst case.
+    #[must_use]
+    pub fn median_pair(&self) -> Option<(&T, &T)> {
+        let low = self.lower.peek()?;
+        if self.lower.len() > self.upper.len() {
+            Some((low, low))
+        } else {
+            self.upper.peek().map(|high| (low, high))
+        }
+    }
+}
+
+impl<T, const D: usize> RunningMedian<T, D> {
+    /// Returns the median, or `None` if empty.
+    ///
+    /// For an even number of items, the lower of the two middle items is
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::median::RunningMedian;
+    /// let mut median = RunningMedian::<_, 2>::new();
+    /// assert_eq!(median.median(), None);
+    ///
+    /// median.insert(7);
+    /// median.insert(2);
+    /// assert_eq!(median.median(), Some(&2));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Cost is *O*(1) in the worst case.
+    #[must_use]
+    pub fn median(&self) -> Option<&T> {
+        self.lower.peek()
+    }
+
+    /// Returns the number of inserted items.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lower.len() + self.upper.len()
+    }
+
+    /// Checks if no items have been inserted.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.lower.is_empty()
+    }
+
+    /// Returns all inserted items in arbitrary order.
+    pub fn into_vec(self) -> Vec<T> {
+        let mut vec = self.lower.into_vec();
+        vec.extend(self.upper.into_vec());
+        vec
+    }
+
+    /// Removes all items.
+    pub fn clear(&mut self) {
+        self.lower.clear();
+        self.upper.clear();
+    }
+}
+
+impl<T: Ord, const D: usize> Extend<T> for RunningMedian<T, D> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.insert(item);
+        }
+    }
+}
+
+impl<T: Ord, const D: usize> FromIterator<T> for RunningMedian<T, D> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> RunningMedian<T, D> {
+        let mut median = RunningMedian::new();
+        median.extend(iter);
+        median
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{thread_rng, Rng};
+
+    #[test]
+    fn matches_sorted() {
+        let mut rng = thread_rng();
+        let mut median = RunningMedian::<u8, 3>::new();
+        let mut sorted = Vec::new();
+        for _ in 0..200 {
+            let item = rng.gen();
+            median.insert(item);
+            let pos = sorted.binary_search(&item).unwrap_or_else(|pos| pos);
+            sorted.insert(pos, item);
+
+            let n = sorted.len();
+            let pair = (&sorted[(n - 1) / 2], &sorted[n / 2]);
+            assert_eq!(median.len(), n);
+            assert_eq!(median.median(), Some(pair.0));
+            assert_eq!(median.median_pair(), Some(pair));
+        }
+    }
+}
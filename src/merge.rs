@@ -0,0 +1,227 @@
+//! Merging of sorted iterators.
+//!
+//! The [`kmerge`] function in this module merges any number of iterators that
+//! yield items in ascending order into a single iterator that yields all items
+//! in ascending order. A *d*-ary heap holding the next item of every input
+//! iterator is used to select the smallest item at each step.
+
+use core::cmp::Ordering;
+use core::fmt;
+use core::iter::FusedIterator;
+use core::mem;
+
+use alloc::vec::Vec;
+
+use crate::{DaryHeap, PeekMut};
+
+/// Merges iterators that yield items in ascending order into one iterator
+/// that yields all items in ascending order.
+///
+/// Equal items are yielded in the order of the iterators they originate from.
+/// If any of the input iterators is not sorted, the output is not sorted
+/// either, but every item is still yielded exactly once.
+///
+/// The arity *d* of the heap used internally is passed as a const generic
+/// parameter.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use dary_heap::merge::kmerge;
+///
+/// let a = vec![1, 4, 7];
+/// let b = vec![2, 5, 8];
+/// let c = vec![3, 6, 9];
+///
+/// let merged: Vec<_> = kmerge::<_, 4>(vec![a, b, c]).collect();
+/// assert_eq!(merged, [1, 2, 3, 4, 5, 6, 7, 8, 9]);
+/// ```
+///
+/// # Time complexity
+///
+/// Yielding an item costs *O*(log(*k*)) for *k* input iterators.
+pub fn kmerge<I, const D: usize>(iters: I) -> KMerge<<I::Item as IntoIterator>::IntoIter, D>
+where
+    I: IntoIterator,
+    I::Item: IntoIterator,
+    <I::Item as IntoIterator>::Item: Ord,
+{
+    let heads = iters
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, iter)| {
+            let mut iter = iter.into_iter();
+            iter.next().map(|item| Head { item, index, iter })
+        })
+        .collect();
+    KMerge { heap: heads }
+}
+
+/// The next item of an input iterator of [`KMerge`].
+///
+/// Heads are ordered in reverse, so the head with the smallest item (and the
+/// lowest index among equal items) is at the top of the max-heap.
+struct Head<I: Iterator> {
+    item: I::Item,
+    index: usize,
+    iter: I,
+}
+
+impl<I: Iterator> PartialEq for Head<I>
+where
+    I::Item: Ord,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<I: Iterator> Eq for Head<I> where I::Item: Ord {}
+
+impl<I: Iterator> PartialOrd for Head<I>
+where
+    I::Item: Ord,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<I: Iterator> Ord for Head<I>
+where
+    I::Item: Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        (&other.item, other.index).cmp(&(&self.item, self.index))
+    }
+}
+
+impl<I: Iterator + Clone> Clone for Head<I>
+where
+    I::Item: Clone,
+{
+    fn clone(&self) -> Self {
+        Head {
+            item: self.item.clone(),
+            index: self.index,
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+/// An iterator that merges sorted iterators.
+///
+/// This `struct` is created by [`kmerge`]. See its documentation for more.
+pub struct KMerge<I: Iterator, const D: usize> {
+    heap: DaryHeap<Head<I>, D>,
+}
+
+impl<I: Iterator + Clone, const D: usize> Clone for KMerge<I, D>
+where
+    I::Item: Clone,
+{
+    fn clone(&self) -> Self {
+        KMerge {
+            heap: self.heap.clone(),
+        }
+    }
+}
+
+impl<I: Iterator, const D: usize> fmt::Debug for KMerge<I, D>
+where
+    I::Item: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("KMerge")
+            .field(&self.heap.iter().map(|head| &head.item).collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl<I: Iterator, const D: usize> Iterator for KMerge<I, D>
+where
+    I::Item: Ord,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let mut top = self.heap.peek_mut()?;
+        match top.iter.next() {
+            Some(next) => Some(mem::replace(&mut top.item, next)),
+            None => Some(PeekMut::pop(top).item),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.heap.iter().fold(
+            (self.heap.len(), Some(self.heap.len())),
+            |(lo, hi), head| {
+                let (head_lo, head_hi) = head.iter.size_hint();
+                let hi = match (hi, head_hi) {
+                    (Some(hi), Some(head_hi)) => hi.checked_add(head_hi),
+                    _ => None,
+                };
+                (lo.saturating_add(head_lo), hi)
+            },
+        )
+    }
+}
+
+impl<I: Iterator, const D: usize> FusedIterator for KMerge<I, D> where I::Item: Ord {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use rand::{thread_rng, Rng};
+
+    #[test]
+    fn merge_sorted() {
+        let mut rng = thread_rng();
+        let mut inputs: Vec<Vec<u8>> = (0..20)
+            .map(|_| (0..rng.gen_range(0..50)).map(|_| rng.gen()).collect())
+            .collect();
+        for input in &mut inputs {
+            input.sort_unstable();
+        }
+        let mut expected: Vec<_> = inputs.iter().flatten().copied().collect();
+        expected.sort_unstable();
+
+        let merged = kmerge::<_, 3>(inputs);
+        assert_eq!(merged.size_hint(), (expected.len(), Some(expected.len())));
+        assert_eq!(merged.collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn merge_stable() {
+        let a = [(1, 'a'), (2, 'a'), (2, 'a')];
+        let b = [(0, 'b'), (2, 'b')];
+        let key =
+            |iter: &[(i32, char)]| -> Vec<_> { iter.iter().map(|&(k, c)| Key(k, c)).collect() };
+        let merged: Vec<_> = kmerge::<_, 2>(vec![key(&a), key(&b)])
+            .map(|Key(_, c)| c)
+            .collect();
+        assert_eq!(merged, ['b', 'a', 'a', 'a', 'b']);
+
+        #[derive(Debug)]
+        struct Key(i32, char);
+        impl PartialEq for Key {
+            fn eq(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+        }
+        impl Eq for Key {}
+        impl PartialOrd for Key {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        impl Ord for Key {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.0.cmp(&other.0)
+            }
+        }
+    }
+}
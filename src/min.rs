@@ -0,0 +1,628 @@
+//! A *d*-ary min-heap.
+//!
+//! The [`MinDaryHeap`] type in this module is a *d*-ary heap that yields the
+//! smallest item first. It is equivalent to a [`DaryHeap`] of [`Reverse`]
+//! items, but does not require wrapping and unwrapping items at every call
+//! site.
+//!
+//! [`DaryHeap`]: crate::DaryHeap
+
+use core::cmp::Reverse;
+use core::fmt;
+use core::iter::{FromIterator, FusedIterator};
+
+use alloc::vec::Vec;
+
+use crate::DaryHeap;
+
+/// A binary min-heap (*d* = 2).
+pub type MinBinaryHeap<T> = MinDaryHeap<T, 2>;
+
+/// A ternary min-heap (*d* = 3).
+pub type MinTernaryHeap<T> = MinDaryHeap<T, 3>;
+
+/// A quaternary min-heap (*d* = 4).
+pub type MinQuaternaryHeap<T> = MinDaryHeap<T, 4>;
+
+/// A quinary min-heap (*d* = 5).
+pub type MinQuinaryHeap<T> = MinDaryHeap<T, 5>;
+
+/// A senary min-heap (*d* = 6).
+pub type MinSenaryHeap<T> = MinDaryHeap<T, 6>;
+
+/// A septenary min-heap (*d* = 7).
+pub type MinSeptenaryHeap<T> = MinDaryHeap<T, 7>;
+
+/// An octonary min-heap (*d* = 8).
+pub type MinOctonaryHeap<T> = MinDaryHeap<T, 8>;
+
+/// A novenary min-heap (*d* = 9).
+pub type MinNovenaryHeap<T> = MinDaryHeap<T, 9>;
+
+/// A denary min-heap (*d* = 10).
+pub type MinDenaryHeap<T> = MinDaryHeap<T, 10>;
+
+/// An undenary min-heap (*d* = 11).
+pub type MinUndenaryHeap<T> = MinDaryHeap<T, 11>;
+
+/// A duodenary min-heap (*d* = 12).
+pub type MinDuodenaryHeap<T> = MinDaryHeap<T, 12>;
+
+/// A tredenary min-heap (*d* = 13).
+pub type MinTredenaryHeap<T> = MinDaryHeap<T, 13>;
+
+/// A quattuordenary min-heap (*d* = 14).
+pub type MinQuattuordenaryHeap<T> = MinDaryHeap<T, 14>;
+
+/// A quindenary min-heap (*d* = 15).
+pub type MinQuindenaryHeap<T> = MinDaryHeap<T, 15>;
+
+/// A sedenary min-heap (*d* = 16).
+pub type MinSedenaryHeap<T> = MinDaryHeap<T, 16>;
+
+/// A priority queue implemented with a *d*-ary heap.
+///
+/// This will be a min-heap. Apart from the ordering, it behaves like
+/// [`DaryHeap`]; see its documentation for more information.
+///
+/// [`DaryHeap`]: crate::DaryHeap
+///
+/// # Examples
+///
+/// ```
+/// use dary_heap::min::MinQuaternaryHeap;
+///
+/// let mut heap = MinQuaternaryHeap::new();
+/// heap.push(5);
+/// heap.push(1);
+/// heap.push(2);
+///
+/// assert_eq!(heap.peek(), Some(&1));
+/// assert_eq!(heap.pop(), Some(1));
+/// assert_eq!(heap.pop(), Some(2));
+/// assert_eq!(heap.pop(), Some(5));
+/// assert_eq!(heap.pop(), None);
+/// ```
+pub struct MinDaryHeap<T, const D: usize> {
+    heap: DaryHeap<Reverse<T>, D>,
+}
+
+impl<T: Clone, const D: usize> Clone for MinDaryHeap<T, D> {
+    fn clone(&self) -> Self {
+        MinDaryHeap {
+            heap: self.heap.clone(),
+        }
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        self.heap.clone_from(&source.heap);
+    }
+}
+
+impl<T, const D: usize> Default for MinDaryHeap<T, D> {
+    /// Creates an empty `MinDaryHeap<T, D>`.
+    #[inline]
+    fn default() -> MinDaryHeap<T, D> {
+        MinDaryHeap::new()
+    }
+}
+
+impl<T: fmt::Debug, const D: usize> fmt::Debug for MinDaryHeap<T, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T, const D: usize> MinDaryHeap<T, D> {
+    /// Creates an empty `MinDaryHeap` as a min-heap.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::min::MinDaryHeap;
+    /// let mut heap = MinDaryHeap::<_, 3>::new();
+    /// heap.push(4);
+    /// ```
+    #[must_use]
+    pub const fn new() -> MinDaryHeap<T, D> {
+        MinDaryHeap {
+            heap: DaryHeap::new(),
+        }
+    }
+
+    /// Creates an empty `MinDaryHeap` with at least the specified capacity.
+    ///
+    /// The *d*-ary heap will be able to hold at least `capacity` elements without
+    /// reallocating. This method is allowed to allocate for more elements than
+    /// `capacity`. If `capacity` is 0, the *d*-ary heap will not allocate.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::min::MinDaryHeap;
+    /// let mut heap = MinDaryHeap::<_, 3>::with_capacity(10);
+    /// heap.push(4);
+    /// ```
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> MinDaryHeap<T, D> {
+        MinDaryHeap {
+            heap: DaryHeap::with_capacity(capacity),
+        }
+    }
+}
+
+impl<T: Ord, const D: usize> MinDaryHeap<T, D> {
+    /// Removes the smallest item from the *d*-ary heap and returns it, or `None` if it
+    /// is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::min::MinDaryHeap;
+    /// let mut heap = MinDaryHeap::<_, 2>::from(vec![1, 3]);
+    ///
+    /// assert_eq!(heap.pop(), Some(1));
+    /// assert_eq!(heap.pop(), Some(3));
+    /// assert_eq!(heap.pop(), None);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The worst case cost of `pop` on a heap containing *n* elements is *O*(log(*n*)).
+    pub fn pop(&mut self) -> Option<T> {
+        self.heap.pop().map(|Reverse(item)| item)
+    }
+
+    /// Pushes an item onto the *d*-ary heap.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::min::MinDaryHeap;
+    /// let mut heap = MinDaryHeap::<_, 4>::new();
+    /// heap.push(3);
+    /// heap.push(5);
+    /// heap.push(1);
+    ///
+    /// assert_eq!(heap.len(), 3);
+    /// assert_eq!(heap.peek(), Some(&1));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The expected cost of `push`, averaged over every possible ordering of
+    /// the elements being pushed, and over a sufficiently large number of
+    /// pushes, is *O*(1). See [`DaryHeap::push`] for a more detailed analysis.
+    ///
+    /// [`DaryHeap::push`]: crate::DaryHeap::push
+    pub fn push(&mut self, item: T) {
+        self.heap.push(Reverse(item));
+    }
+
+    /// Consumes the `MinDaryHeap` and returns a vector in sorted
+    /// (ascending) order.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::min::MinDaryHeap;
+    ///
+    /// let mut heap = MinDaryHeap::<_, 3>::from(vec![1, 2, 4, 5, 7]);
+    /// heap.push(6);
+    /// heap.push(3);
+    ///
+    /// let vec = heap.into_sorted_vec();
+    /// assert_eq!(vec, [1, 2, 3, 4, 5, 6, 7]);
+    /// ```
+    #[must_use = "`self` will be dropped if the result is not used"]
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .rev()
+            .map(|Reverse(item)| item)
+            .collect()
+    }
+
+    /// Moves all the elements of `other` into `self`, leaving `other` empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::min::MinDaryHeap;
+    ///
+    /// let mut a = MinDaryHeap::<_, 3>::from(vec![-10, 1, 2, 3, 3]);
+    /// let mut b = MinDaryHeap::<_, 3>::from(vec![-20, 5, 43]);
+    ///
+    /// a.append(&mut b);
+    ///
+    /// assert_eq!(a.into_sorted_vec(), [-20, -10, 1, 2, 3, 3, 5, 43]);
+    /// assert!(b.is_empty());
+    /// ```
+    pub fn append(&mut self, other: &mut Self) {
+        self.heap.append(&mut other.heap);
+    }
+}
+
+impl<T, const D: usize> MinDaryHeap<T, D> {
+    /// Returns an iterator visiting all values in the underlying vector, in
+    /// arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::min::MinDaryHeap;
+    /// let heap = MinDaryHeap::<_, 3>::from(vec![1, 2, 3, 4]);
+    ///
+    /// // Print 1, 2, 3, 4 in arbitrary order
+    /// for x in heap.iter() {
+    ///     println!("{}", x);
+    /// }
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            iter: self.heap.iter(),
+        }
+    }
+
+    /// Returns the smallest item in the *d*-ary heap, or `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::min::MinDaryHeap;
+    /// let mut heap = MinDaryHeap::<_, 2>::new();
+    /// assert_eq!(heap.peek(), None);
+    ///
+    /// heap.push(1);
+    /// heap.push(5);
+    /// heap.push(2);
+    /// assert_eq!(heap.peek(), Some(&1));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Cost is *O*(1) in the worst case.
+    #[must_use]
+    pub fn peek(&self) -> Option<&T> {
+        self.heap.peek().map(|item| &item.0)
+    }
+
+    /// Returns the number of elements the *d*-ary heap can hold without reallocating.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.heap.capacity()
+    }
+
+    /// Reserves the minimum capacity for at least `additional` elements more than
+    /// the current length. See [`DaryHeap::reserve_exact`] for more information.
+    ///
+    /// [`DaryHeap::reserve_exact`]: crate::DaryHeap::reserve_exact
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity overflows [`usize`].
+    pub fn reserve_exact(&mut self, additional: usize) {
+        self.heap.reserve_exact(additional);
+    }
+
+    /// Reserves capacity for at least `additional` elements more than the
+    /// current length. See [`DaryHeap::reserve`] for more information.
+    ///
+    /// [`DaryHeap::reserve`]: crate::DaryHeap::reserve
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity overflows [`usize`].
+    pub fn reserve(&mut self, additional: usize) {
+        self.heap.reserve(additional);
+    }
+
+    /// Discards as much additional capacity as possible.
+    pub fn shrink_to_fit(&mut self) {
+        self.heap.shrink_to_fit();
+    }
+
+    /// Consumes the `MinDaryHeap` and returns the underlying vector
+    /// in arbitrary order.
+    #[must_use = "`self` will be dropped if the result is not used"]
+    pub fn into_vec(self) -> Vec<T> {
+        self.into_iter().collect()
+    }
+
+    /// Returns the length of the *d*-ary heap.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Checks if the *d*-ary heap is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Clears the *d*-ary heap, returning an iterator over the removed elements
+    /// in arbitrary order. If the iterator is dropped before being fully
+    /// consumed, it drops the remaining elements in arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::min::MinDaryHeap;
+    /// let mut heap = MinDaryHeap::<_, 4>::from(vec![1, 3]);
+    ///
+    /// assert!(!heap.is_empty());
+    ///
+    /// for x in heap.drain() {
+    ///     println!("{}", x);
+    /// }
+    ///
+    /// assert!(heap.is_empty());
+    /// ```
+    #[inline]
+    pub fn drain(&mut self) -> Drain<'_, T> {
+        Drain {
+            iter: self.heap.drain(),
+        }
+    }
+
+    /// Drops all items from the *d*-ary heap.
+    pub fn clear(&mut self) {
+        self.heap.clear();
+    }
+}
+
+/// An iterator over the elements of a `MinDaryHeap`.
+///
+/// This `struct` is created by [`MinDaryHeap::iter()`]. See its
+/// documentation for more.
+///
+/// [`iter`]: MinDaryHeap::iter
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Iter<'a, T: 'a> {
+    iter: crate::Iter<'a, Reverse<T>>,
+}
+
+impl<T> Default for Iter<'_, T> {
+    /// Creates an empty `min::Iter`.
+    fn default() -> Self {
+        Iter {
+            iter: Default::default(),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Iter<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Iter").field(&self.clone()).finish()
+    }
+}
+
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Iter {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        self.iter.next().map(|item| &item.0)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a T> {
+        self.iter.next_back().map(|item| &item.0)
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+/// An owning iterator over the elements of a `MinDaryHeap`.
+///
+/// This `struct` is created by [`MinDaryHeap::into_iter()`]
+/// (provided by the [`IntoIterator`] trait). See its documentation for more.
+///
+/// [`into_iter`]: MinDaryHeap::into_iter
+#[derive(Clone)]
+pub struct IntoIter<T> {
+    iter: crate::IntoIter<Reverse<T>>,
+}
+
+impl<T: fmt::Debug> fmt::Debug for IntoIter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IntoIter").field(&self.iter).finish()
+    }
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.iter.next().map(|Reverse(item)| item)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        self.iter.next_back().map(|Reverse(item)| item)
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+/// A draining iterator over the elements of a `MinDaryHeap`.
+///
+/// This `struct` is created by [`MinDaryHeap::drain()`]. See its
+/// documentation for more.
+///
+/// [`drain`]: MinDaryHeap::drain
+#[derive(Debug)]
+pub struct Drain<'a, T: 'a> {
+    iter: crate::Drain<'a, Reverse<T>>,
+}
+
+impl<T> Iterator for Drain<'_, T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.iter.next().map(|Reverse(item)| item)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for Drain<'_, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        self.iter.next_back().map(|Reverse(item)| item)
+    }
+}
+
+impl<T> ExactSizeIterator for Drain<'_, T> {}
+
+impl<T> FusedIterator for Drain<'_, T> {}
+
+impl<T: Ord, const D: usize> From<Vec<T>> for MinDaryHeap<T, D> {
+    /// Converts a `Vec<T>` into a `MinDaryHeap<T, D>`.
+    ///
+    /// This conversion has *O*(*n*) time complexity.
+    fn from(vec: Vec<T>) -> MinDaryHeap<T, D> {
+        vec.into_iter().collect()
+    }
+}
+
+impl<T: Ord, const D: usize, const N: usize> From<[T; N]> for MinDaryHeap<T, D> {
+    /// ```
+    /// use dary_heap::min::MinDaryHeap;
+    ///
+    /// let mut h1 = MinDaryHeap::<_, 3>::from([1, 4, 2, 3]);
+    /// let mut h2: MinDaryHeap<_, 3> = vec![1, 4, 2, 3].into();
+    /// while let Some((a, b)) = h1.pop().zip(h2.pop()) {
+    ///     assert_eq!(a, b);
+    /// }
+    /// ```
+    fn from(arr: [T; N]) -> Self {
+        // See `From<[T; N]>` for `DaryHeap` on why `IntoIter::new` is used.
+        #[allow(deprecated)]
+        core::array::IntoIter::new(arr).collect()
+    }
+}
+
+impl<T, const D: usize> From<MinDaryHeap<T, D>> for Vec<T> {
+    /// Converts a `MinDaryHeap<T, D>` into a `Vec<T>`.
+    ///
+    /// This conversion has *O*(*n*) time complexity.
+    fn from(heap: MinDaryHeap<T, D>) -> Vec<T> {
+        heap.into_vec()
+    }
+}
+
+impl<T: Ord, const D: usize> FromIterator<T> for MinDaryHeap<T, D> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> MinDaryHeap<T, D> {
+        MinDaryHeap {
+            heap: iter.into_iter().map(Reverse).collect(),
+        }
+    }
+}
+
+impl<T, const D: usize> IntoIterator for MinDaryHeap<T, D> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    /// Creates a consuming iterator, that is, one that moves each value out of
+    /// the *d*-ary heap in arbitrary order.
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter {
+            iter: self.heap.into_iter(),
+        }
+    }
+}
+
+impl<'a, T, const D: usize> IntoIterator for &'a MinDaryHeap<T, D> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<T: Ord, const D: usize> Extend<T> for MinDaryHeap<T, D> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.heap.extend(iter.into_iter().map(Reverse));
+    }
+}
+
+impl<'a, T: 'a + Ord + Copy, const D: usize> Extend<&'a T> for MinDaryHeap<T, D> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().cloned());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{seq::SliceRandom, thread_rng};
+
+    #[test]
+    fn pop_ascending() {
+        let mut data: Vec<_> = (0..100).collect();
+        data.shuffle(&mut thread_rng());
+
+        let mut heap = MinDaryHeap::<_, 3>::from(data[..50].to_vec());
+        heap.extend(&data[50..]);
+        assert_eq!(heap.iter().min(), Some(&0));
+        for i in 0..100 {
+            assert_eq!(heap.peek(), Some(&i));
+            assert_eq!(heap.pop(), Some(i));
+        }
+        assert_eq!(heap.pop(), None);
+    }
+}
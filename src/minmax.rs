@@ -0,0 +1,590 @@
+//! A double-ended priority queue implemented with a *d*-ary min-max heap.
+//!
+//! The [`MinMaxDaryHeap`] type in this module gives access to both the
+//! smallest and the greatest item. In a min-max heap, the levels of the tree
+//! alternate between min levels and max levels. An item on a min level is
+//! smaller than or equal to all its descendants and an item on a max level is
+//! greater than or equal to all its descendants. The root is on a min level,
+//! so the smallest item is the root and the greatest item is one of its
+//! children.
+
+use core::fmt;
+use core::iter::FromIterator;
+
+use alloc::vec::Vec;
+
+use crate::{IntoIter, Iter};
+
+/// A binary min-max heap (*d* = 2).
+pub type MinMaxBinaryHeap<T> = MinMaxDaryHeap<T, 2>;
+
+/// A ternary min-max heap (*d* = 3).
+pub type MinMaxTernaryHeap<T> = MinMaxDaryHeap<T, 3>;
+
+/// A quaternary min-max heap (*d* = 4).
+pub type MinMaxQuaternaryHeap<T> = MinMaxDaryHeap<T, 4>;
+
+/// A double-ended priority queue implemented with a *d*-ary min-max heap.
+///
+/// Both the smallest and the greatest item can be peeked in *O*(1) time and
+/// popped in *O*(log(*n*)) time. Otherwise, it behaves like [`DaryHeap`]; see
+/// its documentation for more information.
+///
+/// [`DaryHeap`]: crate::DaryHeap
+///
+/// # Examples
+///
+/// ```
+/// use dary_heap::minmax::MinMaxDaryHeap;
+///
+/// let mut heap = MinMaxDaryHeap::<_, 4>::new();
+/// heap.push(3);
+/// heap.push(1);
+/// heap.push(5);
+/// heap.push(4);
+///
+/// assert_eq!(heap.peek_min(), Some(&1));
+/// assert_eq!(heap.peek_max(), Some(&5));
+/// assert_eq!(heap.pop_max(), Some(5));
+/// assert_eq!(heap.pop_min(), Some(1));
+/// assert_eq!(heap.pop_max(), Some(4));
+/// assert_eq!(heap.pop_max(), Some(3));
+/// assert_eq!(heap.pop_min(), None);
+/// ```
+pub struct MinMaxDaryHeap<T, const D: usize> {
+    data: Vec<T>,
+}
+
+impl<T: Clone, const D: usize> Clone for MinMaxDaryHeap<T, D> {
+    fn clone(&self) -> Self {
+        MinMaxDaryHeap {
+            data: self.data.clone(),
+        }
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        self.data.clone_from(&source.data);
+    }
+}
+
+impl<T: Ord, const D: usize> Default for MinMaxDaryHeap<T, D> {
+    /// Creates an empty `MinMaxDaryHeap<T, D>`.
+    #[inline]
+    fn default() -> MinMaxDaryHeap<T, D> {
+        MinMaxDaryHeap::new()
+    }
+}
+
+impl<T: fmt::Debug, const D: usize> fmt::Debug for MinMaxDaryHeap<T, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Ord, const D: usize> MinMaxDaryHeap<T, D> {
+    /// Creates an empty `MinMaxDaryHeap`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::minmax::MinMaxDaryHeap;
+    /// let mut heap = MinMaxDaryHeap::<_, 3>::new();
+    /// heap.push(4);
+    /// ```
+    #[must_use]
+    pub fn new() -> MinMaxDaryHeap<T, D> {
+        MinMaxDaryHeap { data: Vec::new() }
+    }
+
+    /// Creates an empty `MinMaxDaryHeap` with at least the specified capacity.
+    ///
+    /// The *d*-ary heap will be able to hold at least `capacity` elements without
+    /// reallocating. This method is allowed to allocate for more elements than
+    /// `capacity`. If `capacity` is 0, the *d*-ary heap will not allocate.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::minmax::MinMaxDaryHeap;
+    /// let mut heap = MinMaxDaryHeap::<_, 3>::with_capacity(10);
+    /// heap.push(4);
+    /// ```
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> MinMaxDaryHeap<T, D> {
+        MinMaxDaryHeap {
+            data: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Pushes an item onto the min-max heap.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::minmax::MinMaxDaryHeap;
+    /// let mut heap = MinMaxDaryHeap::<_, 2>::new();
+    /// heap.push(3);
+    /// heap.push(5);
+    /// heap.push(1);
+    ///
+    /// assert_eq!(heap.len(), 3);
+    /// assert_eq!(heap.peek_min(), Some(&1));
+    /// assert_eq!(heap.peek_max(), Some(&5));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The worst case cost of `push` on a heap containing *n* elements is
+    /// *O*(log(*n*)).
+    pub fn push(&mut self, item: T) {
+        self.data.push(item);
+        self.bubble_up(self.data.len() - 1);
+    }
+
+    /// Removes the smallest item from the min-max heap and returns it, or
+    /// `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::minmax::MinMaxDaryHeap;
+    /// let mut heap = MinMaxDaryHeap::<_, 2>::from(vec![1, 3, 2]);
+    ///
+    /// assert_eq!(heap.pop_min(), Some(1));
+    /// assert_eq!(heap.pop_min(), Some(2));
+    /// assert_eq!(heap.pop_min(), Some(3));
+    /// assert_eq!(heap.pop_min(), None);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The worst case cost of `pop_min` on a heap containing *n* elements is
+    /// *O*(*d*<sup>2</sup> log(*n*) / log(*d*)).
+    pub fn pop_min(&mut self) -> Option<T> {
+        self.remove_at(0)
+    }
+
+    /// Removes the greatest item from the min-max heap and returns it, or
+    /// `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::minmax::MinMaxDaryHeap;
+    /// let mut heap = MinMaxDaryHeap::<_, 2>::from(vec![1, 3, 2]);
+    ///
+    /// assert_eq!(heap.pop_max(), Some(3));
+    /// assert_eq!(heap.pop_max(), Some(2));
+    /// assert_eq!(heap.pop_max(), Some(1));
+    /// assert_eq!(heap.pop_max(), None);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The worst case cost of `pop_max` on a heap containing *n* elements is
+    /// *O*(*d*<sup>2</sup> log(*n*) / log(*d*)).
+    pub fn pop_max(&mut self) -> Option<T> {
+        let pos = self.max_pos()?;
+        self.remove_at(pos)
+    }
+
+    /// Returns the index of the greatest item, or `None` if the heap is empty.
+    fn max_pos(&self) -> Option<usize> {
+        match self.data.len() {
+            0 => None,
+            1 => Some(0),
+            len => {
+                let end = len.min(D + 1);
+                Some(self.extreme_in(1, end, &|a, b| a > b))
+            }
+        }
+    }
+
+    /// Returns the index in `start..end` of the item for which `better` holds
+    /// against all others.
+    fn extreme_in(&self, start: usize, end: usize, better: &impl Fn(&T, &T) -> bool) -> usize {
+        let mut best = start;
+        for i in start + 1..end {
+            if better(&self.data[i], &self.data[best]) {
+                best = i;
+            }
+        }
+        best
+    }
+
+    /// Removes the item at `pos` and restores the min-max heap property.
+    fn remove_at(&mut self, pos: usize) -> Option<T> {
+        if pos >= self.data.len() {
+            return None;
+        }
+        let item = self.data.swap_remove(pos);
+        if pos < self.data.len() {
+            self.trickle_down(pos);
+        }
+        Some(item)
+    }
+
+    /// Moves the item at `pos` up to its place, assuming the rest of the
+    /// heap is valid.
+    fn bubble_up(&mut self, mut pos: usize) {
+        assert_ne!(D, 0, "Arity should be greater than zero");
+        if pos == 0 {
+            return;
+        }
+        let parent = (pos - 1) / D;
+        let min_level = is_min_level::<D>(pos);
+        let wrong_side = if min_level {
+            self.data[pos] > self.data[parent]
+        } else {
+            self.data[pos] < self.data[parent]
+        };
+        if wrong_side {
+            self.data.swap(pos, parent);
+            pos = parent;
+        }
+        // After the swap, `pos` is on a level of the opposite kind.
+        let min = min_level != wrong_side;
+        while pos > D {
+            let grandparent = ((pos - 1) / D - 1) / D;
+            let out_of_order = if min {
+                self.data[pos] < self.data[grandparent]
+            } else {
+                self.data[pos] > self.data[grandparent]
+            };
+            if !out_of_order {
+                break;
+            }
+            self.data.swap(pos, grandparent);
+            pos = grandparent;
+        }
+    }
+
+    /// Moves the item at `pos` down to its place, assuming its subtrees are
+    /// valid min-max heaps.
+    fn trickle_down(&mut self, mut pos: usize) {
+        assert_ne!(D, 0, "Arity should be greater than zero");
+        let min = is_min_level::<D>(pos);
+        let better = |a: &T, b: &T| if min { a < b } else { a > b };
+        let len = self.data.len();
+        loop {
+            let first_child = D * pos + 1;
+            if first_child >= len {
+                return;
+            }
+            // Grandchildren of `pos` are contiguous and follow the children.
+            let children_end = len.min(first_child + D);
+            let grand_start = D * first_child + 1;
+            let grand_end = len.min(D * (first_child + D - 1) + D + 1);
+            let mut best = self.extreme_in(first_child, children_end, &better);
+            if grand_start < grand_end {
+                let grand = self.extreme_in(grand_start, grand_end, &better);
+                if better(&self.data[grand], &self.data[best]) {
+                    best = grand;
+                }
+            }
+            if !better(&self.data[best], &self.data[pos]) {
+                return;
+            }
+            self.data.swap(best, pos);
+            if best < grand_start {
+                // `best` is a child, which has no descendants that need to
+                // be compared with the moved item.
+                return;
+            }
+            let parent = (best - 1) / D;
+            if better(&self.data[parent], &self.data[best]) {
+                self.data.swap(best, parent);
+            }
+            pos = best;
+        }
+    }
+
+    /// Consumes the `MinMaxDaryHeap` and returns a vector in sorted
+    /// (ascending) order.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::minmax::MinMaxDaryHeap;
+    ///
+    /// let mut heap = MinMaxDaryHeap::<_, 3>::from(vec![1, 2, 4, 5, 7]);
+    /// heap.push(6);
+    /// heap.push(3);
+    ///
+    /// let vec = heap.into_sorted_vec();
+    /// assert_eq!(vec, [1, 2, 3, 4, 5, 6, 7]);
+    /// ```
+    #[must_use = "`self` will be dropped if the result is not used"]
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        let mut vec = self.data;
+        vec.sort();
+        vec
+    }
+
+    fn rebuild(&mut self) {
+        assert_ne!(D, 0, "Arity should be greater than zero");
+        if self.data.len() < 2 {
+            return;
+        }
+        let mut n = (self.data.len() - 1) / D + 1;
+        while n > 0 {
+            n -= 1;
+            self.trickle_down(n);
+        }
+    }
+}
+
+impl<T, const D: usize> MinMaxDaryHeap<T, D> {
+    /// Returns the smallest item in the min-max heap, or `None` if it is
+    /// empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::minmax::MinMaxDaryHeap;
+    /// let mut heap = MinMaxDaryHeap::<_, 2>::new();
+    /// assert_eq!(heap.peek_min(), None);
+    ///
+    /// heap.push(1);
+    /// heap.push(5);
+    /// heap.push(2);
+    /// assert_eq!(heap.peek_min(), Some(&1));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Cost is *O*(1) in the worst case.
+    #[must_use]
+    pub fn peek_min(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    /// Returns the greatest item in the min-max heap, or `None` if it is
+    /// empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::minmax::MinMaxDaryHeap;
+    /// let mut heap = MinMaxDaryHeap::<_, 2>::new();
+    /// assert_eq!(heap.peek_max(), None);
+    ///
+    /// heap.push(1);
+    /// heap.push(5);
+    /// heap.push(2);
+    /// assert_eq!(heap.peek_max(), Some(&5));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Cost is *O*(*d*) in the worst case.
+    #[must_use]
+    pub fn peek_max(&self) -> Option<&T>
+    where
+        T: Ord,
+    {
+        match self.data.len() {
+            0 => None,
+            1 => self.data.first(),
+            len => self.data[1..len.min(D + 1)].iter().max(),
+        }
+    }
+
+    /// Returns an iterator visiting all values in the underlying vector, in
+    /// arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::minmax::MinMaxDaryHeap;
+    /// let heap = MinMaxDaryHeap::<_, 3>::from(vec![1, 2, 3, 4]);
+    ///
+    /// // Print 1, 2, 3, 4 in arbitrary order
+    /// for x in heap.iter() {
+    ///     println!("{}", x);
+    /// }
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            iter: self.data.iter(),
+        }
+    }
+
+    /// Returns the number of elements the heap can hold without reallocating.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.data.capacity()
+    }
+
+    /// Reserves capacity for at least `additional` elements more than the
+    /// current length. See [`DaryHeap::reserve`] for more information.
+    ///
+    /// [`DaryHeap::reserve`]: crate::DaryHeap::reserve
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity overflows [`usize`].
+    pub fn reserve(&mut self, additional: usize) {
+        self.data.reserve(additional);
+    }
+
+    /// Returns a slice of all values in the underlying vector, in arbitrary
+    /// order.
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        self.data.as_slice()
+    }
+
+    /// Consumes the `MinMaxDaryHeap` and returns the underlying vector
+    /// in arbitrary order.
+    #[must_use = "`self` will be dropped if the result is not used"]
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+
+    /// Returns the length of the min-max heap.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Checks if the min-max heap is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drops all items from the min-max heap.
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+}
+
+/// Returns whether index `pos` is on a min level of a *d*-ary min-max heap.
+fn is_min_level<const D: usize>(mut pos: usize) -> bool {
+    let mut min = true;
+    while pos > 0 {
+        pos = (pos - 1) / D;
+        min = !min;
+    }
+    min
+}
+
+impl<T: Ord, const D: usize> From<Vec<T>> for MinMaxDaryHeap<T, D> {
+    /// Converts a `Vec<T>` into a `MinMaxDaryHeap<T, D>`.
+    ///
+    /// This conversion happens in-place, and has *O*(*n*) time complexity.
+    fn from(vec: Vec<T>) -> MinMaxDaryHeap<T, D> {
+        let mut heap = MinMaxDaryHeap { data: vec };
+        heap.rebuild();
+        heap
+    }
+}
+
+impl<T, const D: usize> From<MinMaxDaryHeap<T, D>> for Vec<T> {
+    /// Converts a `MinMaxDaryHeap<T, D>` into a `Vec<T>`.
+    ///
+    /// This conversion requires no data movement or allocation, and has
+    /// constant time complexity.
+    fn from(heap: MinMaxDaryHeap<T, D>) -> Vec<T> {
+        heap.data
+    }
+}
+
+impl<T: Ord, const D: usize> FromIterator<T> for MinMaxDaryHeap<T, D> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> MinMaxDaryHeap<T, D> {
+        MinMaxDaryHeap::from(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl<T, const D: usize> IntoIterator for MinMaxDaryHeap<T, D> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    /// Creates a consuming iterator, that is, one that moves each value out of
+    /// the min-max heap in arbitrary order.
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter {
+            iter: self.data.into_iter(),
+        }
+    }
+}
+
+impl<'a, T, const D: usize> IntoIterator for &'a MinMaxDaryHeap<T, D> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<T: Ord, const D: usize> Extend<T> for MinMaxDaryHeap<T, D> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        iter.for_each(move |elem| self.push(elem));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::collections::VecDeque;
+    use rand::{seq::SliceRandom, thread_rng, Rng};
+
+    fn check<const D: usize>() {
+        let mut rng = thread_rng();
+        let mut data: Vec<_> = (0..200).collect();
+        data.shuffle(&mut rng);
+
+        let mut heap = MinMaxDaryHeap::<_, D>::from(data[..100].to_vec());
+        let mut reference: Vec<_> = data[..100].to_vec();
+        reference.sort_unstable();
+        let mut reference: VecDeque<_> = reference.into();
+        for &x in &data[100..] {
+            heap.push(x);
+            let pos = reference.binary_search(&x).unwrap_err();
+            reference.insert(pos, x);
+            if rng.gen() {
+                assert_eq!(heap.pop_min(), reference.pop_front());
+            } else {
+                assert_eq!(heap.pop_max(), reference.pop_back());
+            }
+            assert_eq!(heap.peek_min(), reference.front());
+            assert_eq!(heap.peek_max(), reference.back());
+        }
+        while !reference.is_empty() {
+            assert_eq!(heap.pop_max(), reference.pop_back());
+            assert_eq!(heap.pop_min(), reference.pop_front());
+        }
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn matches_sorted_reference() {
+        check::<1>();
+        check::<2>();
+        check::<3>();
+        check::<4>();
+        check::<7>();
+    }
+}
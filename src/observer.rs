@@ -0,0 +1,414 @@
+//! Heaps that report their operations to an observer.
+//!
+//! The [`ObservedDaryHeap`] type in this module is a *d*-ary heap that calls
+//! the methods of a [`HeapObserver`] whenever an item is pushed, popped, or
+//! sifted to restore the heap property. This can be used for tracing,
+//! auditing the heap invariant, or collecting custom metrics, without
+//! changing the heap algorithms themselves.
+
+use core::fmt;
+use core::marker::PhantomData;
+use core::mem::swap;
+
+use alloc::vec::Vec;
+
+use crate::compare::Natural;
+use crate::sift::{self, Arity};
+use crate::Iter;
+
+/// An observer of the operations of an [`ObservedDaryHeap`].
+///
+/// All methods have empty default implementations, so an observer only needs
+/// to implement the events it is interested in. The methods take `&self`, so
+/// observers that keep state should use interior mutability, e.g. a [`Cell`].
+///
+/// [`Cell`]: core::cell::Cell
+///
+/// # Examples
+///
+/// An observer that audits the heap invariant after every operation:
+///
+/// ```
+/// use dary_heap::observer::{HeapObserver, ObservedDaryHeap};
+/// use dary_heap::slice::is_heap;
+///
+/// struct Auditor;
+///
+/// impl HeapObserver<i32> for Auditor {
+///     fn on_push(&self, heap: &[i32], _pos: usize) {
+///         assert!(is_heap::<_, 4>(heap));
+///     }
+///
+///     fn on_pop(&self, heap: &[i32], _item: &i32) {
+///         assert!(is_heap::<_, 4>(heap));
+///     }
+/// }
+///
+/// let mut heap = ObservedDaryHeap::<_, 4, _>::with_observer(Auditor);
+/// heap.push(3);
+/// heap.push(5);
+/// assert_eq!(heap.pop(), Some(5));
+/// ```
+pub trait HeapObserver<T> {
+    /// Called after an item was pushed onto the heap, with the items of the
+    /// heap and the final position of the pushed item.
+    #[inline]
+    fn on_push(&self, heap: &[T], pos: usize) {
+        let _ = (heap, pos);
+    }
+
+    /// Called after `item` was popped from the heap, with the remaining items
+    /// of the heap.
+    #[inline]
+    fn on_pop(&self, heap: &[T], item: &T) {
+        let _ = (heap, item);
+    }
+
+    /// Called after an item was moved from position `from` to position `to`
+    /// to restore the heap property. This is also called if the item stayed
+    /// in place, in which case `from == to`.
+    #[inline]
+    fn on_sift(&self, from: usize, to: usize) {
+        let _ = (from, to);
+    }
+}
+
+/// Observer that ignores all operations.
+impl<T> HeapObserver<T> for () {}
+
+impl<T, O: HeapObserver<T> + ?Sized> HeapObserver<T> for &O {
+    #[inline]
+    fn on_push(&self, heap: &[T], pos: usize) {
+        (**self).on_push(heap, pos);
+    }
+
+    #[inline]
+    fn on_pop(&self, heap: &[T], item: &T) {
+        (**self).on_pop(heap, item);
+    }
+
+    #[inline]
+    fn on_sift(&self, from: usize, to: usize) {
+        (**self).on_sift(from, to);
+    }
+}
+
+/// Arity of a heap that forwards the sifts to an observer.
+struct Observed<'a, T, const D: usize, O> {
+    observer: &'a O,
+    marker: PhantomData<fn(&T)>,
+}
+
+impl<'a, T, const D: usize, O> Observed<'a, T, D, O> {
+    fn new(observer: &'a O) -> Observed<'a, T, D, O> {
+        Observed {
+            observer,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T, const D: usize, O> Clone for Observed<'_, T, D, O> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, const D: usize, O> Copy for Observed<'_, T, D, O> {}
+
+impl<T, const D: usize, O: HeapObserver<T>> Arity for Observed<'_, T, D, O> {
+    #[inline(always)]
+    fn get(self) -> usize {
+        D
+    }
+
+    #[inline]
+    fn sifted(self, from: usize, to: usize, _levels: usize) {
+        self.observer.on_sift(from, to);
+    }
+}
+
+/// A priority queue implemented with a *d*-ary heap, reporting its operations
+/// to a [`HeapObserver`].
+///
+/// This will be a max-heap. Apart from calling the observer, it behaves like
+/// [`DaryHeap`]; see its documentation for more information.
+///
+/// [`DaryHeap`]: crate::DaryHeap
+///
+/// # Examples
+///
+/// ```
+/// use dary_heap::observer::{HeapObserver, ObservedDaryHeap};
+/// use std::cell::Cell;
+///
+/// #[derive(Default)]
+/// struct MoveCounter(Cell<usize>);
+///
+/// impl<T> HeapObserver<T> for MoveCounter {
+///     fn on_sift(&self, from: usize, to: usize) {
+///         if from != to {
+///             self.0.set(self.0.get() + 1);
+///         }
+///     }
+/// }
+///
+/// let mut heap = ObservedDaryHeap::<_, 2, _>::with_observer(MoveCounter::default());
+/// heap.push(1);
+/// heap.push(2);
+/// heap.push(3);
+///
+/// // Both 2 and 3 were moved to the root.
+/// assert_eq!(heap.observer().0.get(), 2);
+/// ```
+#[derive(Clone)]
+pub struct ObservedDaryHeap<T, const D: usize, O> {
+    data: Vec<T>,
+    observer: O,
+}
+
+impl<T: fmt::Debug, const D: usize, O> fmt::Debug for ObservedDaryHeap<T, D, O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Ord, const D: usize, O: HeapObserver<T> + Default> Default for ObservedDaryHeap<T, D, O> {
+    /// Creates an empty `ObservedDaryHeap<T, D, O>` with the default observer.
+    #[inline]
+    fn default() -> ObservedDaryHeap<T, D, O> {
+        ObservedDaryHeap::with_observer(O::default())
+    }
+}
+
+impl<T: Ord, const D: usize, O: HeapObserver<T>> ObservedDaryHeap<T, D, O> {
+    /// Creates an empty `ObservedDaryHeap` reporting to `observer`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::observer::ObservedDaryHeap;
+    /// let mut heap = ObservedDaryHeap::<_, 3, _>::with_observer(());
+    /// heap.push(4);
+    /// ```
+    #[must_use]
+    pub fn with_observer(observer: O) -> ObservedDaryHeap<T, D, O> {
+        ObservedDaryHeap {
+            data: Vec::new(),
+            observer,
+        }
+    }
+
+    /// Converts a vector into an `ObservedDaryHeap` reporting to `observer`.
+    ///
+    /// This conversion happens in-place, and has *O*(*n*) time complexity.
+    /// The sifts needed to rearrange the vector are reported to the observer.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::observer::ObservedDaryHeap;
+    /// let heap = ObservedDaryHeap::<_, 2, _>::from_vec_with(vec![1, 3, 2], ());
+    /// assert_eq!(heap.peek(), Some(&3));
+    /// ```
+    #[must_use]
+    pub fn from_vec_with(vec: Vec<T>, observer: O) -> ObservedDaryHeap<T, D, O> {
+        let mut heap = ObservedDaryHeap {
+            data: vec,
+            observer,
+        };
+        let arity = Observed::<T, D, O>::new(&heap.observer);
+        sift::rebuild(&mut heap.data, arity, &Natural);
+        heap
+    }
+
+    /// Removes the greatest item from the *d*-ary heap and returns it, or `None` if it
+    /// is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::observer::ObservedDaryHeap;
+    /// let mut heap = ObservedDaryHeap::<_, 2, _>::from_vec_with(vec![1, 3], ());
+    ///
+    /// assert_eq!(heap.pop(), Some(3));
+    /// assert_eq!(heap.pop(), Some(1));
+    /// assert_eq!(heap.pop(), None);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The worst case cost of `pop` on a heap containing *n* elements is
+    /// *O*(log(*n*)), plus the cost of the calls to the observer.
+    pub fn pop(&mut self) -> Option<T> {
+        self.data.pop().map(|mut item| {
+            if !self.data.is_empty() {
+                swap(&mut item, &mut self.data[0]);
+                let arity = Observed::<T, D, O>::new(&self.observer);
+                // SAFETY: !self.data.is_empty() means that self.len() > 0
+                unsafe { sift::sift_down_to_bottom(&mut self.data, arity, &Natural, 0) };
+            }
+            self.observer.on_pop(&self.data, &item);
+            item
+        })
+    }
+
+    /// Pushes an item onto the *d*-ary heap.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::observer::ObservedDaryHeap;
+    /// let mut heap = ObservedDaryHeap::<_, 4, _>::with_observer(());
+    /// heap.push(3);
+    /// heap.push(5);
+    /// heap.push(1);
+    ///
+    /// assert_eq!(heap.len(), 3);
+    /// assert_eq!(heap.peek(), Some(&5));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The expected cost of `push`, averaged over every possible ordering of
+    /// the elements being pushed, and over a sufficiently large number of
+    /// pushes, is *O*(1), plus the cost of the calls to the observer. See
+    /// [`DaryHeap::push`] for a more detailed analysis.
+    ///
+    /// [`DaryHeap::push`]: crate::DaryHeap::push
+    pub fn push(&mut self, item: T) {
+        let old_len = self.data.len();
+        self.data.push(item);
+        let arity = Observed::<T, D, O>::new(&self.observer);
+        // SAFETY: Since we pushed a new item it means that
+        //  old_len = self.len() - 1 < self.len()
+        let pos = unsafe { sift::sift_up(&mut self.data, arity, &Natural, 0, old_len) };
+        self.observer.on_push(&self.data, pos);
+    }
+}
+
+impl<T, const D: usize, O> ObservedDaryHeap<T, D, O> {
+    /// Returns a reference to the observer of the *d*-ary heap.
+    #[must_use]
+    pub fn observer(&self) -> &O {
+        &self.observer
+    }
+
+    /// Returns an iterator visiting all values in the underlying vector, in
+    /// arbitrary order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            iter: self.data.iter(),
+        }
+    }
+
+    /// Returns the greatest item in the *d*-ary heap, or `None` if it is empty.
+    ///
+    /// # Time complexity
+    ///
+    /// Cost is *O*(1) in the worst case.
+    #[must_use]
+    pub fn peek(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    /// Returns a slice of all values in the underlying vector, in arbitrary
+    /// order.
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        self.data.as_slice()
+    }
+
+    /// Consumes the `ObservedDaryHeap` and returns the underlying vector
+    /// in arbitrary order, and the observer.
+    #[must_use = "`self` will be dropped if the result is not used"]
+    pub fn into_parts(self) -> (Vec<T>, O) {
+        (self.data, self.observer)
+    }
+
+    /// Returns the length of the *d*-ary heap.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Checks if the *d*-ary heap is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drops all items from the *d*-ary heap.
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+}
+
+impl<'a, T, const D: usize, O> IntoIterator for &'a ObservedDaryHeap<T, D, O> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::slice::is_heap;
+    use core::cell::RefCell;
+    use rand::{seq::SliceRandom, thread_rng};
+
+    #[derive(Default)]
+    struct Log {
+        pushes: RefCell<Vec<usize>>,
+        pops: RefCell<Vec<i32>>,
+        sifts: RefCell<Vec<(usize, usize)>>,
+    }
+
+    impl HeapObserver<i32> for Log {
+        fn on_push(&self, heap: &[i32], pos: usize) {
+            assert!(is_heap::<_, 3>(heap));
+            self.pushes.borrow_mut().push(pos);
+        }
+
+        fn on_pop(&self, heap: &[i32], item: &i32) {
+            assert!(is_heap::<_, 3>(heap));
+            self.pops.borrow_mut().push(*item);
+        }
+
+        fn on_sift(&self, from: usize, to: usize) {
+            self.sifts.borrow_mut().push((from, to));
+        }
+    }
+
+    #[test]
+    fn reports_operations() {
+        let mut data: Vec<_> = (0..100).collect();
+        data.shuffle(&mut thread_rng());
+
+        let log = Log::default();
+        let mut heap = ObservedDaryHeap::<_, 3, _>::with_observer(&log);
+        for &x in &data {
+            heap.push(x);
+        }
+        assert_eq!(log.pushes.borrow().len(), 100);
+        assert_eq!(log.sifts.borrow().len(), 100);
+        for (&(from, to), &pos) in log.sifts.borrow().iter().zip(&*log.pushes.borrow()) {
+            assert!(to <= from);
+            assert_eq!(to, pos);
+        }
+
+        while heap.pop().is_some() {}
+        assert_eq!(*log.pops.borrow(), (0..100).rev().collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,122 @@
+//! Strategies for generating *d*-ary heaps with [`proptest`].
+//!
+//! The [`heap`] function creates a strategy for heaps with items drawn from
+//! another strategy, and [`DaryHeap`] implements [`Arbitrary`] so that
+//! `any::<DaryHeap<T, D>>()` can be used directly. Generated heaps shrink by
+//! shrinking the underlying vector of items and rebuilding the heap from it.
+//!
+//! [`proptest`]: https://docs.rs/proptest
+//!
+//! # Examples
+//!
+//! ```
+//! use dary_heap::QuaternaryHeap;
+//! use proptest::prelude::*;
+//! use proptest::test_runner::TestRunner;
+//!
+//! let strategy = dary_heap::proptest::heap::<_, 4>(any::<u32>(), 0..100);
+//! TestRunner::default()
+//!     .run(&strategy, |heap: QuaternaryHeap<u32>| {
+//!         let max = heap.iter().max().copied();
+//!         prop_assert_eq!(heap.peek().copied(), max);
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! ```
+
+use ::proptest::arbitrary::{any_with, Arbitrary};
+use ::proptest::collection::{vec, SizeRange, VecStrategy, VecValueTree};
+use ::proptest::strategy::{NewTree, Strategy, ValueTree};
+use ::proptest::test_runner::TestRunner;
+
+use crate::DaryHeap;
+
+/// Creates a strategy to generate [`DaryHeap`]s containing items drawn from
+/// `element` and with a size range given by `size`.
+pub fn heap<T, const D: usize>(element: T, size: impl Into<SizeRange>) -> DaryHeapStrategy<T, D>
+where
+    T: Strategy,
+    T::Value: Ord,
+{
+    DaryHeapStrategy(vec(element, size))
+}
+
+/// Strategy to create [`DaryHeap`]s with a length in a certain range.
+///
+/// This `struct` is created by the [`heap`] function. See its documentation
+/// for more.
+#[derive(Clone, Debug)]
+pub struct DaryHeapStrategy<T: Strategy, const D: usize>(VecStrategy<T>);
+
+impl<T, const D: usize> Strategy for DaryHeapStrategy<T, D>
+where
+    T: Strategy,
+    T::Value: Ord,
+{
+    type Tree = DaryHeapValueTree<T::Tree, D>;
+    type Value = DaryHeap<T::Value, D>;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        self.0.new_tree(runner).map(DaryHeapValueTree)
+    }
+}
+
+/// [`ValueTree`] corresponding to [`DaryHeapStrategy`].
+#[derive(Clone, Debug)]
+pub struct DaryHeapValueTree<T: ValueTree, const D: usize>(VecValueTree<T>);
+
+impl<T, const D: usize> ValueTree for DaryHeapValueTree<T, D>
+where
+    T: ValueTree,
+    T::Value: Ord,
+{
+    type Value = DaryHeap<T::Value, D>;
+
+    fn current(&self) -> Self::Value {
+        self.0.current().into()
+    }
+
+    fn simplify(&mut self) -> bool {
+        self.0.simplify()
+    }
+
+    fn complicate(&mut self) -> bool {
+        self.0.complicate()
+    }
+}
+
+impl<T: Arbitrary + Ord, const D: usize> Arbitrary for DaryHeap<T, D> {
+    type Parameters = (SizeRange, T::Parameters);
+    type Strategy = DaryHeapStrategy<T::Strategy, D>;
+
+    fn arbitrary_with((size, params): Self::Parameters) -> Self::Strategy {
+        heap(any_with::<T>(params), size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::proptest::arbitrary::any;
+    use ::proptest::prop_assert;
+    use ::proptest::test_runner::TestError;
+
+    #[test]
+    fn valid_and_shrinks() {
+        TestRunner::default()
+            .run(&any::<DaryHeap<u8, 3>>(), |heap| {
+                heap.assert_valid_state();
+                Ok(())
+            })
+            .unwrap();
+
+        let result = TestRunner::default().run(&heap::<_, 3>(any::<u8>(), 0..100), |heap| {
+            prop_assert!(heap.iter().all(|&x| x < 100));
+            Ok(())
+        });
+        match result {
+            Err(TestError::Fail(_, heap)) => assert_eq!(heap.into_vec(), [100]),
+            _ => panic!("expected a failure"),
+        }
+    }
+}
@@ -0,0 +1,221 @@
+//! Parallel algorithms for *d*-ary heaps using [`rayon`].
+//!
+//! [`rayon`]: https://docs.rs/rayon
+
+use ::rayon::iter::plumbing::{Consumer, ProducerCallback, UnindexedConsumer};
+use ::rayon::prelude::*;
+
+use alloc::vec::Vec;
+
+use crate::compare::Natural;
+use crate::{sift, DaryHeap};
+
+/// Minimum number of items for which a heap is built in parallel.
+const PARALLEL_THRESHOLD: usize = 1 << 15;
+
+/// Minimum number of sift-downs performed by a single parallel task.
+const MIN_TASK_LEN: usize = 1 << 10;
+
+/// A pointer to the data of a heap that can be shared between threads.
+///
+/// Only used to sift down disjoint subtrees concurrently.
+#[derive(Clone, Copy)]
+struct SyncPtr<T>(*mut T);
+
+// SAFETY: Every thread accesses a disjoint set of items through the pointer,
+//  which is equivalent to sending a mutable reference to those items.
+unsafe impl<T: Send> Send for SyncPtr<T> {}
+unsafe impl<T: Send> Sync for SyncPtr<T> {}
+
+impl<T: Ord + Send, const D: usize> DaryHeap<T, D> {
+    /// Converts a `Vec<T>` into a `DaryHeap<T, D>`, using multiple threads
+    /// for large vectors.
+    ///
+    /// The heap is built level by level from the bottom up. The subtrees
+    /// rooted at the nodes of a level are disjoint, so the nodes of each level
+    /// are sifted down in parallel. The small top levels are finished on the
+    /// current thread.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::QuaternaryHeap;
+    /// let heap = QuaternaryHeap::from_vec_parallel((0..100_000).collect());
+    ///
+    /// assert_eq!(heap.len(), 100_000);
+    /// assert_eq!(heap.peek(), Some(&99_999));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The cost of `from_vec_parallel` is *O*(*n*), like converting the vector
+    /// with [`From`], but the work is spread over the threads of the current
+    /// [`rayon`] thread pool.
+    ///
+    /// [`rayon`]: https://docs.rs/rayon
+    pub fn from_vec_parallel(vec: Vec<T>) -> DaryHeap<T, D> {
+        Self::check_len(vec.len());
+        let mut heap = DaryHeap {
+            data: vec,
+            #[cfg(feature = "stats")]
+            stats: crate::stats::HeapStats::new(),
+        };
+        heap.par_rebuild();
+        heap
+    }
+
+    fn par_rebuild(&mut self) {
+        let len = self.data.len();
+        if len < PARALLEL_THRESHOLD || D < 2 {
+            self.rebuild();
+            return;
+        }
+
+        // Start indices of the levels of the heap that contain internal nodes.
+        let last_internal = (len - 2) / D;
+        let mut levels = Vec::new();
+        let mut start = 0;
+        while start <= last_internal {
+            levels.push(start);
+            start = D * start + 1;
+        }
+
+        let ptr = SyncPtr(self.data.as_mut_ptr());
+        let mut end = last_internal + 1;
+        for &start in levels.iter().rev() {
+            let sift_down = |pos| {
+                let SyncPtr(ptr) = ptr;
+                // SAFETY: start <= pos < end <= len, and ptr is valid for
+                //  len items as self.data is borrowed for the whole loop.
+                //  Nodes on the same level have disjoint subtrees, so
+                //  concurrent calls never access the same item.
+                unsafe { sift::sift_down_range_raw(ptr, sift::Const::<D>, &Natural, pos, len) };
+            };
+            if end - start < 2 * MIN_TASK_LEN {
+                (start..end).rev().for_each(sift_down);
+            } else {
+                (start..end)
+                    .into_par_iter()
+                    .with_min_len(MIN_TASK_LEN)
+                    .for_each(sift_down);
+            }
+            end = start;
+        }
+    }
+}
+
+impl<T: Send, const D: usize> DaryHeap<T, D> {
+    /// Clears the *d*-ary heap, returning a parallel iterator over the removed
+    /// elements in arbitrary order. If the iterator is dropped before being
+    /// fully consumed, it drops the remaining elements.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::TernaryHeap;
+    /// use rayon::prelude::*;
+    /// let mut heap = TernaryHeap::from(vec![1, 3, 5]);
+    ///
+    /// assert_eq!(heap.par_drain().sum::<i32>(), 9);
+    /// assert!(heap.is_empty());
+    /// ```
+    pub fn par_drain(&mut self) -> ParDrain<'_, T> {
+        ParDrain {
+            inner: self.data.par_drain(..),
+        }
+    }
+}
+
+/// A parallel draining iterator over the elements of a `DaryHeap`.
+///
+/// This `struct` is created by [`DaryHeap::par_drain()`]. See its
+/// documentation for more.
+#[derive(Debug)]
+pub struct ParDrain<'a, T: Send> {
+    inner: ::rayon::vec::Drain<'a, T>,
+}
+
+impl<T: Send> ParallelIterator for ParDrain<'_, T> {
+    type Item = T;
+
+    fn drive_unindexed<C: UnindexedConsumer<T>>(self, consumer: C) -> C::Result {
+        self.inner.drive_unindexed(consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.inner.len())
+    }
+}
+
+impl<T: Send> IndexedParallelIterator for ParDrain<'_, T> {
+    fn drive<C: Consumer<T>>(self, consumer: C) -> C::Result {
+        self.inner.drive(consumer)
+    }
+
+    fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    fn with_producer<CB: ProducerCallback<T>>(self, callback: CB) -> CB::Output {
+        self.inner.with_producer(callback)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{thread_rng, Rng};
+
+    fn check<const D: usize>() {
+        // A unary heap is a sorted list, which is not built in parallel.
+        let nelem = if cfg!(miri) || D == 1 {
+            100
+        } else {
+            4 * PARALLEL_THRESHOLD
+        };
+        let mut rng = thread_rng();
+        let data: Vec<u32> = (0..nelem).map(|_| rng.gen()).collect();
+        let heap = DaryHeap::<_, D>::from_vec_parallel(data.clone());
+        heap.assert_valid_state();
+
+        let mut sorted = data;
+        sorted.sort_unstable();
+        assert_eq!(heap.into_sorted_vec(), sorted);
+    }
+
+    #[test]
+    fn par_drain() {
+        let mut heap: DaryHeap<_, 4> = (0..1000).collect();
+        let mut items: Vec<_> = heap.par_drain().collect();
+        assert!(heap.is_empty());
+        items.sort_unstable();
+        assert_eq!(items, (0..1000).collect::<Vec<_>>());
+
+        heap.extend(0..1000);
+        assert_eq!(heap.par_drain().take(10).count(), 10);
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn from_vec_parallel() {
+        check::<1>();
+        check::<2>();
+        check::<3>();
+        check::<4>();
+        check::<7>();
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds the maximum length")]
+    fn from_vec_parallel_beyond_max_len() {
+        // The maximum length is `PARALLEL_THRESHOLD`, so a longer vector is built
+        // in parallel.
+        const HUGE_D: usize = usize::MAX / PARALLEL_THRESHOLD;
+        let vec: Vec<u8> = (0..=PARALLEL_THRESHOLD).map(|_| 0).collect();
+        let _ = DaryHeap::<_, HUGE_D>::from_vec_parallel(vec);
+    }
+}
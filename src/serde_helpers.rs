@@ -0,0 +1,322 @@
+//! Alternative (de)serialization modes for use with `#[serde(with = "...")]`.
+//!
+//! By default, a [`DaryHeap`] is serialized as a sequence of its items and
+//! rebuilt after deserialization, so any sequence can be deserialized. The
+//! modules in this module change that behavior for individual fields.
+//!
+//! [`DaryHeap`]: crate::DaryHeap
+
+/// Deserialize a heap only if the sequence already is a valid *d*-ary heap.
+///
+/// Instead of rebuilding the heap after deserialization, the sequence is
+/// checked to satisfy the heap property, and an error is returned otherwise.
+/// This detects corrupted or mismatched snapshots, and avoids moving any items
+/// for data that was serialized from a heap with the same arity.
+/// Serialization is unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use dary_heap::TernaryHeap;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Snapshot {
+///     #[serde(with = "dary_heap::serde_helpers::validated")]
+///     queue: TernaryHeap<u32>,
+/// }
+/// ```
+pub mod validated {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use alloc::vec::Vec;
+
+    use crate::{slice, DaryHeap};
+
+    /// Serializes the heap as a sequence of its items.
+    pub fn serialize<S, T, const D: usize>(
+        heap: &DaryHeap<T, D>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: Serialize,
+    {
+        heap.serialize(serializer)
+    }
+
+    /// Deserializes a sequence of items that must already be a valid *d*-ary
+    /// heap.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sequence cannot be deserialized, or if it is
+    /// not a valid *d*-ary heap.
+    pub fn deserialize<'de, De, T, const D: usize>(
+        deserializer: De,
+    ) -> Result<DaryHeap<T, D>, De::Error>
+    where
+        De: Deserializer<'de>,
+        T: Ord + Deserialize<'de>,
+    {
+        let data = Vec::deserialize(deserializer)?;
+        if slice::is_heap::<T, D>(&data) {
+            Ok(DaryHeap {
+                data,
+                #[cfg(feature = "stats")]
+                stats: crate::stats::HeapStats::new(),
+            })
+        } else {
+            Err(De::Error::custom(format_args!(
+                "sequence is not a valid heap with arity {}",
+                D
+            )))
+        }
+    }
+}
+
+/// Serialize a heap together with its arity, and reject a different arity
+/// when deserializing.
+///
+/// The heap is serialized as a struct with an `arity` field containing *d*
+/// and a `data` field containing the sequence of items. Deserialization
+/// returns an error if the arity does not match the arity of the target type,
+/// so a snapshot of, e.g., a [`TernaryHeap`] cannot silently be loaded into an
+/// [`OctonaryHeap`].
+///
+/// [`TernaryHeap`]: crate::TernaryHeap
+/// [`OctonaryHeap`]: crate::OctonaryHeap
+///
+/// # Examples
+///
+/// ```
+/// use dary_heap::TernaryHeap;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Snapshot {
+///     #[serde(with = "dary_heap::serde_helpers::with_arity")]
+///     queue: TernaryHeap<u32>,
+/// }
+/// ```
+pub mod with_arity {
+    use core::fmt;
+    use core::marker::PhantomData;
+
+    use serde::de::{Error, MapAccess, SeqAccess, Visitor};
+    use serde::ser::SerializeStruct;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use alloc::vec::Vec;
+
+    use crate::DaryHeap;
+
+    const FIELDS: &[&str] = &["arity", "data"];
+
+    /// Serializes the heap as a struct containing its arity and items.
+    pub fn serialize<S, T, const D: usize>(
+        heap: &DaryHeap<T, D>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: Serialize,
+    {
+        let mut state = serializer.serialize_struct("DaryHeap", FIELDS.len())?;
+        state.serialize_field("arity", &D)?;
+        state.serialize_field("data", &heap.data)?;
+        state.end()
+    }
+
+    /// Deserializes a struct containing the arity and items of a heap.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the struct cannot be deserialized, or if its arity
+    /// does not match *d*.
+    pub fn deserialize<'de, De, T, const D: usize>(
+        deserializer: De,
+    ) -> Result<DaryHeap<T, D>, De::Error>
+    where
+        De: Deserializer<'de>,
+        T: Ord + Deserialize<'de>,
+    {
+        deserializer.deserialize_struct("DaryHeap", FIELDS, HeapVisitor(PhantomData))
+    }
+
+    enum Field {
+        Arity,
+        Data,
+    }
+
+    impl<'de> Deserialize<'de> for Field {
+        fn deserialize<De: Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+            struct FieldVisitor;
+
+            impl<'de> Visitor<'de> for FieldVisitor {
+                type Value = Field;
+
+                fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    f.write_str("`arity` or `data`")
+                }
+
+                fn visit_str<E: Error>(self, value: &str) -> Result<Field, E> {
+                    match value {
+                        "arity" => Ok(Field::Arity),
+                        "data" => Ok(Field::Data),
+                        _ => Err(E::unknown_field(value, FIELDS)),
+                    }
+                }
+            }
+
+            deserializer.deserialize_identifier(FieldVisitor)
+        }
+    }
+
+    struct HeapVisitor<T, const D: usize>(PhantomData<T>);
+
+    impl<T, const D: usize> HeapVisitor<T, D> {
+        fn build<E: Error>(arity: usize, data: Vec<T>) -> Result<DaryHeap<T, D>, E>
+        where
+            T: Ord,
+        {
+            if arity == D {
+                Ok(DaryHeap::from(data))
+            } else {
+                Err(E::custom(format_args!(
+                    "heap has arity {}, expected arity {}",
+                    arity, D
+                )))
+            }
+        }
+    }
+
+    impl<'de, T: Ord + Deserialize<'de>, const D: usize> Visitor<'de> for HeapVisitor<T, D> {
+        type Value = DaryHeap<T, D>;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("struct DaryHeap")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let arity = seq
+                .next_element()?
+                .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+            let data = seq
+                .next_element()?
+                .ok_or_else(|| A::Error::invalid_length(1, &self))?;
+            Self::build(arity, data)
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+            let mut arity = None;
+            let mut data = None;
+            while let Some(key) = map.next_key()? {
+                match key {
+                    Field::Arity => {
+                        if arity.is_some() {
+                            return Err(A::Error::duplicate_field("arity"));
+                        }
+                        arity = Some(map.next_value()?);
+                    }
+                    Field::Data => {
+                        if data.is_some() {
+                            return Err(A::Error::duplicate_field("data"));
+                        }
+                        data = Some(map.next_value()?);
+                    }
+                }
+            }
+            let arity = arity.ok_or_else(|| A::Error::missing_field("arity"))?;
+            let data = data.ok_or_else(|| A::Error::missing_field("data"))?;
+            Self::build(arity, data)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DaryHeap;
+    use alloc::vec;
+    use serde::Serialize;
+    use serde::{Deserialize, Deserializer};
+    use serde_test::Token::{Seq, SeqEnd, Str, Struct, StructEnd, I32, U64};
+
+    #[derive(Debug)]
+    struct Validated(DaryHeap<i32, 2>);
+
+    impl<'de> Deserialize<'de> for Validated {
+        fn deserialize<De: Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+            validated::deserialize(deserializer).map(Validated)
+        }
+    }
+
+    impl PartialEq for Validated {
+        fn eq(&self, other: &Self) -> bool {
+            self.0.as_slice() == other.0.as_slice()
+        }
+    }
+
+    #[test]
+    fn validated() {
+        let heap = Validated(DaryHeap::from(vec![3, 1, 2]));
+        serde_test::assert_de_tokens(
+            &heap,
+            &[Seq { len: Some(3) }, I32(3), I32(1), I32(2), SeqEnd],
+        );
+        serde_test::assert_de_tokens_error::<Validated>(
+            &[Seq { len: Some(3) }, I32(1), I32(3), I32(2), SeqEnd],
+            "sequence is not a valid heap with arity 2",
+        );
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Snapshot<const D: usize> {
+        #[serde(with = "with_arity")]
+        queue: DaryHeap<i32, D>,
+    }
+
+    impl<const D: usize> PartialEq for Snapshot<D> {
+        fn eq(&self, other: &Self) -> bool {
+            self.queue.as_slice() == other.queue.as_slice()
+        }
+    }
+
+    #[test]
+    fn with_arity() {
+        let tokens = |arity| {
+            [
+                Struct {
+                    name: "Snapshot",
+                    len: 1,
+                },
+                Str("queue"),
+                Struct {
+                    name: "DaryHeap",
+                    len: 2,
+                },
+                Str("arity"),
+                U64(arity),
+                Str("data"),
+                Seq { len: Some(3) },
+                I32(3),
+                I32(1),
+                I32(2),
+                SeqEnd,
+                StructEnd,
+                StructEnd,
+            ]
+        };
+        let snapshot = Snapshot::<3> {
+            queue: DaryHeap::from(vec![3, 1, 2]),
+        };
+        serde_test::assert_tokens(&snapshot, &tokens(3));
+        // The error is raised before the ends of the structs are read.
+        serde_test::assert_de_tokens_error::<Snapshot<8>>(
+            &tokens(3)[..12],
+            "heap has arity 3, expected arity 8",
+        );
+    }
+}
@@ -0,0 +1,707 @@
+//! Heap algorithms operating on slices, shared by the heap types of this
+//! crate.
+//!
+//! The implementations of sift_up and sift_down use unsafe blocks in
+//! order to move an element out of the vector (leaving behind a
+//! hole), shift along the others and move the removed element back into the
+//! vector at the final location of the hole.
+//! The `Hole` type is used to represent this, and make sure
+//! the hole is filled back at the end of its scope, even on panic.
+//! Using a hole reduces the constant factor compared to using swaps,
+//! which involves twice as many moves.
+//!
+//! Most of this module is derived from `binary_heap/mod.rs` in the standard
+//! library. `scripts/sync.sh` applies upstream changes to that file to
+//! `src/lib.rs` and lists the hunks that need to be ported here by hand.
+
+#[cfg(all(feature = "prefetch", target_arch = "aarch64"))]
+use core::arch::asm;
+#[cfg(all(feature = "prefetch", target_arch = "x86", target_feature = "sse"))]
+use core::arch::x86::{_mm_prefetch, _MM_HINT_T0};
+#[cfg(all(feature = "prefetch", target_arch = "x86_64"))]
+use core::arch::x86_64::{_mm_prefetch, _MM_HINT_T0};
+use core::marker::PhantomData;
+#[cfg(any(feature = "alloc", feature = "allocator-api2"))]
+use core::mem::size_of;
+use core::mem::ManuallyDrop;
+use core::{ptr, slice};
+
+use crate::compare::Compare;
+
+/// Arity of a heap, either known at compile time ([`Const`]) or only at run
+/// time (`usize`).
+pub(crate) trait Arity: Copy {
+    /// Returns the arity *d*.
+    fn get(self) -> usize;
+
+    /// Called after an element was sifted from `from` to `to`, passing
+    /// through `levels` levels of the heap. This allows instrumenting the
+    /// functions in this module.
+    #[inline(always)]
+    fn sifted(self, from: usize, to: usize, levels: usize) {
+        let _ = (from, to, levels);
+    }
+}
+
+/// Arity known at compile time, allowing specialized code for small arities.
+#[derive(Clone, Copy)]
+pub(crate) struct Const<const D: usize>;
+
+impl<const D: usize> Arity for Const<D> {
+    #[inline(always)]
+    fn get(self) -> usize {
+        D
+    }
+}
+
+impl Arity for usize {
+    #[inline(always)]
+    fn get(self) -> usize {
+        self
+    }
+}
+
+/// Comparator that may find the greatest of a group of siblings faster than
+/// by comparing them one by one.
+///
+/// With the `unstable_nightly` feature, this is specialized to use SIMD
+/// instructions for [`Natural`] on primitive integers and groups of 8 or 16
+/// siblings. Groups of 4 siblings are faster to compare one by one.
+///
+/// [`Natural`]: crate::compare::Natural
+pub(crate) trait MaxOf<T> {
+    /// Returns the index of the last of the greatest items, or `None` to fall
+    /// back to comparing the items one by one.
+    fn max_index(&self, items: &[T]) -> Option<usize>;
+}
+
+macro_rules! max_of_fallback {
+    ($($default:ident)?) => {
+        impl<T, C: Compare<T>> MaxOf<T> for C {
+            #[inline(always)]
+            $($default)? fn max_index(&self, _items: &[T]) -> Option<usize> {
+                None
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "unstable_nightly"))]
+max_of_fallback!();
+#[cfg(feature = "unstable_nightly")]
+max_of_fallback!(default);
+
+#[cfg(feature = "unstable_nightly")]
+mod simd {
+    use core::simd::cmp::SimdPartialEq;
+    use core::simd::num::{SimdInt, SimdUint};
+    use core::simd::Simd;
+
+    use super::MaxOf;
+    use crate::compare::Natural;
+
+    macro_rules! max_of_simd {
+        ($($t:ty)*) => {$(
+            impl MaxOf<$t> for Natural {
+                #[inline]
+                fn max_index(&self, items: &[$t]) -> Option<usize> {
+                    let mask = match items.len() {
+                        8 => {
+                            let items = Simd::<$t, 8>::from_slice(items);
+                            items.simd_eq(Simd::splat(items.reduce_max())).to_bitmask()
+                        }
+                        16 => {
+                            let items = Simd::<$t, 16>::from_slice(items);
+                            items.simd_eq(Simd::splat(items.reduce_max())).to_bitmask()
+                        }
+                        _ => return None,
+                    };
+                    Some(63 - mask.leading_zeros() as usize)
+                }
+            }
+        )*};
+    }
+
+    max_of_simd! { u8 u16 u32 u64 usize i8 i16 i32 i64 isize }
+}
+
+/// Take an element at `pos` and move it up the heap, while its parent is
+/// smaller, but not beyond `start`. Returns the new position of the element.
+///
+/// # Safety
+///
+/// The caller must guarantee that `pos < data.len()`.
+pub(crate) unsafe fn sift_up<T, A: Arity, C: Compare<T>>(
+    data: &mut [T],
+    arity: A,
+    cmp: &C,
+    start: usize,
+    pos: usize,
+) -> usize {
+    let d = arity.get();
+    assert_ne!(d, 0, "Arity should be greater than zero");
+    // Take out the value at `pos` and create a hole.
+    // SAFETY: The caller guarantees that pos < data.len()
+    let mut hole = Hole::new(data, pos);
+
+    while hole.pos() > start {
+        let parent = (hole.pos() - 1) / d;
+
+        // SAFETY: hole.pos() > start >= 0, which means hole.pos() > 0
+        //  and so hole.pos() - 1 can't underflow.
+        //  This guarantees that parent < hole.pos() so
+        //  it's a valid index and also != hole.pos().
+        if cmp.compares_le(hole.element(), hole.get(parent)) {
+            break;
+        }
+
+        // SAFETY: Same as above
+        hole.move_to(parent);
+    }
+
+    arity.sifted(pos, hole.pos(), hole.levels());
+    hole.pos()
+}
+
+/// Take an element at `pos` and move it down the heap,
+/// while its children are larger. Returns the new position of the element.
+///
+/// # Safety
+///
+/// The caller must guarantee that `pos < end <= data.len()`.
+pub(crate) unsafe fn sift_down_range<T, A: Arity, C: Compare<T>>(
+    data: &mut [T],
+    arity: A,
+    cmp: &C,
+    pos: usize,
+    end: usize,
+) -> usize {
+    debug_assert!(end <= data.len());
+    // SAFETY: The caller guarantees that pos < end <= data.len().
+    sift_down_range_raw(data.as_mut_ptr(), arity, cmp, pos, end)
+}
+
+/// Like [`sift_down_range`], but operating on a raw pointer so that disjoint
+/// subtrees of the same heap can be sifted down concurrently. Returns the new
+/// position of the element.
+///
+/// # Safety
+///
+/// The caller must guarantee that `pos < end`, that `ptr` is valid for reads
+/// and writes of `end` elements, and that the elements in the subtree rooted
+/// at `pos` are not accessed by anything else during the call.
+pub(crate) unsafe fn sift_down_range_raw<T, A: Arity, C: Compare<T>>(
+    ptr: *mut T,
+    arity: A,
+    cmp: &C,
+    pos: usize,
+    end: usize,
+) -> usize {
+    let d = arity.get();
+    assert_ne!(d, 0, "Arity should be greater than zero");
+    // SAFETY: The caller guarantees that pos < end and that ptr is valid for
+    //  end elements.
+    let mut hole = Hole::from_raw(ptr, end, pos);
+    let mut child = d * hole.pos() + 1;
+
+    // Loop invariant: child == d * hole.pos() + 1.
+    while child <= end.saturating_sub(d) {
+        hole.prefetch_children_of(arity, child);
+
+        // compare with the greatest of the d children
+        // SAFETY: child < end - d + 1 < data.len() and
+        //  child + d - 1 < end <= data.len(), so they're valid indexes.
+        //  child + i == d * hole.pos() + 1 + i != hole.pos() for i >= 0
+        child = hole.max_sibling(arity, cmp, child);
+
+        // if we are already in order, stop.
+        // SAFETY: child is now either the old child or valid sibling
+        //  We already proven that all are < data.len() and != hole.pos()
+        if cmp.compares_le(hole.get(child), hole.element()) {
+            arity.sifted(pos, hole.pos(), hole.levels());
+            return hole.pos();
+        }
+
+        // SAFETY: same as above.
+        hole.move_to(child);
+        child = d * hole.pos() + 1;
+    }
+
+    child = hole.max_sibling_to(arity, cmp, child, end);
+    // SAFETY: && short circuit, which means that in the
+    //  second condition it's already true that child < end <= data.len().
+    if child < end && cmp.compares_lt(hole.element(), hole.get(child)) {
+        // SAFETY: child is already proven to be a valid index and
+        //  child == d * hole.pos() + 1 != hole.pos().
+        hole.move_to(child);
+    }
+    arity.sifted(pos, hole.pos(), hole.levels());
+    hole.pos()
+}
+
+/// # Safety
+///
+/// The caller must guarantee that `pos < data.len()`.
+pub(crate) unsafe fn sift_down<T, A: Arity, C: Compare<T>>(
+    data: &mut [T],
+    arity: A,
+    cmp: &C,
+    pos: usize,
+) -> usize {
+    let len = data.len();
+    // SAFETY: pos < len is guaranteed by the caller and
+    //  obviously len = data.len() <= data.len().
+    sift_down_range(data, arity, cmp, pos, len)
+}
+
+/// Take an element at `pos` and move it all the way down the heap,
+/// then sift it up to its position.
+///
+/// Note: This is faster when the element is known to be large / should
+/// be closer to the bottom.
+///
+/// # Safety
+///
+/// The caller must guarantee that `pos < data.len()`.
+pub(crate) unsafe fn sift_down_to_bottom<T, A: Arity, C: Compare<T>>(
+    data: &mut [T],
+    arity: A,
+    cmp: &C,
+    mut pos: usize,
+) {
+    let d = arity.get();
+    assert_ne!(d, 0, "Arity should be greater than zero");
+    let end = data.len();
+    let start = pos;
+
+    // SAFETY: The caller guarantees that pos < data.len().
+    let mut hole = Hole::new(data, pos);
+    let mut child = d * hole.pos() + 1;
+
+    // Loop invariant: child == d * hole.pos() + 1.
+    while child <= end.saturating_sub(d) {
+        hole.prefetch_children_of(arity, child);
+
+        // SAFETY: child < end - d + 1 < data.len() and
+        //  child + d - 1 < end <= data.len(), so they're valid indexes.
+        //  child + i == d * hole.pos() + 1 + i != hole.pos() for i >= 0
+        child = hole.max_sibling(arity, cmp, child);
+
+        // SAFETY: Same as above
+        hole.move_to(child);
+        child = d * hole.pos() + 1;
+    }
+
+    child = hole.max_sibling_to(arity, cmp, child, end);
+    if child < end {
+        // SAFETY: child < end <= data.len(), so it's a valid index
+        //  and child == d * hole.pos() + i != hole.pos() for i >= 1
+        hole.move_to(child);
+    }
+    pos = hole.pos();
+    arity.sifted(start, pos, hole.levels());
+    drop(hole);
+
+    // SAFETY: pos is the position in the hole and was already proven
+    //  to be a valid index.
+    sift_up(data, arity, cmp, start, pos);
+}
+
+/// Rebuild assuming data[0..start] is still a proper heap.
+#[cfg(any(feature = "alloc", feature = "allocator-api2"))]
+pub(crate) fn rebuild_tail<T, A: Arity, C: Compare<T>>(
+    data: &mut [T],
+    arity: A,
+    cmp: &C,
+    start: usize,
+) {
+    let d = arity.get();
+    assert_ne!(d, 0, "Arity should be greater than zero");
+
+    if start == data.len() {
+        return;
+    }
+
+    let tail_len = data.len() - start;
+
+    // The fix for this lint (usize::BITS) requires Rust 1.53.0, but the
+    // MSRV is currently 1.51.0.
+    #[allow(clippy::manual_bits)]
+    #[inline(always)]
+    fn log2_fast(x: usize) -> usize {
+        8 * size_of::<usize>() - (x.leading_zeros() as usize) - 1
+    }
+
+    // `rebuild` takes O(data.len()) operations
+    // and about n * data.len() comparisons in the worst case
+    // with n = d / (d - 1)
+    // while repeating `sift_up` takes O(tail_len * log(start)) operations
+    // and about 1 * tail_len * log(start) comparisons in the worst case,
+    // assuming start >= tail_len. For larger heaps, the crossover point
+    // no longer follows this reasoning and was determined empirically.
+    let better_to_rebuild = if start < tail_len {
+        true
+    } else if data.len() <= 4096 / d {
+        d * data.len() < (d - 1) * tail_len * log2_fast(start)
+    } else {
+        d * data.len() < (d - 1) * tail_len * 13usize.saturating_sub(d)
+    };
+
+    if better_to_rebuild {
+        rebuild(data, arity, cmp);
+    } else {
+        for i in start..data.len() {
+            // SAFETY: The index `i` is always less than data.len().
+            unsafe { sift_up(data, arity, cmp, 0, i) };
+        }
+    }
+}
+
+/// Rearrange `data` into a heap.
+pub(crate) fn rebuild<T, A: Arity, C: Compare<T>>(data: &mut [T], arity: A, cmp: &C) {
+    let d = arity.get();
+    assert_ne!(d, 0, "Arity should be greater than zero");
+    if data.len() < 2 {
+        return;
+    }
+    // Start at the last element that has children, which is the parent of
+    // the last element.
+    //
+    // Sifting all the way down and then up like `pop` does would save one
+    // comparison per level, but was slower for every arity in benchmarks:
+    // most elements only move down a level or two, so the extra moves and
+    // the comparisons on the way back up outweigh the savings.
+    let parents = (data.len() - 2) / d + 1;
+    let mut n = parents;
+    if d >= 8 {
+        // With a high arity, most parents only have leaves as children, so
+        // their elements move down at most one level. Handling these
+        // complete groups directly, by finding the greatest child and
+        // swapping it with the parent if needed, avoids the overhead of a
+        // general sift down. For lower arities this was not faster in
+        // benchmarks (see benches/rebuild.rs).
+        let leaf_parents = (parents + d - 2) / d;
+        // The last parent may have fewer than d children.
+        n -= 1;
+        // SAFETY: n < parents <= data.len().
+        unsafe { sift_down(data, arity, cmp, n) };
+        let ptr = data.as_mut_ptr();
+        while n > leaf_parents {
+            n -= 1;
+            let first = d * n + 1;
+            // SAFETY: n < parents - 1, so the group first..first + d of n is
+            //  complete and within data. The parent n < first is not in it.
+            unsafe {
+                let group = slice::from_raw_parts(ptr.add(first), arity.get());
+                let child = first + max_index(cmp, group);
+                if cmp.compares_lt(&*ptr.add(n), &*ptr.add(child)) {
+                    let parent = ptr::read(ptr.add(n));
+                    ptr::copy_nonoverlapping(ptr.add(child), ptr.add(n), 1);
+                    ptr::write(ptr.add(child), parent);
+                    arity.sifted(n, child, 1);
+                } else {
+                    arity.sifted(n, n, 0);
+                }
+            }
+        }
+    }
+    while n > 0 {
+        n -= 1;
+        // SAFETY: n < parents <= data.len().
+        unsafe { sift_down(data, arity, cmp, n) };
+    }
+}
+
+/// Returns the index of the last of the greatest items in a non-empty group.
+#[inline]
+fn max_index<T, C: Compare<T>>(cmp: &C, group: &[T]) -> usize {
+    if let Some(index) = cmp.max_index(group) {
+        return index;
+    }
+    let mut max = 0;
+    for i in 1..group.len() {
+        // SAFETY: max < i < group.len().
+        let le = unsafe { cmp.compares_le(group.get_unchecked(max), group.get_unchecked(i)) };
+        max = select(le, i, max);
+    }
+    max
+}
+
+/// Sort a slice that is a heap in ascending order.
+pub(crate) fn sort_heap<T, A: Arity, C: Compare<T>>(data: &mut [T], arity: A, cmp: &C) {
+    sort_heap_tail(data, arity, cmp, data.len());
+}
+
+/// Moves the `k` greatest elements of a heap to the end of `data` in
+/// ascending order. The elements before them remain a valid heap.
+pub(crate) fn sort_heap_tail<T, A: Arity, C: Compare<T>>(
+    data: &mut [T],
+    arity: A,
+    cmp: &C,
+    k: usize,
+) {
+    let stop = data.len().saturating_sub(k).max(1);
+    let mut end = data.len();
+    while end > stop {
+        end -= 1;
+        // SAFETY: `end` goes from `data.len() - 1` to `stop` >= 1 (both
+        //  included), so it's always a valid index to access.
+        //  It is safe to access index 0 (i.e. `ptr`), because
+        //  1 <= end < data.len(), which means data.len() >= 2.
+        unsafe {
+            let ptr = data.as_mut_ptr();
+            ptr::swap(ptr, ptr.add(end));
+        }
+        // SAFETY: `end` goes from `data.len() - 1` to `stop` >= 1 (both
+        //  included) so:
+        //  0 < 1 <= end <= data.len() - 1 < data.len()
+        //  Which means 0 < end and end < data.len().
+        unsafe { sift_down_range(data, arity, cmp, 0, end) };
+    }
+}
+
+/// Hole represents a hole in a slice i.e., an index without valid value
+/// (because it was moved from or duplicated).
+/// In drop, `Hole` will restore the slice by filling the hole
+/// position with the value that was originally removed.
+struct Hole<'a, T: 'a> {
+    ptr: *mut T,
+    len: usize,
+    elt: ManuallyDrop<T>,
+    pos: usize,
+    levels: usize,
+    marker: PhantomData<&'a mut [T]>,
+}
+
+impl<'a, T> Hole<'a, T> {
+    /// Creates a new `Hole` at index `pos`.
+    ///
+    /// Unsafe because pos must be within the data slice.
+    #[inline]
+    unsafe fn new(data: &'a mut [T], pos: usize) -> Self {
+        Hole::from_raw(data.as_mut_ptr(), data.len(), pos)
+    }
+
+    /// Creates a new `Hole` at index `pos` of the `len` elements at `ptr`.
+    ///
+    /// Unsafe because pos must be less than len, and ptr must be valid for
+    /// reads and writes of len elements for the lifetime of the hole.
+    #[inline]
+    unsafe fn from_raw(ptr: *mut T, len: usize, pos: usize) -> Self {
+        debug_assert!(pos < len);
+        // SAFE: pos should be inside the slice
+        let elt = ptr::read(ptr.add(pos));
+        Hole {
+            ptr,
+            len,
+            elt: ManuallyDrop::new(elt),
+            pos,
+            levels: 0,
+            marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the number of times the hole was moved.
+    #[inline]
+    fn levels(&self) -> usize {
+        self.levels
+    }
+
+    /// Returns a reference to the element removed.
+    #[inline]
+    fn element(&self) -> &T {
+        &self.elt
+    }
+
+    /// Returns a reference to the element at `index`.
+    ///
+    /// Unsafe because index must be within the data slice and not equal to pos.
+    #[inline]
+    unsafe fn get(&self, index: usize) -> &T {
+        debug_assert!(index != self.pos);
+        debug_assert!(index < self.len);
+        &*self.ptr.add(index)
+    }
+
+    /// Move hole to new location
+    ///
+    /// Unsafe because index must be within the data slice and not equal to pos.
+    #[inline]
+    unsafe fn move_to(&mut self, index: usize) {
+        debug_assert!(index != self.pos);
+        debug_assert!(index < self.len);
+        let ptr = self.ptr;
+        let index_ptr: *const _ = ptr.add(index);
+        let hole_ptr = ptr.add(self.pos);
+        ptr::copy_nonoverlapping(index_ptr, hole_ptr, 1);
+        self.pos = index;
+        self.levels += 1;
+    }
+
+    /// Hints the processor to fetch the children of the `d` siblings starting
+    /// at `first_sibling` into the cache, if the `prefetch` feature is
+    /// enabled. These are the elements compared after moving the hole to one
+    /// of the siblings, so fetching them early hides the memory latency of
+    /// the next level of large heaps.
+    #[inline(always)]
+    fn prefetch_children_of<A: Arity>(&self, arity: A, first_sibling: usize) {
+        let d = arity.get();
+        // The children of the siblings are the d * d elements starting here.
+        let first = first_sibling.wrapping_mul(d).wrapping_add(1);
+        let last = first.wrapping_add(d.wrapping_mul(d)).wrapping_sub(1);
+        self.prefetch(first);
+        self.prefetch(last);
+    }
+
+    /// Hints the processor to fetch the element at `index` into the cache,
+    /// if the `prefetch` feature is enabled and `index` is within the slice.
+    #[inline(always)]
+    fn prefetch(&self, index: usize) {
+        if !cfg!(feature = "prefetch") || index >= self.len {
+            return;
+        }
+        let ptr = self.ptr.wrapping_add(index) as *const i8;
+        #[cfg(all(
+            feature = "prefetch",
+            any(
+                all(target_arch = "x86", target_feature = "sse"),
+                target_arch = "x86_64"
+            )
+        ))]
+        // SAFETY: Prefetching has no observable effect and ptr is within the
+        //  slice.
+        unsafe {
+            _mm_prefetch(ptr, _MM_HINT_T0)
+        };
+        #[cfg(all(feature = "prefetch", target_arch = "aarch64"))]
+        // SAFETY: Prefetching has no observable effect and ptr is within the
+        //  slice.
+        unsafe {
+            asm!("prfm pldl1keep, [{}]", in(reg) ptr, options(nostack, preserves_flags, readonly))
+        };
+        let _ = ptr;
+    }
+}
+
+impl<'a, T> Hole<'a, T> {
+    /// Get largest element
+    ///
+    /// Unsafe because both elements must be within the data slice and not equal
+    /// to pos.
+    #[inline]
+    unsafe fn max<C: Compare<T>>(&self, cmp: &C, elem1: usize, elem2: usize) -> usize {
+        select(
+            cmp.compares_le(self.get(elem1), self.get(elem2)),
+            elem2,
+            elem1,
+        )
+    }
+
+    /// Get index of greatest of two siblings
+    ///
+    /// Unsafe because both siblings must be within the data slice and not equal
+    /// to pos.
+    #[inline]
+    unsafe fn max_sibling_pair<C: Compare<T>>(&self, cmp: &C, first_sibling: usize) -> usize {
+        first_sibling
+            + cmp.compares_le(self.get(first_sibling), self.get(first_sibling + 1)) as usize
+    }
+
+    /// Get index of greatest sibling
+    ///
+    /// Unsafe because all siblings must be within the data slice and not equal
+    /// to pos.
+    #[inline]
+    unsafe fn max_sibling<A: Arity, C: Compare<T>>(
+        &self,
+        arity: A,
+        cmp: &C,
+        first_sibling: usize,
+    ) -> usize {
+        // SAFETY: The caller guarantees that all siblings are within the data
+        //  slice and not equal to pos, so they are initialized.
+        let siblings = slice::from_raw_parts(self.ptr.add(first_sibling), arity.get());
+        if let Some(index) = cmp.max_index(siblings) {
+            return first_sibling + index;
+        }
+        let mut sibling = first_sibling;
+        match arity.get() {
+            2 => {
+                sibling = self.max_sibling_pair(cmp, sibling);
+            }
+            3 => {
+                let sibling_a = self.max_sibling_pair(cmp, sibling);
+                let sibling_b = sibling + 2;
+                sibling = self.max(cmp, sibling_a, sibling_b);
+            }
+            4 => {
+                let sibling_a = self.max_sibling_pair(cmp, sibling);
+                let sibling_b = self.max_sibling_pair(cmp, sibling + 2);
+                sibling = self.max(cmp, sibling_a, sibling_b);
+            }
+            d => {
+                for other_sibling in sibling + 1..sibling + d {
+                    let le = cmp.compares_le(self.get(sibling), self.get(other_sibling));
+                    sibling = select(le, other_sibling, sibling);
+                }
+            }
+        }
+        sibling
+    }
+
+    /// Get index of greatest sibling within range
+    ///
+    /// Unsafe because end must be the length of the data slice, last sibling
+    /// must be outside of the data slice and no sibling may be equal to pos.
+    /// It is allowed for first_sibling to be outside of the data slice.
+    #[inline]
+    unsafe fn max_sibling_to<A: Arity, C: Compare<T>>(
+        &self,
+        arity: A,
+        cmp: &C,
+        first_sibling: usize,
+        end: usize,
+    ) -> usize {
+        let mut sibling = first_sibling;
+        match arity.get() {
+            2 => {}
+            3 => {
+                if sibling + 1 < end {
+                    sibling = self.max_sibling_pair(cmp, sibling);
+                }
+            }
+            _ => {
+                for other_sibling in sibling + 1..end {
+                    let le = cmp.compares_le(self.get(sibling), self.get(other_sibling));
+                    sibling = select(le, other_sibling, sibling);
+                }
+            }
+        }
+        sibling
+    }
+}
+
+/// Returns `if_true` if `condition` holds and `if_false` otherwise, without
+/// branching. Branches on the outcome of comparisons between random items
+/// are often mispredicted, which is more expensive than computing both
+/// outcomes.
+#[inline(always)]
+fn select(condition: bool, if_true: usize, if_false: usize) -> usize {
+    if_false ^ ((if_true ^ if_false) & (condition as usize).wrapping_neg())
+}
+
+impl<T> Drop for Hole<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        // fill the hole again
+        unsafe {
+            let pos = self.pos;
+            ptr::copy_nonoverlapping(&*self.elt, self.ptr.add(pos), 1);
+        }
+    }
+}
@@ -0,0 +1,458 @@
+//! Heap algorithms on slices.
+//!
+//! The functions in this module operate on plain slices that are arranged as a
+//! *d*-ary max-heap, without requiring an owning container such as
+//! [`DaryHeap`]. They mirror the heap algorithms of the C++ standard library:
+//! [`make_heap`] arranges a slice into a heap, [`push_heap`] and [`pop_heap`]
+//! add and remove the greatest item at the end of the slice, and [`is_heap`]
+//! and [`is_heap_until`] check whether a slice is a heap. In addition,
+//! [`sort`] and its variants perform an in-place heapsort, and
+//! [`partial_sort`] and [`select_nth`] use a bounded heap to order only part
+//! of a slice.
+//!
+//! The arity *d* is passed as a const generic parameter.
+//!
+//! [`DaryHeap`]: crate::DaryHeap
+//!
+//! # Examples
+//!
+//! ```
+//! use dary_heap::slice;
+//!
+//! let mut data = vec![3, 1, 4, 1, 5];
+//! slice::make_heap::<_, 3>(&mut data);
+//! assert!(slice::is_heap::<_, 3>(&data));
+//!
+//! data.push(9);
+//! slice::push_heap::<_, 3>(&mut data);
+//! assert_eq!(data[0], 9);
+//!
+//! slice::pop_heap::<_, 3>(&mut data);
+//! assert_eq!(data.pop(), Some(9));
+//! assert_eq!(data[0], 5);
+//! ```
+
+use core::cmp::Ordering;
+
+use crate::compare::{Compare, KeyComparator, Natural};
+use crate::sift;
+
+/// Rearranges a slice into a *d*-ary max-heap.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use dary_heap::slice;
+///
+/// let mut data = [2, 7, 1, 8, 2, 8];
+/// slice::make_heap::<_, 4>(&mut data);
+///
+/// assert!(slice::is_heap::<_, 4>(&data));
+/// assert_eq!(data[0], 8);
+/// ```
+///
+/// # Time complexity
+///
+/// The cost of `make_heap` is *O*(*n*).
+pub fn make_heap<T: Ord, const D: usize>(data: &mut [T]) {
+    sift::rebuild(data, sift::Const::<D>, &Natural);
+}
+
+/// Moves the last item of a slice into the *d*-ary max-heap formed by the
+/// other items, making the whole slice a heap.
+///
+/// The slice without its last item must already be a heap. If it is not, the
+/// resulting order is unspecified, but the function is still safe to call.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use dary_heap::slice;
+///
+/// let mut data = vec![5, 3, 4];
+/// data.push(6);
+/// slice::push_heap::<_, 2>(&mut data);
+///
+/// assert!(slice::is_heap::<_, 2>(&data));
+/// assert_eq!(data[0], 6);
+/// ```
+///
+/// # Time complexity
+///
+/// The cost of `push_heap` is *O*(log(*n*)).
+pub fn push_heap<T: Ord, const D: usize>(data: &mut [T]) {
+    if let Some(last) = data.len().checked_sub(1) {
+        // SAFETY: last < data.len()
+        unsafe { sift::sift_up(data, sift::Const::<D>, &Natural, 0, last) };
+    }
+}
+
+/// Moves the greatest item of a *d*-ary max-heap to the end of the slice,
+/// making the rest of the slice a heap.
+///
+/// The slice must already be a heap. If it is not, the resulting order is
+/// unspecified, but the function is still safe to call.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use dary_heap::slice;
+///
+/// let mut data = vec![1, 5, 2];
+/// slice::make_heap::<_, 3>(&mut data);
+/// slice::pop_heap::<_, 3>(&mut data);
+///
+/// assert_eq!(data.pop(), Some(5));
+/// assert!(slice::is_heap::<_, 3>(&data));
+/// ```
+///
+/// # Time complexity
+///
+/// The cost of `pop_heap` is *O*(*d* log(*n*) / log(*d*)).
+pub fn pop_heap<T: Ord, const D: usize>(data: &mut [T]) {
+    if let Some(last) = data.len().checked_sub(1) {
+        data.swap(0, last);
+        if last > 0 {
+            // SAFETY: 0 < last <= data.len()
+            unsafe { sift::sift_down_range(data, sift::Const::<D>, &Natural, 0, last) };
+        }
+    }
+}
+
+/// Checks if a slice is a *d*-ary max-heap.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use dary_heap::slice;
+///
+/// assert!(slice::is_heap::<_, 2>(&[9, 5, 8, 1]));
+/// assert!(!slice::is_heap::<_, 2>(&[1, 5, 8, 9]));
+/// ```
+///
+/// # Time complexity
+///
+/// The cost of `is_heap` is *O*(*n*).
+#[must_use]
+pub fn is_heap<T: Ord, const D: usize>(data: &[T]) -> bool {
+    is_heap_until::<T, D>(data) == data.len()
+}
+
+/// Returns the length of the longest prefix of a slice that is a *d*-ary
+/// max-heap.
+///
+/// # Panics
+///
+/// Panics if *d* is zero.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use dary_heap::slice;
+///
+/// assert_eq!(slice::is_heap_until::<_, 2>(&[9, 5, 8, 6, 1]), 3);
+/// assert_eq!(slice::is_heap_until::<_, 2>(&[9, 5, 8]), 3);
+/// ```
+///
+/// # Time complexity
+///
+/// The cost of `is_heap_until` is *O*(*n*).
+#[must_use]
+pub fn is_heap_until<T: Ord, const D: usize>(data: &[T]) -> usize {
+    assert_ne!(D, 0, "Arity should be greater than zero");
+    (1..data.len())
+        .find(|&i| data[(i - 1) / D] < data[i])
+        .unwrap_or(data.len())
+}
+
+/// Sorts a slice in ascending order using an in-place *d*-ary heapsort.
+///
+/// This sort is unstable (i.e., may reorder equal elements), in-place (i.e.,
+/// does not allocate), and *O*(*n* log(*n*)) worst-case.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use dary_heap::slice;
+///
+/// let mut data = [-5, 4, 1, -3, 2];
+/// slice::sort::<_, 4>(&mut data);
+///
+/// assert_eq!(data, [-5, -3, 1, 2, 4]);
+/// ```
+pub fn sort<T: Ord, const D: usize>(data: &mut [T]) {
+    sort_with::<_, _, D>(data, &Natural);
+}
+
+/// Sorts a slice with a comparator function using an in-place *d*-ary
+/// heapsort.
+///
+/// The comparator function must define a total ordering for the elements in
+/// the slice. If it does not, the resulting order is unspecified.
+///
+/// This sort is unstable (i.e., may reorder equal elements), in-place (i.e.,
+/// does not allocate), and *O*(*n* log(*n*)) worst-case.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use dary_heap::slice;
+///
+/// let mut data = [5, 4, 1, 3, 2];
+/// slice::sort_by::<_, _, 3>(&mut data, |a, b| b.cmp(a));
+///
+/// assert_eq!(data, [5, 4, 3, 2, 1]);
+/// ```
+pub fn sort_by<T, F, const D: usize>(data: &mut [T], compare: F)
+where
+    F: Fn(&T, &T) -> Ordering,
+{
+    sort_with::<_, _, D>(data, &compare);
+}
+
+/// Sorts a slice with a key extraction function using an in-place *d*-ary
+/// heapsort.
+///
+/// The key is extracted on every comparison, so this is best suited to cheap
+/// key functions.
+///
+/// This sort is unstable (i.e., may reorder equal elements), in-place (i.e.,
+/// does not allocate), and *O*(*m* *n* log(*n*)) worst-case, where the key
+/// function is *O*(*m*).
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use dary_heap::slice;
+///
+/// let mut data = [-5i32, 4, 1, -3, 2];
+/// slice::sort_by_key::<_, _, _, 2>(&mut data, |k| k.abs());
+///
+/// assert_eq!(data, [1, 2, -3, 4, -5]);
+/// ```
+pub fn sort_by_key<T, K, F, const D: usize>(data: &mut [T], f: F)
+where
+    K: Ord,
+    F: Fn(&T) -> K,
+{
+    sort_with::<_, _, D>(data, &KeyComparator(f));
+}
+
+fn sort_with<T, C: Compare<T>, const D: usize>(data: &mut [T], cmp: &C) {
+    sift::rebuild(data, sift::Const::<D>, cmp);
+    sift::sort_heap(data, sift::Const::<D>, cmp);
+}
+
+/// Rearranges a slice such that `data[..k]` is a *d*-ary max-heap of its `k`
+/// smallest items.
+fn heap_select<T: Ord, const D: usize>(data: &mut [T], k: usize) {
+    let (heap, rest) = data.split_at_mut(k);
+    make_heap::<T, D>(heap);
+    if k == 0 {
+        return;
+    }
+    for item in rest {
+        if *item < heap[0] {
+            core::mem::swap(item, &mut heap[0]);
+            // SAFETY: 0 < k == heap.len()
+            unsafe { sift::sift_down_range(heap, sift::Const::<D>, &Natural, 0, k) };
+        }
+    }
+}
+
+/// Rearranges a slice such that its `k` smallest items are at the front in
+/// ascending order.
+///
+/// The order of the remaining items is unspecified. If `k` is greater than
+/// the length of the slice, the whole slice is sorted.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use dary_heap::slice;
+///
+/// let mut data = [9, 4, 7, 1, 8, 2, 6];
+/// slice::partial_sort::<_, 4>(&mut data, 3);
+///
+/// assert_eq!(data[..3], [1, 2, 4]);
+/// ```
+///
+/// # Time complexity
+///
+/// The cost of `partial_sort` is *O*(*n* log(*k*)) for a slice of length *n*.
+pub fn partial_sort<T: Ord, const D: usize>(data: &mut [T], k: usize) {
+    let k = k.min(data.len());
+    heap_select::<T, D>(data, k);
+    sift::sort_heap(&mut data[..k], sift::Const::<D>, &Natural);
+}
+
+/// Rearranges a slice such that the item at index `n` is at its sorted
+/// position, and returns a mutable reference to it.
+///
+/// All items before index `n` are less than or equal to it and all items
+/// after it are greater than or equal to it, but their order is otherwise
+/// unspecified.
+///
+/// # Panics
+///
+/// Panics if `n` is greater than or equal to the length of the slice.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use dary_heap::slice;
+///
+/// let mut data = [9, 4, 7, 1, 8, 2, 6];
+/// assert_eq!(*slice::select_nth::<_, 4>(&mut data, 2), 4);
+///
+/// assert!(data[..2].iter().all(|&x| x <= 4));
+/// assert!(data[3..].iter().all(|&x| x >= 4));
+/// ```
+///
+/// # Time complexity
+///
+/// The cost of `select_nth` is *O*(*n* log(*k*)) for a slice of length *n*
+/// and *k* = `n + 1`, which is efficient for small `n`.
+pub fn select_nth<T: Ord, const D: usize>(data: &mut [T], n: usize) -> &mut T {
+    assert!(
+        n < data.len(),
+        "select index {} out of range for slice of length {}",
+        n,
+        data.len()
+    );
+    heap_select::<T, D>(data, n + 1);
+    data.swap(0, n);
+    &mut data[n]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+    use rand::{seq::SliceRandom, thread_rng, Rng};
+
+    #[test]
+    fn push_pop() {
+        fn check<const D: usize>() {
+            let mut data: Vec<_> = (0..100).collect();
+            data.shuffle(&mut thread_rng());
+            let mut heap = Vec::new();
+            for &x in &data {
+                heap.push(x);
+                push_heap::<_, D>(&mut heap);
+                assert!(is_heap::<_, D>(&heap));
+            }
+            for i in (0..100).rev() {
+                pop_heap::<_, D>(&mut heap);
+                assert_eq!(heap.pop(), Some(i));
+                assert!(is_heap::<_, D>(&heap));
+            }
+        }
+        check::<1>();
+        check::<2>();
+        check::<3>();
+        check::<4>();
+        check::<7>();
+    }
+
+    #[test]
+    fn make_heap_valid() {
+        let mut data: Vec<_> = (0..100).collect();
+        data.shuffle(&mut thread_rng());
+        make_heap::<_, 5>(&mut data);
+        assert!(is_heap::<_, 5>(&data));
+        data[50] = 1000;
+        assert_eq!(is_heap_until::<_, 5>(&data), 50);
+    }
+
+    #[test]
+    fn make_heap_high_arity() {
+        fn check<const D: usize>() {
+            let mut rng = thread_rng();
+            for len in 0..300 {
+                let mut data: Vec<i8> = (0..len).map(|_| rng.gen()).collect();
+                let mut expected = data.clone();
+                make_heap::<_, D>(&mut data);
+                assert!(is_heap::<_, D>(&data));
+                data.sort_unstable();
+                expected.sort_unstable();
+                assert_eq!(data, expected);
+            }
+        }
+        check::<8>();
+        check::<9>();
+        check::<16>();
+    }
+
+    #[test]
+    fn sort_variants() {
+        fn check<const D: usize>() {
+            let mut rng = thread_rng();
+            let data: Vec<i8> = (0..200).map(|_| rng.gen()).collect();
+
+            let mut expected = data.clone();
+            expected.sort_unstable();
+            let mut sorted = data.clone();
+            sort::<_, D>(&mut sorted);
+            assert_eq!(sorted, expected);
+
+            expected.reverse();
+            let mut sorted = data.clone();
+            sort_by::<_, _, D>(&mut sorted, |a, b| b.cmp(a));
+            assert_eq!(sorted, expected);
+
+            let mut sorted = data;
+            sort_by_key::<_, _, _, D>(&mut sorted, |&x| i16::from(x).abs());
+            assert!(sorted
+                .windows(2)
+                .all(|w| i16::from(w[0]).abs() <= i16::from(w[1]).abs()));
+        }
+        check::<1>();
+        check::<2>();
+        check::<3>();
+        check::<4>();
+        check::<7>();
+    }
+
+    #[test]
+    fn partial_sort_and_select() {
+        let mut rng = thread_rng();
+        let data: Vec<u8> = (0..200).map(|_| rng.gen()).collect();
+        let mut sorted = data.clone();
+        sorted.sort_unstable();
+
+        for &k in &[0, 1, 17, 199, 200, 300] {
+            let mut partial = data.clone();
+            partial_sort::<_, 3>(&mut partial, k);
+            let k = k.min(200);
+            assert_eq!(partial[..k], sorted[..k]);
+        }
+        for &n in &[0, 1, 17, 199] {
+            let mut selected = data.clone();
+            let nth = *select_nth::<_, 4>(&mut selected, n);
+            assert_eq!(nth, sorted[n]);
+            assert!(selected[..n].iter().all(|&x| x <= nth));
+            assert!(selected[n + 1..].iter().all(|&x| x >= nth));
+        }
+    }
+}
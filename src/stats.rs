@@ -0,0 +1,210 @@
+//! Instrumentation of the heap algorithms.
+//!
+//! With the `stats` feature enabled, every [`DaryHeap`] keeps count of the
+//! work done by its operations in a [`HeapStats`], which can be inspected
+//! with [`DaryHeap::stats`]. Comparing these numbers for different arities on
+//! a representative workload is a more reliable way to choose *d* than
+//! guessing.
+//!
+//! Only the operations of `DaryHeap` itself are counted; operations that
+//! consume the heap, such as [`DaryHeap::into_sorted_vec`], are not.
+
+use core::cell::Cell;
+use core::cmp::Ordering;
+
+use crate::compare::Compare;
+use crate::sift::Arity;
+use crate::DaryHeap;
+
+/// Counts of the work done by the operations of a [`DaryHeap`].
+///
+/// Every time an element is moved up or down the heap to restore the heap
+/// property, this is counted as a sift. The number of levels an element
+/// passes through during a sift is its sift depth.
+///
+/// # Examples
+///
+/// ```
+/// use dary_heap::QuaternaryHeap;
+///
+/// let mut heap = QuaternaryHeap::new();
+/// heap.extend(0..100);
+/// heap.reset_stats();
+///
+/// heap.pop();
+/// let stats = heap.stats();
+/// assert_eq!(stats.sifts, 2);
+/// assert!(stats.comparisons > 0);
+/// assert!(stats.max_sift_depth <= 4);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct HeapStats {
+    /// The number of comparisons between two elements.
+    pub comparisons: u64,
+    /// The number of times an element was moved to another position.
+    pub moves: u64,
+    /// The number of sifts.
+    pub sifts: u64,
+    /// The total number of levels passed through by all sifts.
+    pub sift_depth: u64,
+    /// The largest number of levels passed through by a single sift.
+    pub max_sift_depth: usize,
+}
+
+impl HeapStats {
+    /// Creates a `HeapStats` with all counts set to zero.
+    #[must_use]
+    pub const fn new() -> HeapStats {
+        HeapStats {
+            comparisons: 0,
+            moves: 0,
+            sifts: 0,
+            sift_depth: 0,
+            max_sift_depth: 0,
+        }
+    }
+}
+
+impl<T, const D: usize> DaryHeap<T, D> {
+    /// Returns the counts of the work done by the operations of the *d*-ary
+    /// heap since it was created or since [`reset_stats`] was last called.
+    ///
+    /// [`reset_stats`]: DaryHeap::reset_stats
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::BinaryHeap;
+    /// let mut heap = BinaryHeap::new();
+    /// heap.push(1);
+    /// heap.push(2);
+    ///
+    /// let stats = heap.stats();
+    /// assert_eq!(stats.comparisons, 1);
+    /// assert_eq!(stats.max_sift_depth, 1);
+    /// ```
+    #[must_use]
+    pub fn stats(&self) -> HeapStats {
+        self.stats
+    }
+
+    /// Sets all counts returned by [`stats`] to zero.
+    ///
+    /// [`stats`]: DaryHeap::stats
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::{stats::HeapStats, BinaryHeap};
+    /// let mut heap = BinaryHeap::from(vec![1, 2, 3]);
+    /// assert_ne!(heap.stats(), HeapStats::new());
+    ///
+    /// heap.reset_stats();
+    /// assert_eq!(heap.stats(), HeapStats::new());
+    /// ```
+    pub fn reset_stats(&mut self) {
+        self.stats = HeapStats::new();
+    }
+}
+
+/// Arity and comparator that uses the [`Ord`] implementation of the items,
+/// which records the work of the sift functions in a `HeapStats`.
+#[derive(Clone, Copy)]
+pub(crate) struct Recorder<'a, const D: usize> {
+    stats: &'a Cell<HeapStats>,
+}
+
+impl<'a, const D: usize> Recorder<'a, D> {
+    pub(crate) fn new(stats: &'a mut HeapStats) -> Recorder<'a, D> {
+        Recorder {
+            stats: Cell::from_mut(stats),
+        }
+    }
+
+    fn update(self, f: impl FnOnce(&mut HeapStats)) {
+        let mut stats = self.stats.get();
+        f(&mut stats);
+        self.stats.set(stats);
+    }
+}
+
+impl<const D: usize> Arity for Recorder<'_, D> {
+    #[inline(always)]
+    fn get(self) -> usize {
+        D
+    }
+
+    #[inline]
+    fn sifted(self, _from: usize, _to: usize, levels: usize) {
+        self.update(|stats| {
+            // The sifted element itself is only moved if it changed position.
+            stats.moves += (levels + (levels > 0) as usize) as u64;
+            stats.sifts += 1;
+            stats.sift_depth += levels as u64;
+            stats.max_sift_depth = stats.max_sift_depth.max(levels);
+        });
+    }
+}
+
+impl<T: Ord, const D: usize> Compare<T> for Recorder<'_, D> {
+    #[inline]
+    fn compare(&self, l: &T, r: &T) -> Ordering {
+        self.update(|stats| stats.comparisons += 1);
+        l.cmp(r)
+    }
+
+    #[inline]
+    fn compares_le(&self, l: &T, r: &T) -> bool {
+        self.update(|stats| stats.comparisons += 1);
+        l <= r
+    }
+
+    #[inline]
+    fn compares_lt(&self, l: &T, r: &T) -> bool {
+        self.update(|stats| stats.comparisons += 1);
+        l < r
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check<const D: usize>() {
+        let mut heap = DaryHeap::<_, D>::new();
+        for i in 0..64 {
+            heap.push(i);
+        }
+        // Pushing in ascending order moves every item to the root.
+        let stats = heap.stats();
+        assert_eq!(stats.sifts, 64);
+        assert_eq!(stats.comparisons, stats.sift_depth);
+        assert_eq!(stats.moves, stats.sift_depth + 63);
+
+        heap.reset_stats();
+        heap.push(-1);
+        let stats = heap.stats();
+        assert_eq!(stats.comparisons, 1);
+        assert_eq!(stats.moves, 0);
+        assert_eq!(stats.max_sift_depth, 0);
+
+        heap.reset_stats();
+        heap.pop();
+        // Popping sifts the last item down to the bottom, and then back up.
+        assert_eq!(heap.stats().sifts, 2);
+    }
+
+    #[test]
+    fn counts() {
+        check::<1>();
+        check::<2>();
+        check::<3>();
+        check::<4>();
+        check::<7>();
+    }
+}
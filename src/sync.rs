@@ -0,0 +1,830 @@
+//! Thread-safe *d*-ary heaps.
+//!
+//! The [`SyncDaryHeap`] type in this module wraps a [`DaryHeap`] in a mutex,
+//! and lets threads block until an item is available. It can be shared
+//! between threads, e.g. using an [`Arc`], to distribute work in priority
+//! order.
+//!
+//! The [`ShardedDaryHeap`] type spreads its items over multiple heaps, each
+//! behind its own mutex, to reduce contention when many threads push items.
+//!
+//! The [`priority_channel`] function creates a channel like
+//! [`std::sync::mpsc::channel`], except that the receiver always gets the
+//! greatest pending message instead of the oldest one.
+
+use core::fmt;
+use core::mem;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use core::time::Duration;
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use std::sync::mpsc::{RecvError, RecvTimeoutError, SendError, TryRecvError};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+
+use crate::DaryHeap;
+
+/// A thread-safe priority queue implemented with a *d*-ary heap.
+///
+/// This will be a max-heap. Popping blocks while the heap is empty, until
+/// another thread pushes an item.
+///
+/// A panic in the [`Ord`] implementation of `T` while one of the methods of
+/// this type is running does not poison the heap, but leaves the order of its
+/// items unspecified, just like it would for a [`DaryHeap`].
+///
+/// # Examples
+///
+/// ```
+/// use dary_heap::sync::SyncDaryHeap;
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// let heap = Arc::new(SyncDaryHeap::<_, 4>::new());
+///
+/// let consumer = {
+///     let heap = Arc::clone(&heap);
+///     thread::spawn(move || heap.pop())
+/// };
+/// heap.push(5);
+///
+/// assert_eq!(consumer.join().unwrap(), 5);
+/// ```
+pub struct SyncDaryHeap<T, const D: usize> {
+    heap: Mutex<DaryHeap<T, D>>,
+    available: Condvar,
+}
+
+impl<T, const D: usize> SyncDaryHeap<T, D> {
+    /// Creates an empty `SyncDaryHeap<T, D>`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::sync::SyncDaryHeap;
+    /// let heap = SyncDaryHeap::<_, 4>::new();
+    /// heap.push(4);
+    /// ```
+    #[must_use]
+    pub fn new() -> SyncDaryHeap<T, D> {
+        SyncDaryHeap::from(DaryHeap::new())
+    }
+
+    /// Returns the length of the *d*-ary heap.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Checks if the *d*-ary heap is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Drops all items from the *d*-ary heap.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// Consumes the `SyncDaryHeap<T, D>` and returns the underlying
+    /// `DaryHeap<T, D>`.
+    pub fn into_inner(self) -> DaryHeap<T, D> {
+        self.heap
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Locks the heap, ignoring poisoning. A panic while the lock is held can
+    /// only come from comparing items, which leaves the heap in an unspecified
+    /// but valid state.
+    fn lock(&self) -> MutexGuard<'_, DaryHeap<T, D>> {
+        self.heap.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T: Ord, const D: usize> SyncDaryHeap<T, D> {
+    /// Pushes an item onto the *d*-ary heap, waking up one thread blocked in
+    /// [`pop`] or [`pop_timeout`].
+    ///
+    /// [`pop`]: SyncDaryHeap::pop
+    /// [`pop_timeout`]: SyncDaryHeap::pop_timeout
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::sync::SyncDaryHeap;
+    /// let heap = SyncDaryHeap::<_, 2>::new();
+    /// heap.push(3);
+    /// heap.push(5);
+    ///
+    /// assert_eq!(heap.len(), 2);
+    /// assert_eq!(heap.try_pop(), Some(5));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Apart from waiting for the lock, the cost is that of
+    /// [`DaryHeap::push`].
+    pub fn push(&self, item: T) {
+        self.lock().push(item);
+        self.available.notify_one();
+    }
+
+    /// Removes the greatest item from the *d*-ary heap and returns it,
+    /// blocking the current thread while the heap is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::sync::SyncDaryHeap;
+    /// let heap = SyncDaryHeap::<_, 3>::from(dary_heap::DaryHeap::from(vec![1, 3]));
+    ///
+    /// assert_eq!(heap.pop(), 3);
+    /// assert_eq!(heap.pop(), 1);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Apart from waiting, the cost is that of [`DaryHeap::pop`].
+    pub fn pop(&self) -> T {
+        let mut heap = self.lock();
+        loop {
+            if let Some(item) = heap.pop() {
+                return item;
+            }
+            heap = self
+                .available
+                .wait(heap)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+
+    /// Removes the greatest item from the *d*-ary heap and returns it, or
+    /// `None` if it is empty. Never blocks, except to wait for the lock.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::sync::SyncDaryHeap;
+    /// let heap = SyncDaryHeap::<_, 2>::new();
+    /// assert_eq!(heap.try_pop(), None);
+    ///
+    /// heap.push(1);
+    /// assert_eq!(heap.try_pop(), Some(1));
+    /// ```
+    pub fn try_pop(&self) -> Option<T> {
+        self.lock().pop()
+    }
+
+    /// Removes the greatest item from the *d*-ary heap and returns it,
+    /// blocking the current thread while the heap is empty for at most
+    /// `timeout`. Returns `None` if the heap is still empty after the timeout.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::sync::SyncDaryHeap;
+    /// use std::time::Duration;
+    /// let heap = SyncDaryHeap::<_, 2>::new();
+    /// assert_eq!(heap.pop_timeout(Duration::from_millis(10)), None);
+    ///
+    /// heap.push(1);
+    /// assert_eq!(heap.pop_timeout(Duration::from_millis(10)), Some(1));
+    /// ```
+    pub fn pop_timeout(&self, timeout: Duration) -> Option<T> {
+        let heap = self.lock();
+        let (mut heap, _) = self
+            .available
+            .wait_timeout_while(heap, timeout, |heap| heap.is_empty())
+            .unwrap_or_else(PoisonError::into_inner);
+        heap.pop()
+    }
+}
+
+impl<T, const D: usize> Default for SyncDaryHeap<T, D> {
+    /// Creates an empty `SyncDaryHeap<T, D>`.
+    #[inline]
+    fn default() -> SyncDaryHeap<T, D> {
+        SyncDaryHeap::new()
+    }
+}
+
+impl<T: fmt::Debug, const D: usize> fmt::Debug for SyncDaryHeap<T, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SyncDaryHeap")
+            .field("heap", &*self.lock())
+            .finish()
+    }
+}
+
+impl<T, const D: usize> From<DaryHeap<T, D>> for SyncDaryHeap<T, D> {
+    /// Converts a `DaryHeap<T, D>` into a `SyncDaryHeap<T, D>`.
+    ///
+    /// This conversion does not move any items.
+    fn from(heap: DaryHeap<T, D>) -> SyncDaryHeap<T, D> {
+        SyncDaryHeap {
+            heap: Mutex::new(heap),
+            available: Condvar::new(),
+        }
+    }
+}
+
+/// A concurrent priority queue implemented with multiple *d*-ary heaps.
+///
+/// This will be a max-heap. Items are pushed to the heaps (shards) in turn,
+/// so concurrent pushes rarely wait for each other. Popping compares the
+/// greatest items of all shards and removes the greatest one of those.
+///
+/// The order is relaxed compared to a single heap: as the shards are not all
+/// locked at once, an item pushed concurrently with a pop to a shard that was
+/// already inspected can be greater than the popped item. Without concurrent
+/// pushes, items are popped in the same order as from a [`DaryHeap`].
+///
+/// # Examples
+///
+/// ```
+/// use dary_heap::sync::ShardedDaryHeap;
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// let heap = Arc::new(ShardedDaryHeap::<_, 4>::new(4));
+/// let producers: Vec<_> = (0..4)
+///     .map(|i| {
+///         let heap = Arc::clone(&heap);
+///         thread::spawn(move || (0..10).for_each(|j| heap.push(10 * i + j)))
+///     })
+///     .collect();
+/// for producer in producers {
+///     producer.join().unwrap();
+/// }
+///
+/// assert_eq!(heap.len(), 40);
+/// assert_eq!(heap.try_pop(), Some(39));
+/// ```
+pub struct ShardedDaryHeap<T, const D: usize> {
+    shards: Box<[Mutex<DaryHeap<T, D>>]>,
+    /// The shard to push the next item to, modulo the number of shards.
+    next: AtomicUsize,
+}
+
+impl<T, const D: usize> ShardedDaryHeap<T, D> {
+    /// Creates an empty `ShardedDaryHeap<T, D>` with the given number of
+    /// shards.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shards` is zero.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::sync::ShardedDaryHeap;
+    /// let heap = ShardedDaryHeap::<_, 4>::new(8);
+    /// heap.push(4);
+    ///
+    /// assert_eq!(heap.shards(), 8);
+    /// ```
+    #[must_use]
+    pub fn new(shards: usize) -> ShardedDaryHeap<T, D> {
+        assert_ne!(shards, 0, "Number of shards should be greater than zero");
+        ShardedDaryHeap {
+            shards: (0..shards).map(|_| Mutex::new(DaryHeap::new())).collect(),
+            next: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the number of shards.
+    #[must_use]
+    pub fn shards(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Returns the total length of the shards.
+    ///
+    /// The shards are locked one at a time, so the result may be outdated if
+    /// other threads push or pop concurrently.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        (0..self.shards.len()).map(|i| self.lock(i).len()).sum()
+    }
+
+    /// Checks if all shards are empty.
+    ///
+    /// The shards are locked one at a time, so the result may be outdated if
+    /// other threads push or pop concurrently.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        (0..self.shards.len()).all(|i| self.lock(i).is_empty())
+    }
+
+    /// Drops all items from the shards.
+    pub fn clear(&self) {
+        for i in 0..self.shards.len() {
+            self.lock(i).clear();
+        }
+    }
+
+    /// Locks shard `i`, ignoring poisoning for the same reason as
+    /// [`SyncDaryHeap`] does.
+    fn lock(&self, i: usize) -> MutexGuard<'_, DaryHeap<T, D>> {
+        self.shards[i]
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<T: Ord, const D: usize> ShardedDaryHeap<T, D> {
+    /// Pushes an item onto one of the shards.
+    ///
+    /// The shards are used in turn. If the next shard is locked by another
+    /// thread, the item is pushed to the first shard that is not, and only if
+    /// all shards are locked, this waits for the next shard.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::sync::ShardedDaryHeap;
+    /// let heap = ShardedDaryHeap::<_, 2>::new(2);
+    /// heap.push(3);
+    /// heap.push(5);
+    /// heap.push(1);
+    ///
+    /// assert_eq!(heap.len(), 3);
+    /// assert_eq!(heap.try_pop(), Some(5));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Apart from waiting for a lock, the cost is that of [`DaryHeap::push`]
+    /// on a single shard.
+    pub fn push(&self, item: T) {
+        let n = self.shards.len();
+        let start = self.next.fetch_add(1, Ordering::Relaxed) % n;
+        for i in (start..n).chain(0..start) {
+            if let Ok(mut shard) = self.shards[i].try_lock() {
+                shard.push(item);
+                return;
+            }
+        }
+        self.lock(start).push(item);
+    }
+
+    /// Removes the greatest item of the shards and returns it, or `None` if
+    /// all shards are empty.
+    ///
+    /// At most two shards are locked at the same time: the shard with the
+    /// greatest item found so far, and the shard being inspected.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::sync::ShardedDaryHeap;
+    /// let heap = ShardedDaryHeap::<_, 2>::new(3);
+    /// heap.extend(vec![1, 5, 2, 4]);
+    ///
+    /// assert_eq!(heap.try_pop(), Some(5));
+    /// assert_eq!(heap.try_pop(), Some(4));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// Apart from waiting for the locks, the cost is *O*(*s*) comparisons for
+    /// *s* shards, plus the cost of [`DaryHeap::pop`] on a single shard.
+    pub fn try_pop(&self) -> Option<T> {
+        let mut best: Option<MutexGuard<'_, DaryHeap<T, D>>> = None;
+        for i in 0..self.shards.len() {
+            let shard = self.lock(i);
+            let greater = match (shard.peek(), best.as_ref().and_then(|best| best.peek())) {
+                (Some(item), Some(best)) => item > best,
+                (Some(_), None) => true,
+                (None, _) => false,
+            };
+            if greater {
+                best = Some(shard);
+            }
+        }
+        best.and_then(|mut shard| shard.pop())
+    }
+
+    /// Pushes all items of an iterator onto the shards, each in turn.
+    ///
+    /// This takes `&self`, so unlike [`Extend::extend`] it can be used on a
+    /// shared heap.
+    pub fn extend<I: IntoIterator<Item = T>>(&self, iter: I) {
+        for item in iter {
+            self.push(item);
+        }
+    }
+
+    /// Consumes the `ShardedDaryHeap<T, D>` and combines its shards into a
+    /// single `DaryHeap<T, D>`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::sync::ShardedDaryHeap;
+    /// let heap = ShardedDaryHeap::<_, 2>::new(3);
+    /// heap.extend(vec![1, 5, 2, 4]);
+    ///
+    /// assert_eq!(heap.into_heap().into_sorted_vec(), [1, 2, 4, 5]);
+    /// ```
+    pub fn into_heap(self) -> DaryHeap<T, D> {
+        let shards: Vec<_> = self.shards.into();
+        DaryHeap::meld(
+            shards
+                .into_iter()
+                .map(|shard| shard.into_inner().unwrap_or_else(PoisonError::into_inner)),
+        )
+    }
+}
+
+impl<T: fmt::Debug, const D: usize> fmt::Debug for ShardedDaryHeap<T, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut list = f.debug_list();
+        for i in 0..self.shards.len() {
+            list.entry(&*self.lock(i));
+        }
+        list.finish()
+    }
+}
+
+/// Creates a new priority channel, returning the sender and receiver halves.
+///
+/// Messages sent on the [`Sender`] are stored in a *d*-ary heap, and the
+/// [`Receiver`] receives the greatest pending message first. The `Sender` can
+/// be cloned to send from multiple threads, but only one `Receiver` exists.
+///
+/// Disconnection works like it does for [`std::sync::mpsc::channel`]: once
+/// all senders are dropped, the receiver gets the remaining messages and then
+/// an error, and once the receiver is dropped, sending returns an error.
+///
+/// # Examples
+///
+/// ```
+/// use dary_heap::sync::priority_channel;
+/// use std::thread;
+///
+/// let (sender, receiver) = priority_channel::<_, 4>();
+/// thread::spawn(move || {
+///     for i in vec![2, 7, 4] {
+///         sender.send(i).unwrap();
+///     }
+/// })
+/// .join()
+/// .unwrap();
+///
+/// assert_eq!(receiver.recv(), Ok(7));
+/// assert_eq!(receiver.recv(), Ok(4));
+/// assert_eq!(receiver.recv(), Ok(2));
+/// assert!(receiver.recv().is_err());
+/// ```
+pub fn priority_channel<T: Ord, const D: usize>() -> (Sender<T, D>, Receiver<T, D>) {
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            heap: DaryHeap::new(),
+            senders: 1,
+            receiver: true,
+        }),
+        available: Condvar::new(),
+    });
+    let sender = Sender {
+        shared: Arc::clone(&shared),
+    };
+    (sender, Receiver { shared })
+}
+
+/// The state of a priority channel shared by its senders and receiver.
+struct Shared<T, const D: usize> {
+    state: Mutex<State<T, D>>,
+    available: Condvar,
+}
+
+struct State<T, const D: usize> {
+    heap: DaryHeap<T, D>,
+    /// The number of senders that have not been dropped.
+    senders: usize,
+    /// Whether the receiver has not been dropped.
+    receiver: bool,
+}
+
+impl<T, const D: usize> Shared<T, D> {
+    /// Locks the state, ignoring poisoning for the same reason as
+    /// [`SyncDaryHeap`] does.
+    fn lock(&self) -> MutexGuard<'_, State<T, D>> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// The sending half of a priority channel.
+///
+/// This `struct` is created by the [`priority_channel`] function. See its
+/// documentation for more.
+pub struct Sender<T, const D: usize> {
+    shared: Arc<Shared<T, D>>,
+}
+
+impl<T: Ord, const D: usize> Sender<T, D> {
+    /// Sends a message on the channel.
+    ///
+    /// # Errors
+    ///
+    /// Returns the message in a [`SendError`] if the receiver has been
+    /// dropped.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::sync::priority_channel;
+    /// let (sender, receiver) = priority_channel::<_, 2>();
+    /// assert!(sender.send(1).is_ok());
+    ///
+    /// drop(receiver);
+    /// assert_eq!(sender.send(2).unwrap_err().0, 2);
+    /// ```
+    pub fn send(&self, message: T) -> Result<(), SendError<T>> {
+        let mut state = self.shared.lock();
+        if !state.receiver {
+            return Err(SendError(message));
+        }
+        state.heap.push(message);
+        drop(state);
+        self.shared.available.notify_one();
+        Ok(())
+    }
+}
+
+impl<T, const D: usize> Clone for Sender<T, D> {
+    fn clone(&self) -> Self {
+        self.shared.lock().senders += 1;
+        Sender {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+impl<T, const D: usize> Drop for Sender<T, D> {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.senders -= 1;
+        if state.senders == 0 {
+            drop(state);
+            self.shared.available.notify_all();
+        }
+    }
+}
+
+impl<T, const D: usize> fmt::Debug for Sender<T, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Sender { .. }")
+    }
+}
+
+/// The receiving half of a priority channel.
+///
+/// This `struct` is created by the [`priority_channel`] function. See its
+/// documentation for more.
+pub struct Receiver<T, const D: usize> {
+    shared: Arc<Shared<T, D>>,
+}
+
+impl<T: Ord, const D: usize> Receiver<T, D> {
+    /// Receives the greatest pending message, blocking the current thread
+    /// while no message is pending.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RecvError`] if no message is pending and all senders have
+    /// been dropped.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::sync::priority_channel;
+    /// use std::sync::mpsc::RecvError;
+    /// let (sender, receiver) = priority_channel::<_, 3>();
+    /// sender.send(1).unwrap();
+    /// sender.send(3).unwrap();
+    /// drop(sender);
+    ///
+    /// assert_eq!(receiver.recv(), Ok(3));
+    /// assert_eq!(receiver.recv(), Ok(1));
+    /// assert_eq!(receiver.recv(), Err(RecvError));
+    /// ```
+    pub fn recv(&self) -> Result<T, RecvError> {
+        let state = self.shared.lock();
+        let mut state = self
+            .shared
+            .available
+            .wait_while(state, |state| state.heap.is_empty() && state.senders > 0)
+            .unwrap_or_else(PoisonError::into_inner);
+        state.heap.pop().ok_or(RecvError)
+    }
+
+    /// Receives the greatest pending message without blocking.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TryRecvError::Empty`] if no message is pending, or
+    /// [`TryRecvError::Disconnected`] if additionally all senders have been
+    /// dropped.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::sync::priority_channel;
+    /// use std::sync::mpsc::TryRecvError;
+    /// let (sender, receiver) = priority_channel::<_, 2>();
+    /// assert_eq!(receiver.try_recv(), Err(TryRecvError::Empty));
+    ///
+    /// sender.send(1).unwrap();
+    /// assert_eq!(receiver.try_recv(), Ok(1));
+    ///
+    /// drop(sender);
+    /// assert_eq!(receiver.try_recv(), Err(TryRecvError::Disconnected));
+    /// ```
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        let mut state = self.shared.lock();
+        match state.heap.pop() {
+            Some(message) => Ok(message),
+            None if state.senders == 0 => Err(TryRecvError::Disconnected),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+
+    /// Receives the greatest pending message, blocking the current thread
+    /// while no message is pending for at most `timeout`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RecvTimeoutError::Timeout`] if no message is pending after
+    /// the timeout, or [`RecvTimeoutError::Disconnected`] if no message is
+    /// pending and all senders have been dropped.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::sync::priority_channel;
+    /// use std::sync::mpsc::RecvTimeoutError;
+    /// use std::time::Duration;
+    /// let (sender, receiver) = priority_channel::<_, 2>();
+    /// let timeout = Duration::from_millis(10);
+    /// assert_eq!(receiver.recv_timeout(timeout), Err(RecvTimeoutError::Timeout));
+    ///
+    /// sender.send(1).unwrap();
+    /// assert_eq!(receiver.recv_timeout(timeout), Ok(1));
+    /// ```
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<T, RecvTimeoutError> {
+        let state = self.shared.lock();
+        let (mut state, _) = self
+            .shared
+            .available
+            .wait_timeout_while(state, timeout, |state| {
+                state.heap.is_empty() && state.senders > 0
+            })
+            .unwrap_or_else(PoisonError::into_inner);
+        match state.heap.pop() {
+            Some(message) => Ok(message),
+            None if state.senders == 0 => Err(RecvTimeoutError::Disconnected),
+            None => Err(RecvTimeoutError::Timeout),
+        }
+    }
+}
+
+impl<T, const D: usize> Receiver<T, D> {
+    /// Returns the number of pending messages.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.shared.lock().heap.len()
+    }
+
+    /// Checks if no messages are pending.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.shared.lock().heap.is_empty()
+    }
+}
+
+impl<T, const D: usize> Drop for Receiver<T, D> {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.receiver = false;
+        // Pending messages are dropped after unlocking, as they may contain
+        // senders of this channel.
+        let heap = mem::take(&mut state.heap);
+        drop(state);
+        drop(heap);
+    }
+}
+
+impl<T, const D: usize> fmt::Debug for Receiver<T, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Receiver { .. }")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn producers_consumers() {
+        let heap = Arc::new(SyncDaryHeap::<u32, 4>::new());
+        let consumers: Vec<_> = (0..4)
+            .map(|_| {
+                let heap = Arc::clone(&heap);
+                thread::spawn(move || (0..250).map(|_| heap.pop()).collect::<Vec<_>>())
+            })
+            .collect();
+        let producers: Vec<_> = (0..4)
+            .map(|i| {
+                let heap = Arc::clone(&heap);
+                thread::spawn(move || (0..250).for_each(|j| heap.push(250 * i + j)))
+            })
+            .collect();
+        for producer in producers {
+            producer.join().unwrap();
+        }
+
+        let mut popped = Vec::new();
+        for consumer in consumers {
+            popped.extend(consumer.join().unwrap());
+        }
+        popped.sort_unstable();
+        assert_eq!(popped, (0..1000).collect::<Vec<_>>());
+        assert!(heap.is_empty());
+        assert_eq!(heap.pop_timeout(Duration::from_millis(1)), None);
+    }
+
+    #[test]
+    fn priority_channel() {
+        let (sender, receiver) = super::priority_channel::<u32, 3>();
+        let senders: Vec<_> = (0..4)
+            .map(|i| {
+                let sender = sender.clone();
+                thread::spawn(move || (0..250).for_each(|j| sender.send(250 * i + j).unwrap()))
+            })
+            .collect();
+        drop(sender);
+        for sender in senders {
+            sender.join().unwrap();
+        }
+
+        assert_eq!(receiver.len(), 1000);
+        for i in (0..1000).rev() {
+            assert_eq!(receiver.recv(), Ok(i));
+        }
+        assert_eq!(receiver.recv(), Err(RecvError));
+    }
+
+    #[test]
+    fn sharded() {
+        let heap = Arc::new(ShardedDaryHeap::<u32, 2>::new(3));
+        let producers: Vec<_> = (0..4)
+            .map(|i| {
+                let heap = Arc::clone(&heap);
+                thread::spawn(move || (0..250).for_each(|j| heap.push(250 * i + j)))
+            })
+            .collect();
+        for producer in producers {
+            producer.join().unwrap();
+        }
+
+        assert_eq!(heap.len(), 1000);
+        for i in (500..1000).rev() {
+            assert_eq!(heap.try_pop(), Some(i));
+        }
+        let heap = Arc::try_unwrap(heap).unwrap().into_heap();
+        assert_eq!(heap.into_sorted_vec(), (0..500).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,130 @@
+//! Top-*k* selection on iterators.
+//!
+//! The [`TopKExt`] trait in this module is implemented for every iterator and
+//! selects its *k* largest or smallest items using a bounded *d*-ary heap, so
+//! at most *k* items are kept in memory at any time.
+
+use core::cmp::Reverse;
+
+use alloc::vec::{self, Vec};
+
+use crate::DaryHeap;
+
+/// An extension trait for iterators to select their largest or smallest
+/// items.
+///
+/// The arity *d* of the heap used internally is passed as a const generic
+/// parameter.
+///
+/// # Examples
+///
+/// ```
+/// use dary_heap::topk::TopKExt;
+///
+/// let data = [5, 1, 8, 3, 9, 2];
+///
+/// let largest: Vec<_> = data.iter().copied().k_largest::<4>(3).collect();
+/// assert_eq!(largest, [9, 8, 5]);
+///
+/// let smallest: Vec<_> = data.iter().copied().k_smallest::<4>(3).collect();
+/// assert_eq!(smallest, [1, 2, 3]);
+/// ```
+pub trait TopKExt: Iterator {
+    /// Consumes the iterator and returns its `k` largest items in descending
+    /// order.
+    ///
+    /// If the iterator yields fewer than `k` items, all of them are returned.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::topk::TopKExt;
+    ///
+    /// let largest: Vec<_> = (0..100).k_largest::<2>(4).collect();
+    /// assert_eq!(largest, [99, 98, 97, 96]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The cost is *O*(*n* log(*k*)) for an iterator yielding *n* items, and
+    /// at most *k* items are stored at any time.
+    fn k_largest<const D: usize>(self, k: usize) -> vec::IntoIter<Self::Item>
+    where
+        Self: Sized,
+        Self::Item: Ord,
+    {
+        let sorted = bounded::<_, D>(self.map(Reverse), k);
+        let items: Vec<_> = sorted.into_iter().map(|Reverse(item)| item).collect();
+        items.into_iter()
+    }
+
+    /// Consumes the iterator and returns its `k` smallest items in ascending
+    /// order.
+    ///
+    /// If the iterator yields fewer than `k` items, all of them are returned.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::topk::TopKExt;
+    ///
+    /// let smallest: Vec<_> = (0..100).rev().k_smallest::<2>(4).collect();
+    /// assert_eq!(smallest, [0, 1, 2, 3]);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The cost is *O*(*n* log(*k*)) for an iterator yielding *n* items, and
+    /// at most *k* items are stored at any time.
+    fn k_smallest<const D: usize>(self, k: usize) -> vec::IntoIter<Self::Item>
+    where
+        Self: Sized,
+        Self::Item: Ord,
+    {
+        bounded::<_, D>(self, k).into_iter()
+    }
+}
+
+impl<I: Iterator> TopKExt for I {}
+
+/// Returns the `k` smallest items of `iter` in ascending order.
+fn bounded<T: Ord, const D: usize>(mut iter: impl Iterator<Item = T>, k: usize) -> Vec<T> {
+    if k == 0 {
+        iter.for_each(drop);
+        return Vec::new();
+    }
+    let mut heap = DaryHeap::<T, D>::with_capacity(k);
+    heap.extend(iter.by_ref().take(k));
+    for item in iter {
+        if let Some(mut top) = heap.peek_mut() {
+            if item < *top {
+                *top = item;
+            }
+        }
+    }
+    heap.into_sorted_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{seq::SliceRandom, thread_rng};
+
+    #[test]
+    fn k_largest_and_smallest() {
+        let mut data: Vec<_> = (0..200).collect();
+        data.shuffle(&mut thread_rng());
+
+        for &k in &[0, 1, 7, 200, 300] {
+            let n = k.min(200);
+            let largest: Vec<_> = data.iter().copied().k_largest::<3>(k).collect();
+            assert_eq!(largest, (200 - n..200).rev().collect::<Vec<_>>());
+            let smallest: Vec<_> = data.iter().copied().k_smallest::<5>(k).collect();
+            assert_eq!(smallest, (0..n).collect::<Vec<_>>());
+        }
+    }
+}
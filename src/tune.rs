@@ -0,0 +1,279 @@
+//! Choosing the arity of a heap by benchmarking.
+//!
+//! Which arity performs best depends on the workload and the hardware, so the
+//! only reliable way to choose one is to measure. The [`recommend_arity`]
+//! function in this module runs quick micro-benchmarks of a [`Workload`] for
+//! *d* = 2 to 8 in the current process, and reports the fastest arity. The
+//! result can be used to pick the arity of a [`DaryHeap`], or directly as the
+//! arity of a [`DynDaryHeap`].
+//!
+//! The measurements are only meaningful in optimized builds, and are subject
+//! to noise from other work done by the machine.
+//!
+//! [`DaryHeap`]: crate::DaryHeap
+//! [`DynDaryHeap`]: crate::dynamic::DynDaryHeap
+
+use core::ptr;
+use core::time::Duration;
+
+use alloc::{vec, vec::Vec};
+
+use std::time::Instant;
+
+use crate::DaryHeap;
+
+/// Description of the way a heap is used, to be benchmarked by
+/// [`recommend_arity`].
+///
+/// # Examples
+///
+/// ```
+/// use dary_heap::tune::Workload;
+///
+/// // A heap of about 100,000 items of 32 bytes, which is pushed to twice as
+/// // often as it is popped from.
+/// let workload = Workload::new()
+///     .len(100_000)
+///     .push_ratio(2.0 / 3.0)
+///     .element_size(32);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Workload {
+    len: usize,
+    operations: usize,
+    push_ratio: f64,
+    element_size: usize,
+    rounds: usize,
+}
+
+impl Workload {
+    /// Creates a description of a workload on a heap of 10,000 items of 8
+    /// bytes, performing 100,000 operations that are equally likely to be a
+    /// push or a pop.
+    #[must_use]
+    pub fn new() -> Workload {
+        Workload {
+            len: 10_000,
+            operations: 100_000,
+            push_ratio: 0.5,
+            element_size: 8,
+            rounds: 3,
+        }
+    }
+
+    /// Sets the number of items in the heap at the start of the workload.
+    #[must_use]
+    pub fn len(mut self, len: usize) -> Workload {
+        self.len = len;
+        self
+    }
+
+    /// Sets the number of pushes and pops performed by the workload.
+    #[must_use]
+    pub fn operations(mut self, operations: usize) -> Workload {
+        self.operations = operations;
+        self
+    }
+
+    /// Sets the fraction of the operations that are pushes, the others being
+    /// pops.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `push_ratio` is not between 0 and 1.
+    #[must_use]
+    pub fn push_ratio(mut self, push_ratio: f64) -> Workload {
+        assert!(
+            (0.0..=1.0).contains(&push_ratio),
+            "Push ratio should be between 0 and 1"
+        );
+        self.push_ratio = push_ratio;
+        self
+    }
+
+    /// Sets the size of the items in bytes.
+    ///
+    /// The items are simulated by types of 8, 16, 32, 64 or 128 bytes, using
+    /// the smallest one that is at least as large as `element_size`, or the
+    /// largest one.
+    #[must_use]
+    pub fn element_size(mut self, element_size: usize) -> Workload {
+        self.element_size = element_size;
+        self
+    }
+
+    /// Sets how often the workload is run for every arity. The fastest run
+    /// counts, which reduces the influence of noise.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rounds` is zero.
+    #[must_use]
+    pub fn rounds(mut self, rounds: usize) -> Workload {
+        assert_ne!(rounds, 0, "Number of rounds should be greater than zero");
+        self.rounds = rounds;
+        self
+    }
+}
+
+impl Default for Workload {
+    /// Creates the default workload, see [`Workload::new`].
+    #[inline]
+    fn default() -> Workload {
+        Workload::new()
+    }
+}
+
+/// Results of benchmarking a [`Workload`], created by [`recommend_arity`].
+#[derive(Clone, Debug)]
+pub struct Report {
+    timings: Vec<(usize, Duration)>,
+}
+
+impl Report {
+    /// Returns the arity for which the workload ran fastest.
+    #[must_use]
+    pub fn arity(&self) -> usize {
+        self.timings
+            .iter()
+            .min_by_key(|&&(_, time)| time)
+            .map(|&(arity, _)| arity)
+            .unwrap()
+    }
+
+    /// Returns the fastest time the workload took for every benchmarked
+    /// arity, in order of increasing arity.
+    #[must_use]
+    pub fn timings(&self) -> &[(usize, Duration)] {
+        &self.timings
+    }
+}
+
+/// Benchmarks `workload` for *d* = 2 to 8, and reports which arity is
+/// fastest.
+///
+/// This runs the workload [`rounds`] times for every arity, so it takes a
+/// while for large workloads.
+///
+/// [`rounds`]: Workload::rounds
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use dary_heap::dynamic::DynDaryHeap;
+/// use dary_heap::tune::{recommend_arity, Workload};
+///
+/// let report = recommend_arity(&Workload::new().len(1000).operations(1000));
+/// assert!((2..=8).contains(&report.arity()));
+///
+/// let mut heap = DynDaryHeap::new(report.arity());
+/// heap.push(1);
+/// ```
+#[must_use]
+pub fn recommend_arity(workload: &Workload) -> Report {
+    match workload.element_size {
+        0..=8 => run::<1>(workload),
+        9..=16 => run::<2>(workload),
+        17..=32 => run::<4>(workload),
+        33..=64 => run::<8>(workload),
+        _ => run::<16>(workload),
+    }
+}
+
+/// Item of `8 * N` bytes, ordered by its first word.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Item<const N: usize>([u64; N]);
+
+/// Benchmarks the workload for all arities, with items of `8 * N` bytes.
+fn run<const N: usize>(workload: &Workload) -> Report {
+    let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+    let initial: Vec<_> = (0..workload.len).map(|_| item::<N>(&mut rng)).collect();
+    let threshold = (workload.push_ratio * u64::MAX as f64) as u64;
+    let operations: Vec<_> = (0..workload.operations)
+        .map(|_| (rng.next_u64() <= threshold).then(|| item::<N>(&mut rng)))
+        .collect();
+
+    let bench = |f: fn(&[Item<N>], &[Option<Item<N>>]) -> Duration| {
+        (0..workload.rounds)
+            .map(|_| f(&initial, &operations))
+            .min()
+            .unwrap()
+    };
+    let timings = vec![
+        (2, bench(time::<N, 2>)),
+        (3, bench(time::<N, 3>)),
+        (4, bench(time::<N, 4>)),
+        (5, bench(time::<N, 5>)),
+        (6, bench(time::<N, 6>)),
+        (7, bench(time::<N, 7>)),
+        (8, bench(time::<N, 8>)),
+    ];
+    Report { timings }
+}
+
+/// Times a single run of the workload on a heap with arity `D`. Pushes are
+/// represented by `Some(item)` and pops by `None`.
+fn time<const N: usize, const D: usize>(
+    initial: &[Item<N>],
+    operations: &[Option<Item<N>>],
+) -> Duration {
+    let mut heap = DaryHeap::<_, D>::from(initial.to_vec());
+    let mut checksum = 0u64;
+    let start = Instant::now();
+    for operation in operations {
+        match *operation {
+            Some(item) => heap.push(item),
+            None => {
+                if let Some(item) = heap.pop() {
+                    checksum ^= item.0[0];
+                }
+            }
+        }
+    }
+    let elapsed = start.elapsed();
+    // SAFETY: `checksum` is a valid `u64`. The volatile read prevents the
+    //  pops from being optimized away.
+    unsafe { ptr::read_volatile(&checksum) };
+    elapsed
+}
+
+fn item<const N: usize>(rng: &mut XorShift) -> Item<N> {
+    let mut item = Item([0; N]);
+    item.0[0] = rng.next_u64();
+    item
+}
+
+/// Small pseudorandom number generator, to generate the workload.
+struct XorShift(u64);
+
+impl XorShift {
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_all_arities() {
+        for &size in &[8, 24, 200] {
+            let workload = Workload::new()
+                .len(100)
+                .operations(100)
+                .push_ratio(0.25)
+                .element_size(size)
+                .rounds(1);
+            let report = recommend_arity(&workload);
+            let arities: Vec<_> = report.timings().iter().map(|&(d, _)| d).collect();
+            assert_eq!(arities, (2..=8).collect::<Vec<_>>());
+            assert!(arities.contains(&report.arity()));
+        }
+    }
+}
@@ -0,0 +1,231 @@
+//! A *d*-ary heap view over a borrowed vector.
+//!
+//! The [`DaryHeapMut`] type in this module arranges a vector owned elsewhere
+//! into a *d*-ary heap and offers the heap operations on it, without moving
+//! the vector into a [`DaryHeap`]. The vector is a valid heap after every
+//! operation, so it is left as one when the view is dropped.
+//!
+//! [`DaryHeap`]: crate::DaryHeap
+
+use core::fmt;
+use core::mem::swap;
+
+use alloc::vec::Vec;
+
+use crate::compare::Natural;
+use crate::{sift, Iter};
+
+/// A priority queue implemented with a *d*-ary heap over a borrowed vector.
+///
+/// This will be a max-heap.
+///
+/// # Examples
+///
+/// ```
+/// use dary_heap::view::DaryHeapMut;
+///
+/// let mut data = vec![1, 5, 2];
+///
+/// let mut heap = DaryHeapMut::<_, 4>::new(&mut data);
+/// heap.push(3);
+/// assert_eq!(heap.pop(), Some(5));
+/// assert_eq!(heap.peek(), Some(&3));
+///
+/// // The vector is left as a heap of the remaining items.
+/// assert_eq!(data.len(), 3);
+/// assert_eq!(data[0], 3);
+/// ```
+pub struct DaryHeapMut<'a, T, const D: usize> {
+    data: &'a mut Vec<T>,
+}
+
+impl<T: fmt::Debug, const D: usize> fmt::Debug for DaryHeapMut<'_, T, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, T: Ord, const D: usize> DaryHeapMut<'a, T, D> {
+    /// Rearranges a borrowed vector into a *d*-ary heap and returns a view
+    /// over it.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::view::DaryHeapMut;
+    /// let mut data = vec![1, 5, 2];
+    /// let heap = DaryHeapMut::<_, 2>::new(&mut data);
+    ///
+    /// assert_eq!(heap.peek(), Some(&5));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The cost of `new` is *O*(*n*).
+    pub fn new(data: &'a mut Vec<T>) -> DaryHeapMut<'a, T, D> {
+        sift::rebuild(data, sift::Const::<D>, &Natural);
+        DaryHeapMut { data }
+    }
+
+    /// Removes the greatest item from the *d*-ary heap and returns it, or
+    /// `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::view::DaryHeapMut;
+    /// let mut data = vec![1, 3];
+    /// let mut heap = DaryHeapMut::<_, 3>::new(&mut data);
+    ///
+    /// assert_eq!(heap.pop(), Some(3));
+    /// assert_eq!(heap.pop(), Some(1));
+    /// assert_eq!(heap.pop(), None);
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The worst case cost of `pop` on a heap containing *n* elements is
+    /// *O*(log(*n*)).
+    pub fn pop(&mut self) -> Option<T> {
+        self.data.pop().map(|mut item| {
+            if !self.data.is_empty() {
+                swap(&mut item, &mut self.data[0]);
+                // SAFETY: !self.data.is_empty() means that self.data.len() > 0
+                unsafe { sift::sift_down_to_bottom(self.data, sift::Const::<D>, &Natural, 0) };
+            }
+            item
+        })
+    }
+
+    /// Pushes an item onto the *d*-ary heap.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::view::DaryHeapMut;
+    /// let mut data = Vec::new();
+    /// let mut heap = DaryHeapMut::<_, 4>::new(&mut data);
+    /// heap.push(3);
+    /// heap.push(5);
+    /// heap.push(1);
+    ///
+    /// assert_eq!(heap.len(), 3);
+    /// assert_eq!(heap.peek(), Some(&5));
+    /// ```
+    ///
+    /// # Time complexity
+    ///
+    /// The expected cost of `push`, averaged over every possible ordering of
+    /// the elements being pushed, and over a sufficiently large number of
+    /// pushes, is *O*(1). This is the most meaningful cost metric when pushing
+    /// elements that are *not* already in any sorted pattern.
+    pub fn push(&mut self, item: T) {
+        let old_len = self.data.len();
+        self.data.push(item);
+        // SAFETY: Since we pushed a new item it means that
+        //  old_len = self.data.len() - 1 < self.data.len()
+        unsafe { sift::sift_up(self.data, sift::Const::<D>, &Natural, 0, old_len) };
+    }
+
+    /// Consumes the view and sorts the borrowed vector in ascending order.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use dary_heap::view::DaryHeapMut;
+    /// let mut data = vec![1, 2, 4, 5, 7];
+    /// let mut heap = DaryHeapMut::<_, 2>::new(&mut data);
+    /// heap.push(6);
+    /// heap.push(3);
+    /// heap.sort();
+    ///
+    /// assert_eq!(data, [1, 2, 3, 4, 5, 6, 7]);
+    /// ```
+    pub fn sort(self) {
+        sift::sort_heap(self.data, sift::Const::<D>, &Natural);
+    }
+}
+
+impl<T, const D: usize> DaryHeapMut<'_, T, D> {
+    /// Returns an iterator visiting all values in the underlying vector, in
+    /// arbitrary order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            iter: self.data.iter(),
+        }
+    }
+
+    /// Returns the greatest item in the *d*-ary heap, or `None` if it is
+    /// empty.
+    ///
+    /// # Time complexity
+    ///
+    /// Cost is *O*(1) in the worst case.
+    #[must_use]
+    pub fn peek(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    /// Returns a slice of all values in the underlying vector, in arbitrary
+    /// order.
+    #[must_use]
+    pub fn as_slice(&self) -> &[T] {
+        self.data.as_slice()
+    }
+
+    /// Returns the length of the *d*-ary heap.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Checks if the *d*-ary heap is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Drops all items from the *d*-ary heap.
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+}
+
+impl<T: Ord, const D: usize> Extend<T> for DaryHeapMut<'_, T, D> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let start = self.data.len();
+        self.data.extend(iter);
+        sift::rebuild_tail(self.data, sift::Const::<D>, &Natural, start);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{seq::SliceRandom, thread_rng};
+
+    #[test]
+    fn pop_descending() {
+        let mut data: Vec<_> = (0..100).collect();
+        data.shuffle(&mut thread_rng());
+        let (front, back) = data.split_at(50);
+        let mut vec = front.to_vec();
+
+        let mut heap = DaryHeapMut::<_, 3>::new(&mut vec);
+        heap.extend(back.iter().copied());
+        for i in (50..100).rev() {
+            assert_eq!(heap.pop(), Some(i));
+        }
+        assert!(crate::slice::is_heap::<_, 3>(&vec));
+        DaryHeapMut::<_, 3>::new(&mut vec).sort();
+        assert_eq!(vec, (0..50).collect::<Vec<_>>());
+    }
+}
@@ -73,7 +73,6 @@ fn test_into_iter_rev_collect() {
 }
 
 #[test]
-#[cfg(feature = "unstable")]
 fn test_into_iter_sorted_collect() {
     let heap = BinaryHeap::from(vec![2, 4, 6, 2, 1, 8, 10, 3, 5, 7, 0, 9, 1]);
     let it = heap.into_iter_sorted();
@@ -82,7 +81,6 @@ fn test_into_iter_sorted_collect() {
 }
 
 #[test]
-#[cfg(feature = "unstable")]
 fn test_drain_sorted_collect() {
     let mut heap = BinaryHeap::from(vec![2, 4, 6, 2, 1, 8, 10, 3, 5, 7, 0, 9, 1]);
     let it = heap.drain_sorted();
@@ -110,10 +108,8 @@ fn test_exact_size_iterator() {
     let heap = BinaryHeap::from(vec![2, 4, 6, 2, 1, 8, 10, 3, 5, 7, 0, 9, 1]);
     check_exact_size_iterator(heap.len(), heap.iter());
     check_exact_size_iterator(heap.len(), heap.clone().into_iter());
-    #[cfg(feature = "unstable")]
     check_exact_size_iterator(heap.len(), heap.clone().into_iter_sorted());
     check_exact_size_iterator(heap.len(), heap.clone().drain());
-    #[cfg(feature = "unstable")]
     check_exact_size_iterator(heap.len(), heap.clone().drain_sorted());
 }
 
@@ -135,9 +131,7 @@ fn check_trusted_len<I: std::iter::TrustedLen>(len: usize, it: I) {
 fn test_trusted_len() {
     let heap = BinaryHeap::from(vec![2, 4, 6, 2, 1, 8, 10, 3, 5, 7, 0, 9, 1]);
     check_trusted_len(heap.len(), heap.clone().into_vec().into_iter());
-    #[cfg(feature = "unstable")]
     check_trusted_len(heap.len(), heap.clone().into_iter_sorted());
-    #[cfg(feature = "unstable")]
     check_trusted_len(heap.len(), heap.clone().drain_sorted());
 }
 
@@ -197,6 +191,48 @@ fn test_peek_mut_pop() {
     assert_eq!(heap.peek(), Some(&9));
 }
 
+#[test]
+fn test_peek_mut_pop_if() {
+    let data = vec![2, 4, 6, 2, 1, 8, 10, 3, 5, 7, 0, 9, 1];
+    let mut heap = BinaryHeap::from(data);
+    let top = heap.peek_mut().unwrap();
+    assert_eq!(PeekMut::pop_if(top, |top| *top > 10), None);
+    assert_eq!(heap.peek(), Some(&10));
+
+    let top = heap.peek_mut().unwrap();
+    let popped = PeekMut::pop_if(top, |top| {
+        *top -= 5;
+        false
+    });
+    assert_eq!(popped, None);
+    assert_eq!(heap.peek(), Some(&9));
+
+    let top = heap.peek_mut().unwrap();
+    assert_eq!(PeekMut::pop_if(top, |top| *top == 9), Some(9));
+    assert_eq!(heap.len(), 12);
+    assert_eq!(heap.peek(), Some(&8));
+}
+
+#[test]
+fn test_peek_mut_commit_cancel() {
+    let data = vec![2, 4, 6, 2, 1, 8, 10, 3, 5, 7, 0, 9, 1];
+    let mut heap = BinaryHeap::from(data);
+    let mut top = heap.peek_mut().unwrap();
+    *top = 0;
+    PeekMut::commit(top);
+    assert_eq!(heap.peek(), Some(&9));
+
+    let mut top = heap.peek_mut().unwrap();
+    *top = 11;
+    PeekMut::cancel(top);
+    assert_eq!(heap.len(), 13);
+    assert_eq!(heap.peek(), Some(&11));
+    assert_eq!(
+        heap.into_sorted_vec(),
+        [0, 0, 1, 1, 2, 2, 3, 4, 5, 6, 7, 8, 11]
+    );
+}
+
 #[test]
 fn test_push() {
     let mut heap = BinaryHeap::from(vec![2, 4, 9]);
@@ -323,7 +359,6 @@ fn test_drain() {
 }
 
 #[test]
-#[cfg(feature = "unstable")]
 fn test_drain_sorted() {
     let mut q: BinaryHeap<_> = [9, 8, 7, 6, 5, 4, 3, 2, 1].iter().cloned().collect();
 
@@ -336,7 +371,6 @@ fn test_drain_sorted() {
 }
 
 #[test]
-#[cfg(feature = "unstable")]
 #[cfg_attr(not(panic = "unwind"), ignore = "test requires unwinding support")]
 fn test_drain_sorted_leak() {
     let d0 = CrashTestDummy::new(0);
@@ -396,7 +430,6 @@ fn test_drain_forget() {
 }
 
 #[test]
-#[cfg(feature = "unstable")]
 fn test_drain_sorted_forget() {
     let a = CrashTestDummy::new(0);
     let b = CrashTestDummy::new(1);
@@ -530,6 +563,37 @@ fn test_retain_catch_unwind() {
     assert_eq!(heap.into_vec(), [2, 1]);
 }
 
+#[test]
+#[cfg_attr(not(panic = "unwind"), ignore = "test requires unwinding support")]
+fn test_as_vec_mut_catch_unwind() {
+    let mut heap = BinaryHeap::from(vec![3, 1, 2]);
+
+    // Breaks the heap property, then unwinds out of the modification.
+    let _ = catch_unwind(AssertUnwindSafe(|| {
+        let mut vec = heap.as_vec_mut();
+        vec[1] = 4;
+        vec.push(5);
+        panic!();
+    }));
+
+    assert!(heap.is_valid());
+    assert_eq!(heap.into_sorted_vec(), [2, 3, 4, 5]);
+}
+
+#[test]
+#[cfg_attr(not(panic = "unwind"), ignore = "test requires unwinding support")]
+fn test_extend_catch_unwind() {
+    let mut heap = BinaryHeap::from(vec![1, 2]);
+
+    // Appends 3, 4 and 5, then unwinds out of extend.
+    let _ = catch_unwind(AssertUnwindSafe(|| {
+        heap.extend((3..10).map(|x| if x == 6 { panic!() } else { x }));
+    }));
+
+    assert!(heap.is_valid());
+    assert_eq!(heap.into_sorted_vec(), [1, 2, 3, 4, 5]);
+}
+
 // old binaryheap failed this test
 //
 // Integrity means that all elements are present after a comparison panics,